@@ -11,8 +11,8 @@ macro_rules! simpleb {
 
 pub fn bench_create(c: &mut Criterion) {
     let mut g = c.benchmark_group("create new");
-    g.bench_function("1B", simpleb!(|| Bitmap::<1>::new()));
-    g.bench_function("16B", simpleb!(|| Bitmap::<128>::new()));
+    g.bench_function("1B", simpleb!(Bitmap::<1>::new));
+    g.bench_function("16B", simpleb!(Bitmap::<128>::new));
     g.finish();
 
     let mut g = c.benchmark_group("create from");
@@ -82,6 +82,13 @@ fn bench_set(c: &mut Criterion) {
             let _ = &mut map.flip_all();
         }),
     );
+    let mut page = Bitmap::<4096>::new();
+    g.bench_function(
+        "flip all 4KB",
+        simpleb!(|| {
+            black_box(&mut page).flip_all();
+        }),
+    );
     g.finish();
 }
 
@@ -92,7 +99,7 @@ fn bench_find(c: &mut Criterion) {
     g.bench_function(
         "at begin",
         simpleb!(|| {
-            let _ = &mut map.find_first_one();
+            black_box(black_box(&map).find_first_one());
         }),
     );
     map.reset(0);
@@ -100,14 +107,22 @@ fn bench_find(c: &mut Criterion) {
     g.bench_function(
         "at middle",
         simpleb!(|| {
-            let _ = &mut map.find_first_one();
+            black_box(black_box(&map).find_first_one());
         }),
     );
     map.reset(64);
     g.bench_function(
         "all 0 (128)",
         simpleb!(|| {
-            let _ = &mut map.find_first_one();
+            black_box(black_box(&map).find_first_one());
+        }),
+    );
+    let mut full = Bitmap::<4096>::new();
+    full.set_all().reset(4096 * 8 - 3);
+    g.bench_function(
+        "0 near end (4KB)",
+        simpleb!(|| {
+            black_box(black_box(&full).find_first_zero());
         }),
     );
     g.finish();
@@ -152,13 +167,34 @@ fn bench_op(c: &mut Criterion) {
     g.bench_function(
         "and= u128",
         simpleb!(|| {
-            let _ = map &= black_box(12348u128);
+            map &= black_box(12348u128);
         }),
     );
     g.bench_function(
         "or= u128",
         simpleb!(|| {
-            let _ = map |= black_box(12348u128);
+            map |= black_box(12348u128);
+        }),
+    );
+    let mut page = Bitmap::<4096>::from_fn(|i| i % 3 == 0);
+    let mask = [0x5a_u8; 4096];
+    let other = Bitmap::<4096>::from_fn(|i| i % 5 == 0);
+    g.bench_function(
+        "and= [u8; 4096]",
+        simpleb!(|| {
+            *black_box(&mut page) &= *black_box(&mask);
+        }),
+    );
+    g.bench_function(
+        "xor= [u8; 4096]",
+        simpleb!(|| {
+            *black_box(&mut page) ^= *black_box(&mask);
+        }),
+    );
+    g.bench_function(
+        "or= map 4KB",
+        simpleb!(|| {
+            *black_box(&mut page) |= black_box(&other);
         }),
     );
     g.finish();
@@ -191,12 +227,98 @@ fn bench_fill(c: &mut Criterion) {
     g.finish();
 }
 
+fn bench_bulk(c: &mut Criterion) {
+    let mut small = newmap!(;128);
+    let other = newmap!(0b_1010; 128);
+    small.set_all().reset(127);
+    let mut g = c.benchmark_group("bulk 128b");
+    g.bench_function(
+        "find first 0",
+        simpleb!(|| {
+            black_box(black_box(&small).find_first_zero());
+        }),
+    );
+    g.bench_function(
+        "flip all",
+        simpleb!(|| {
+            black_box(&mut small).flip_all();
+        }),
+    );
+    g.bench_function(
+        "or= map",
+        simpleb!(|| {
+            *black_box(&mut small) |= black_box(&other);
+        }),
+    );
+    g.finish();
+
+    let mut large = Bitmap::<{ 2 << 20 }>::full_boxed();
+    let other = Bitmap::<{ 2 << 20 }>::new_boxed();
+    large.reset((2 << 23) - 1);
+    let mut g = c.benchmark_group("bulk 2MB");
+    g.bench_function(
+        "find first 0",
+        simpleb!(|| {
+            black_box(black_box(&*large).find_first_zero());
+        }),
+    );
+    let mut hier = HierBitmap::<{ 2 << 20 }, { hier_summary_bytes(2 << 20) }>::new_boxed();
+    hier.set_all().reset((2 << 23) - 1);
+    g.bench_function(
+        "hier find first 0",
+        simpleb!(|| {
+            black_box(black_box(&*hier).find_first_zero());
+        }),
+    );
+    g.bench_function(
+        "set all",
+        simpleb!(|| {
+            black_box(&mut *large).set_all();
+        }),
+    );
+    g.bench_function(
+        "flip all",
+        simpleb!(|| {
+            black_box(&mut *large).flip_all();
+        }),
+    );
+    g.bench_function(
+        "or= map",
+        simpleb!(|| {
+            *black_box(&mut *large) |= black_box(&*other);
+        }),
+    );
+    g.finish();
+}
+
+fn bench_count(c: &mut Criterion) {
+    let mut g = c.benchmark_group("count");
+    let small = Bitmap::<4096>::from_fn(|i| i % 3 == 0);
+    g.bench_function(
+        "4KB",
+        simpleb!(|| {
+            black_box(black_box(&small).count());
+        }),
+    );
+    let mut large = Bitmap::<{ 2 << 20 }>::new_boxed();
+    large.set_range(..(2 << 22));
+    g.bench_function(
+        "2MB",
+        simpleb!(|| {
+            black_box(black_box(&*large).count());
+        }),
+    );
+    g.finish();
+}
+
 criterion_group!(benches, 
   bench_create, 
   bench_set, 
   bench_find, 
   bench_bitref, 
   bench_op, 
-  bench_fill
+  bench_fill,
+  bench_bulk,
+  bench_count
 );
 criterion_main!(benches);
@@ -8,22 +8,26 @@ extern crate alloc;
 extern crate cbitmap;
 
 use cbitmap::bitmap::*;
+use core::marker::PhantomData;
 
 type Cacheline = [u8; 64];
 type Page = [u8; 4096];
 
-/// Must be `repr(C)`, making the bitmap's address fixed.
-#[repr(C)]
 struct CachelineManager<'a> {
-    _bitmap: &'a Bitmap<8>,
-    page: &'a mut Page,
+    /// The allocation status, kept in the first cacheline of the page.
+    bitmap: BitmapMut<'a>,
+    /// The address of the page.
+    page: usize,
+    /// The first line after the bitmap's cacheline.
+    lines: *mut Cacheline,
+    _page: PhantomData<&'a mut Page>,
 }
 
 #[derive(Debug)]
+#[allow(dead_code)]
 enum ManagerError {
     Oor(i128),
     Oom,
-    Realloc,
     Unallocated,
     Unknown,
 }
@@ -36,29 +40,19 @@ impl<'a> CachelineManager<'a> {
     /// As bitmap occupies 1 line, only 63.
     const LINECNT: usize = 63;
 
-    /// Get the ptr of bitmap.
-    unsafe fn get_map_ptr(&self) -> core::ptr::NonNull<Bitmap<8>> {
-        let raw = self.page.as_ptr().cast::<Bitmap<8>>();
-        raw.as_ref().unwrap().into()
-    }
-
-    /// Get the ptr of line array.
-    unsafe fn get_lines_start_ptr(&self) -> core::ptr::NonNull<Cacheline> {
-        let raw = self.page.as_ptr().cast::<Cacheline>().add(1);
-        raw.as_ref().unwrap().into()
-    }
-
     /// Create a manager with a pre allocated `Page`.
     fn new(page: &'a mut Page) -> Self {
+        let base = page.as_mut_ptr();
+        // Only the first 8 bytes of the first line are needed for 64 bits.
+        // The page is borrowed for 'a, and the lines never overlap them.
+        let mut bitmap = unsafe { BitmapMut::from_raw_parts(base, 8) };
         // set the first bit to '1', for bitmap has occupied it.
-        let bitmap = unsafe {
-            let mapptr = page.as_mut_ptr().cast::<Bitmap<8>>();
-            *mapptr = newmap!(0b1; 64);
-            mapptr.as_ref().unwrap()
-        };
+        bitmap.reset_all().set(0);
         Self {
-            _bitmap: bitmap,
-            page,
+            bitmap,
+            page: base as usize,
+            lines: unsafe { base.add(core::mem::size_of::<Cacheline>()).cast::<Cacheline>() },
+            _page: PhantomData,
         }
     }
 
@@ -66,46 +60,30 @@ impl<'a> CachelineManager<'a> {
     ///
     /// # Fails on:
     /// - `Oom`: Out or memory, indicating all the cachelines in the
-    /// manager are allocated.
-    /// - `Reallocate`: Allocating at a cacheline that has already been
-    /// allocated. This is not expected to happen if the implementation
-    /// of `Bitmap` is correct.
+    ///   manager are allocated.
     /// - `Unknown`: Specifically, when the cacheline is not allocated
-    /// but the calculated pointer happens to be `Null`. This is not
-    /// expected to happen.
-    fn allocate(&self) -> Result<&mut Cacheline, ManagerError> {
-        unsafe {
-            let map = self.get_map_ptr().as_mut();
-            let start = self.get_lines_start_ptr().as_ptr();
-            // Cannot find a free line.
-            let idx = map.find_first_zero().ok_or(Oom)?;
-            match map.test(idx) {
-                // Has been allocated.
-                true => Err(Realloc),
-                false => {
-                    map.set(idx);
-                    // Sutract 1 for start is actually at 1
-                    start.add(idx - 1).as_mut().ok_or(Unknown)
-                }
-            }
-        }
+    ///   but the calculated pointer happens to be `Null`. This is not
+    ///   expected to happen.
+    fn allocate(&mut self) -> Result<&'a mut Cacheline, ManagerError> {
+        // Cannot find a free line.
+        let idx = self.bitmap.set_first_zero().ok_or(Oom)?;
+        // Sutract 1 for lines actually start at 1. The bit was 0, so no
+        // one else holds this line.
+        unsafe { self.lines.add(idx - 1).as_mut().ok_or(Unknown) }
     }
 
     /// Deallocate a existing cacheline.
     ///
     /// # Fails on:
     /// - `Unallocated`: Deallocating a cacheline that hasn't been
-    /// allocated previously.
-    fn deallocate(&self, line: &mut Cacheline) -> Result<(), ManagerError> {
+    ///   allocated previously.
+    fn deallocate(&mut self, line: &mut Cacheline) -> Result<(), ManagerError> {
         let idx = self.get_idx(line)?;
-        unsafe {
-            let map = self.get_map_ptr().as_mut();
-            if !map.test(idx) {
-                Err(Unallocated)
-            } else {
-                map.reset(idx);
-                Ok(())
-            }
+        if !self.bitmap.test(idx) {
+            Err(Unallocated)
+        } else {
+            self.bitmap.reset(idx);
+            Ok(())
         }
     }
 
@@ -114,9 +92,9 @@ impl<'a> CachelineManager<'a> {
     ///
     /// # Fails on:
     /// - `Oor(i128)`: Out of range. The `i128` is the raw index, which
-    /// is out of a page's range.
+    ///   is out of a page's range.
     fn get_idx(&self, line: &Cacheline) -> Result<usize, ManagerError> {
-        let page = self.page.as_ptr() as usize;
+        let page = self.page;
         let ptr = line.as_ptr() as usize;
         if ptr < page {
             Err(Oor(
@@ -135,7 +113,7 @@ impl<'a> CachelineManager<'a> {
 
 fn main() {
     let mut page = Box::new([0u8; 4096]);
-    let manager = CachelineManager::new(page.as_mut());
+    let mut manager = CachelineManager::new(page.as_mut());
     let mut lines = vec![];
 
     println!("Allocating 8 cachelines, their indexes:");
@@ -0,0 +1,23 @@
+//! Implementation of [`arbitrary::Arbitrary`] for [`Bitmap`], with the
+//! `arbitrary` feature.
+
+use super::*;
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+impl<'a, const BYTES: usize> Arbitrary<'a> for Bitmap<BYTES> {
+    /// Take the next `BYTES` bytes of the input as the map.
+    ///
+    /// # Errors
+    /// [`arbitrary::Error::NotEnoughData`] if fewer than `BYTES` bytes
+    /// remain.
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let bytes = u.bytes(BYTES)?;
+        let mut map = Bitmap::<BYTES>::new();
+        map.bits.copy_from_slice(bytes);
+        Ok(map)
+    }
+
+    fn size_hint(_depth: usize) -> (usize, Option<usize>) {
+        (BYTES, Some(BYTES))
+    }
+}
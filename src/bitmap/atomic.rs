@@ -0,0 +1,466 @@
+//! A bitmap that can be shared and changed by many threads at once.
+//!
+//! The module is only there on targets with both byte and pointer-sized
+//! atomics, that is, `target_has_atomic = "8"` and
+//! `target_has_atomic = "ptr"`.
+
+use super::*;
+use core::mem::size_of;
+use core::sync::atomic::{AtomicU8, AtomicUsize, Ordering};
+
+/// The bytes in a word of [`AtomicBitmap`].
+const WORD: usize = size_of::<usize>();
+
+/// The bits in a word of [`AtomicBitmap`].
+const WORD_BITS: usize = usize::BITS as usize;
+
+/// A read-modify-write on a word of [`AtomicBitmap`].
+#[derive(Clone, Copy)]
+enum Rmw {
+    Or,
+    And,
+    Xor,
+}
+
+/// A size-fixed bitmap of `BYTES` bytes, which can be changed through a
+/// shared reference, without a lock.
+///
+/// # Layout
+/// The bytes are aligned like a [`usize`] and are grouped into words of
+/// `size_of::<usize>()` bytes. The bit `i` of the word `w` is the bit
+/// `w * usize::BITS + i` of the map, on any endianness. Each full word
+/// is only accessed as an [`AtomicUsize`], so a bit operation is a single
+/// `fetch_or`, `fetch_and` or `fetch_xor` on the word holding the bit,
+/// with the given [`Ordering`].
+///
+/// If `BYTES` is not a multiple of the word size, the last
+/// `BYTES % size_of::<usize>()` bytes form a short tail word, which is only
+/// accessed as [`AtomicU8`]s. A bit operation there is still a single
+/// atomic on its byte, but an operation on the whole tail word, like
+/// [`AtomicBitmap::fetch_or_at()`], is done byte by byte.
+///
+/// Operations on many words, like [`AtomicBitmap::count()`] or
+/// [`AtomicBitmap::load()`], load the words one by one, so they are not a
+/// snapshot if other threads change the map meanwhile.
+///
+/// # Examples
+/// ```
+/// use cbitmap::bitmap::*;
+/// use core::sync::atomic::Ordering;
+///
+/// static SLOTS: AtomicBitmap<8> = AtomicBitmap::new();
+///
+/// assert_eq!(SLOTS.test_and_set(3, Ordering::AcqRel), false);
+/// assert_eq!(SLOTS.test_and_set(3, Ordering::AcqRel), true);
+/// SLOTS.set(4, Ordering::Release);
+/// assert_eq!(SLOTS.count(Ordering::Acquire), 2);
+/// assert_eq!(SLOTS.find_first_zero(Ordering::Acquire), Some(0));
+/// ```
+#[repr(C)]
+pub struct AtomicBitmap<const BYTES: usize> {
+    _align: [AtomicUsize; 0],
+    bits: [AtomicU8; BYTES],
+}
+
+impl<const BYTES: usize> AtomicBitmap<BYTES> {
+    /// Create an `AtomicBitmap<BYTES>` whose flags are all set to 0.
+    /// Usable in const contexts, so the map can be a `static`.
+    pub const fn new() -> Self {
+        Self {
+            _align: [],
+            bits: [const { AtomicU8::new(0) }; BYTES],
+        }
+    }
+
+    /// Create an `AtomicBitmap<BYTES>` holding the bits of `map`.
+    pub fn from_bitmap(map: &Bitmap<BYTES>) -> Self {
+        Self {
+            _align: [],
+            bits: map.bits.map(AtomicU8::new),
+        }
+    }
+
+    /// Load every byte with `order` into a [`Bitmap`].
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    /// use core::sync::atomic::Ordering;
+    ///
+    /// let atomic = AtomicBitmap::from_bitmap(&he_lang!(1 | 9; 16));
+    /// atomic.flip(1, Ordering::Relaxed);
+    /// assert_eq!(atomic.load(Ordering::Relaxed), he_lang!(9; 16));
+    /// ```
+    pub fn load(&self, order: Ordering) -> Bitmap<BYTES> {
+        let mut map = Bitmap::new();
+        for (w, chunk) in map.bits.chunks_mut(WORD).enumerate() {
+            let word = self.__load_word(w, order).to_le_bytes();
+            chunk.copy_from_slice(&word[..chunk.len()]);
+        }
+        map
+    }
+
+    /// Store every word of `map` with `order`.
+    pub fn store(&self, map: &Bitmap<BYTES>, order: Ordering) {
+        for (w, chunk) in map.bits.chunks(WORD).enumerate() {
+            match w < Self::FULL_WORDS {
+                true => {
+                    let word = usize::from_le_bytes(chunk.try_into().unwrap());
+                    self.__word(w).store(word.to_le(), order);
+                }
+                false => {
+                    for (byte, value) in self.bits[w * WORD..].iter().zip(chunk) {
+                        byte.store(*value, order);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Consume the map and give back its bits. No other thread can hold
+    /// it, so this is exact.
+    pub fn into_bitmap(self) -> Bitmap<BYTES> {
+        Bitmap {
+            bits: self.bits.map(AtomicU8::into_inner),
+        }
+    }
+
+    /// Get the bool value of indexed bit, loading its word with `order`.
+    ///
+    /// # Panics
+    /// Panic if `index` is out of range, or if `order` is
+    /// [`Ordering::Release`] or [`Ordering::AcqRel`].
+    pub fn test(&self, index: usize, order: Ordering) -> bool {
+        self.__locate(index, "getting");
+        self.__load_word(index / WORD_BITS, order) & (1 << (index % WORD_BITS)) != 0
+    }
+
+    /// Set the indexed bit to '1', by a `fetch_or` with `order`.
+    ///
+    /// # Return
+    /// `&self`, allowing a call chain.
+    ///
+    /// # Panics
+    /// Panic if `index` is out of range.
+    pub fn set(&self, index: usize, order: Ordering) -> &Self {
+        self.test_and_set(index, order);
+        self
+    }
+
+    /// Set the indexed bit to '0', by a `fetch_and` with `order`.
+    ///
+    /// # Return
+    /// `&self`, allowing a call chain.
+    ///
+    /// # Panics
+    /// Panic if `index` is out of range.
+    pub fn reset(&self, index: usize, order: Ordering) -> &Self {
+        self.test_and_reset(index, order);
+        self
+    }
+
+    /// Flip the indexed bit, by a `fetch_xor` with `order`.
+    ///
+    /// # Return
+    /// `&self`, allowing a call chain.
+    ///
+    /// # Panics
+    /// Panic if `index` is out of range.
+    pub fn flip(&self, index: usize, order: Ordering) -> &Self {
+        self.__rmw_bit(index, "flipping", Rmw::Xor, order);
+        self
+    }
+
+    /// Set the indexed bit to '1' and give its old value, in one
+    /// `fetch_or` with `order`. Of many threads racing on the same '0'
+    /// bit, exactly one sees `false`.
+    ///
+    /// # Panics
+    /// Panic if `index` is out of range.
+    pub fn test_and_set(&self, index: usize, order: Ordering) -> bool {
+        self.__rmw_bit(index, "setting", Rmw::Or, order)
+    }
+
+    /// Set the indexed bit to '0' and give its old value, in one
+    /// `fetch_and` with `order`.
+    ///
+    /// # Panics
+    /// Panic if `index` is out of range.
+    pub fn test_and_reset(&self, index: usize, order: Ordering) -> bool {
+        self.__rmw_bit(index, "resetting", Rmw::And, order)
+    }
+
+    /// Count the '1's, loading the words one by one with `order`.
+    ///
+    /// # Panics
+    /// Panic if `order` is [`Ordering::Release`] or [`Ordering::AcqRel`].
+    pub fn count(&self, order: Ordering) -> usize {
+        (0..Self::WORDS)
+            .map(|w| self.__load_word(w, order).count_ones() as usize)
+            .sum()
+    }
+
+    /// Find the first '0', loading the words one by one with `order`.
+    ///
+    /// # Return
+    /// [`None`] if there is no '0', `Some(usize)` otherwise. The bit may
+    /// be set by another thread right after; use
+    /// [`AtomicBitmap::test_and_set()`] or [`AtomicBitmap::allocate()`] to
+    /// claim it.
+    ///
+    /// # Panics
+    /// Panic if `order` is [`Ordering::Release`] or [`Ordering::AcqRel`].
+    pub fn find_first_zero(&self, order: Ordering) -> Option<usize> {
+        (0..Self::WORDS).find_map(|w| {
+            let word = self.__load_word(w, order) | !Self::__valid(w);
+            (word != usize::MAX).then(|| w * WORD_BITS + word.trailing_ones() as usize)
+        })
+    }
+
+    /// OR `mask` into the indexed word with `order`, and give the old
+    /// word. The bit `i` of `mask` is the bit
+    /// `word_index * usize::BITS + i` of the map.
+    ///
+    /// On a full word this is one `fetch_or`. On the short tail word (see
+    /// [Layout](AtomicBitmap#layout)) it is a `fetch_or` on each of its
+    /// bytes, and the bits of `mask` past the end of the map are ignored.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    /// use core::sync::atomic::Ordering;
+    ///
+    /// let map = AtomicBitmap::<16>::new();
+    /// map.fetch_or_at(1, 0b_101, Ordering::Relaxed);
+    /// assert_eq!(map.test(usize::BITS as usize + 2, Ordering::Relaxed), true);
+    /// ```
+    ///
+    /// # Panics
+    /// Panic if `word_index` is out of range, that is, not less than
+    /// `BYTES.div_ceil(size_of::<usize>())`.
+    pub fn fetch_or_at(&self, word_index: usize, mask: usize, order: Ordering) -> usize {
+        if word_index >= Self::WORDS {
+            panic!("Bitmap: setting out of range");
+        }
+        self.__rmw_word(word_index, Rmw::Or, mask, order)
+    }
+
+    /// AND `mask` into the indexed word with `order`, and give the old
+    /// word, like [`AtomicBitmap::fetch_or_at()`]. The bits of the tail
+    /// word past the end of the map are ignored.
+    ///
+    /// # Panics
+    /// Panic if `word_index` is out of range.
+    pub fn fetch_and_at(&self, word_index: usize, mask: usize, order: Ordering) -> usize {
+        if word_index >= Self::WORDS {
+            panic!("Bitmap: resetting out of range");
+        }
+        self.__rmw_word(word_index, Rmw::And, mask, order)
+    }
+
+    /// OR `other` into the map, word by word, each by a `fetch_or` with
+    /// [`AcqRel`](Ordering::AcqRel). Words of `other` that are all '0' are
+    /// skipped. The whole operation is not atomic: another thread may see
+    /// some words changed and others not yet.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    /// use core::sync::atomic::Ordering;
+    ///
+    /// let map = AtomicBitmap::<4>::from(he_lang!(0; 32));
+    /// map.or_assign_atomic(&he_lang!(7 | 31; 32));
+    /// assert_eq!(map.load(Ordering::Relaxed), he_lang!(0 | 7 | 31; 32));
+    /// ```
+    pub fn or_assign_atomic(&self, other: &Bitmap<BYTES>) {
+        for (w, chunk) in other.bits.chunks(WORD).enumerate() {
+            let mut word = [0u8; WORD];
+            word[..chunk.len()].copy_from_slice(chunk);
+            let mask = usize::from_le_bytes(word);
+            if mask != 0 {
+                self.__rmw_word(w, Rmw::Or, mask, Ordering::AcqRel);
+            }
+        }
+    }
+
+    /// Find a '0' and set it to '1', as one lock-free operation, so that
+    /// no two callers get the same bit. Give the index of the claimed bit.
+    ///
+    /// The words are scanned from the first one with
+    /// [`Relaxed`](Ordering::Relaxed) loads. On a word with a '0', the bit
+    /// is claimed by a `fetch_or` with [`AcqRel`](Ordering::AcqRel), and
+    /// the old word it returns tells whether the bit was still '0'. If
+    /// another thread won it, the next '0' of that old word is tried. The
+    /// acquire pairs with the release in [`AtomicBitmap::release()`], so
+    /// the winner sees everything the last owner of the bit did.
+    ///
+    /// It is lock-free: a retry only happens when another thread has just
+    /// claimed the bit, so some caller always makes progress. It is not
+    /// wait-free, as a caller may keep losing races under contention.
+    ///
+    /// # Return
+    /// [`None`] if no '0' is seen. A bit released behind the scan may be
+    /// missed, so `None` does not mean the map is full at every moment.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let slots = AtomicBitmap::<1>::new();
+    /// assert_eq!(slots.allocate(), Some(0));
+    /// assert_eq!(slots.allocate(), Some(1));
+    /// slots.release(0);
+    /// assert_eq!(slots.allocate(), Some(0));
+    /// ```
+    pub fn allocate(&self) -> Option<usize> {
+        for w in 0..Self::WORDS {
+            let invalid = !Self::__valid(w);
+            let mut seen = self.__load_word(w, Ordering::Relaxed) | invalid;
+            while seen != usize::MAX {
+                let mask = 1usize << seen.trailing_ones();
+                let old = self.__rmw_word(w, Rmw::Or, mask, Ordering::AcqRel);
+                if old & mask == 0 {
+                    return Some(w * WORD_BITS + mask.trailing_zeros() as usize);
+                }
+                seen = old | invalid;
+            }
+        }
+        None
+    }
+
+    /// Set the indexed bit to '0', giving it back to
+    /// [`AtomicBitmap::allocate()`], by a `fetch_and` with
+    /// [`Release`](Ordering::Release).
+    ///
+    /// # Return
+    /// The old value of the bit: `false` means it was not allocated.
+    ///
+    /// # Panics
+    /// Panic if `index` is out of range.
+    pub fn release(&self, index: usize) -> bool {
+        self.test_and_reset(index, Ordering::Release)
+    }
+
+    /// The count of full words.
+    const FULL_WORDS: usize = BYTES / WORD;
+
+    /// The count of words, including the short tail word.
+    const WORDS: usize = BYTES.div_ceil(WORD);
+
+    /// Panic with `verb` if `index` is out of range.
+    #[inline]
+    fn __locate(&self, index: usize, verb: &str) {
+        if __out_bound(BYTES, index) {
+            panic!("Bitmap: {verb} out of range");
+        }
+    }
+
+    /// The bits of the indexed word that are in the map.
+    #[inline]
+    fn __valid(w: usize) -> usize {
+        match w < Self::FULL_WORDS {
+            true => usize::MAX,
+            false => (1usize << ((BYTES % WORD) * 8)) - 1,
+        }
+    }
+
+    /// The indexed full word.
+    #[inline]
+    fn __word(&self, w: usize) -> &AtomicUsize {
+        debug_assert!(w < Self::FULL_WORDS);
+        // SAFETY: the map is aligned like a `usize`, and the word is in
+        // `bits`, whose `AtomicU8`s allow changes through `&self`. A full
+        // word is only ever accessed as an `AtomicUsize`, never as bytes,
+        // so there are no mixed-size atomic accesses.
+        unsafe { AtomicUsize::from_ptr(self.bits.as_ptr().add(w * WORD) as *mut usize) }
+    }
+
+    /// Load the indexed word, in the order of the map's bits.
+    #[inline]
+    fn __load_word(&self, w: usize, order: Ordering) -> usize {
+        match w < Self::FULL_WORDS {
+            true => usize::from_le(self.__word(w).load(order)),
+            false => self.bits[w * WORD..]
+                .iter()
+                .enumerate()
+                .fold(0, |word, (k, byte)| word | (byte.load(order) as usize) << (k * 8)),
+        }
+    }
+
+    /// Apply `rmw` with `mask` to the indexed word, and give the old word,
+    /// in the order of the map's bits. The tail word goes byte by byte.
+    #[inline]
+    fn __rmw_word(&self, w: usize, rmw: Rmw, mask: usize, order: Ordering) -> usize {
+        if w < Self::FULL_WORDS {
+            let word = self.__word(w);
+            let old = match rmw {
+                Rmw::Or => word.fetch_or(mask.to_le(), order),
+                Rmw::And => word.fetch_and(mask.to_le(), order),
+                Rmw::Xor => word.fetch_xor(mask.to_le(), order),
+            };
+            return usize::from_le(old);
+        }
+        let mut old = 0;
+        for (k, byte) in self.bits[w * WORD..].iter().enumerate() {
+            let mask = (mask >> (k * 8)) as u8;
+            let value = match rmw {
+                Rmw::Or => byte.fetch_or(mask, order),
+                Rmw::And => byte.fetch_and(mask, order),
+                Rmw::Xor => byte.fetch_xor(mask, order),
+            };
+            old |= (value as usize) << (k * 8);
+        }
+        old
+    }
+
+    /// Apply `rmw` to the indexed bit alone, and give its old value. In the
+    /// tail word only the byte of the bit is touched.
+    #[inline]
+    fn __rmw_bit(&self, index: usize, verb: &str, rmw: Rmw, order: Ordering) -> bool {
+        self.__locate(index, verb);
+        let (w, bit) = (index / WORD_BITS, index % WORD_BITS);
+        if w < Self::FULL_WORDS {
+            let mask = match rmw {
+                Rmw::And => !(1 << bit),
+                _ => 1 << bit,
+            };
+            return self.__rmw_word(w, rmw, mask, order) & (1 << bit) != 0;
+        }
+        let (byte, bit) = __idx_1dto2d(index);
+        let byte = &self.bits[byte];
+        let old = match rmw {
+            Rmw::Or => byte.fetch_or(1 << bit, order),
+            Rmw::And => byte.fetch_and(!(1 << bit), order),
+            Rmw::Xor => byte.fetch_xor(1 << bit, order),
+        };
+        old & (1 << bit) != 0
+    }
+}
+
+impl<const BYTES: usize> Default for AtomicBitmap<BYTES> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const BYTES: usize> From<Bitmap<BYTES>> for AtomicBitmap<BYTES> {
+    fn from(map: Bitmap<BYTES>) -> Self {
+        Self::from_bitmap(&map)
+    }
+}
+
+impl<const BYTES: usize> From<AtomicBitmap<BYTES>> for Bitmap<BYTES> {
+    fn from(map: AtomicBitmap<BYTES>) -> Self {
+        map.into_bitmap()
+    }
+}
+
+impl<const BYTES: usize> core::fmt::Debug for AtomicBitmap<BYTES> {
+    /// Formats a [`Relaxed`](Ordering::Relaxed) load of the map, like
+    /// [`Debug`] of [`Bitmap`].
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("AtomicBitmap")
+            .field(&self.load(Ordering::Relaxed))
+            .finish()
+    }
+}
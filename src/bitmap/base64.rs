@@ -0,0 +1,127 @@
+//! Base64 encoding of a [`Bitmap`], for embedding it in text.
+
+use super::*;
+use alloc::string::String;
+
+/// The standard alphabet of RFC 4648.
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+impl<const BYTES: usize> Bitmap<BYTES> {
+    /// Encode the bytes of the map as base64, with the standard alphabet
+    /// of RFC 4648 and `'='` padding, so the length of the string is
+    /// always a multiple of 4.
+    ///
+    /// The bytes are in the order of [`Bitmap::to_le_bytes()`]. The
+    /// alphabet has `'+'` and `'/'`, so escape the string before putting
+    /// it in a URL.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// assert_eq!(Bitmap::from_array(*b"foobar").to_base64(), "Zm9vYmFy");
+    /// assert_eq!(Bitmap::from_array(*b"fo").to_base64(), "Zm8=");
+    /// assert_eq!(newmap!(0b_1; 8).to_base64(), "AQ==");
+    /// assert_eq!(newmap!().to_base64(), "");
+    /// ```
+    pub fn to_base64(&self) -> String {
+        let mut base64 = String::with_capacity(BYTES.div_ceil(3) * 4);
+        for chunk in self.bits.chunks(3) {
+            let mut group = [0u8; 4];
+            group[1..=chunk.len()].copy_from_slice(chunk);
+            let group = u32::from_be_bytes(group);
+            for i in 0..4 {
+                if i <= chunk.len() {
+                    let digit = (group >> (18 - 6 * i)) & 0x3f;
+                    base64.push(ALPHABET[digit as usize] as char);
+                } else {
+                    base64.push('=');
+                }
+            }
+        }
+        base64
+    }
+
+    /// Decode a map from base64, as given by [`Bitmap::to_base64()`].
+    ///
+    /// Only the exact output of [`Bitmap::to_base64()`] is accepted: the
+    /// padding is required, and the unused bits of the last digit must
+    /// be '0'. So a map has exactly one base64 string.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// assert_eq!(Bitmap::<1>::from_base64("AQ=="), Ok(newmap!(0b_1; 8)));
+    /// assert_eq!(
+    ///     Bitmap::<1>::from_base64("AQ-="),
+    ///     Err(BitmapError::InvalidDigit { found: '-', index: 2 })
+    /// );
+    /// assert_eq!(
+    ///     Bitmap::<2>::from_base64("AQ=="),
+    ///     Err(BitmapError::LengthMismatch { expected: 2, found: 1 })
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    /// * [`BitmapError::InvalidDigit`] if a character is not in the
+    ///   alphabet, nor padding at the end.
+    /// * [`BitmapError::InvalidEncoding`] if the string is not padded to
+    ///   a multiple of 4 characters, or the unused bits of the last digit
+    ///   are not '0'.
+    /// * [`BitmapError::LengthMismatch`] if the string does not decode to
+    ///   exactly `BYTES` bytes. Both lengths are in bytes.
+    pub fn from_base64(base64: &str) -> Result<Self, BitmapError> {
+        let padding = base64.bytes().rev().take(2).take_while(|&c| c == b'=').count();
+        let digits = &base64[..base64.len() - padding];
+        let invalid = digits
+            .char_indices()
+            .find(|&(_, c)| !c.is_ascii() || __digit(c as u8).is_none());
+        if let Some((index, found)) = invalid {
+            return Err(BitmapError::InvalidDigit { found, index });
+        }
+        let rest = base64.len() % 4;
+        if rest != 0 {
+            return Err(BitmapError::InvalidEncoding {
+                index: base64.len() - rest,
+            });
+        }
+        let found = base64.len() / 4 * 3 - padding;
+        if found != BYTES {
+            return Err(BitmapError::LengthMismatch {
+                expected: BYTES,
+                found,
+            });
+        }
+
+        let digits = digits.as_bytes();
+        let mut map = Self::new();
+        for (i, bytes) in map.bits.chunks_mut(3).enumerate() {
+            let start = i * 4;
+            let group = digits[start..=start + bytes.len()]
+                .iter()
+                .fold(0u32, |group, &c| group << 6 | __digit(c).map_or(0, u32::from));
+            let group = (group << (6 * (3 - bytes.len()))).to_be_bytes();
+            bytes.copy_from_slice(&group[1..=bytes.len()]);
+            if group[bytes.len() + 1..].iter().any(|&byte| byte != 0) {
+                return Err(BitmapError::InvalidEncoding {
+                    index: start + bytes.len(),
+                });
+            }
+        }
+        Ok(map)
+    }
+}
+
+/// The value of a base64 digit `c`, or `None` if it is not in the
+/// alphabet.
+fn __digit(c: u8) -> Option<u8> {
+    match c {
+        b'A'..=b'Z' => Some(c - b'A'),
+        b'a'..=b'z' => Some(c - b'a' + 26),
+        b'0'..=b'9' => Some(c - b'0' + 52),
+        b'+' => Some(62),
+        b'/' => Some(63),
+        _ => None,
+    }
+}
@@ -0,0 +1,148 @@
+//! Implementation of the [`bitset_core::BitSet`] trait for [`Bitmap`],
+//! with the `bitset-core` feature.
+//!
+//! The index conventions are the same in both crates: the bit `i` is the
+//! bit `i % 8`, counted from the LSB, of the byte `i / 8`, like the
+//! `BitSet` of `[u8]`. So `map.bit_test(i)` is `map.test(i)`, and a map
+//! gives the same answers as its bytes through `BitSet`.
+//!
+//! As `BitSet` works on one type, the binary methods take a map of the
+//! same `BYTES`.
+//!
+//! # Examples
+//! ```
+//! use bitset_core::BitSet;
+//! use cbitmap::bitmap::*;
+//!
+//! let mut map = Bitmap::<4>::new();
+//! map.bit_set(3).bit_set(20);
+//! assert_eq!(map.bit_len(), 32);
+//! assert_eq!(map.bit_count(), 2);
+//! assert_eq!(map.bit_test(20), map.test(20));
+//! ```
+
+use super::*;
+use bitset_core::BitSet;
+
+impl<const BYTES: usize> BitSet for Bitmap<BYTES> {
+    #[inline]
+    fn bit_len(&self) -> usize {
+        BYTES * 8
+    }
+
+    #[inline]
+    fn bit_init(&mut self, value: bool) -> &mut Self {
+        self.bits.fill(if value { 0xff } else { 0 });
+        self
+    }
+
+    /// # Panics
+    /// Panic if `bit` is out of range.
+    #[inline]
+    fn bit_test(&self, bit: usize) -> bool {
+        self.test(bit)
+    }
+
+    /// # Panics
+    /// Panic if `bit` is out of range.
+    #[inline]
+    fn bit_set(&mut self, bit: usize) -> &mut Self {
+        self.set(bit)
+    }
+
+    /// # Panics
+    /// Panic if `bit` is out of range.
+    #[inline]
+    fn bit_reset(&mut self, bit: usize) -> &mut Self {
+        self.reset(bit)
+    }
+
+    /// # Panics
+    /// Panic if `bit` is out of range.
+    #[inline]
+    fn bit_flip(&mut self, bit: usize) -> &mut Self {
+        self.flip(bit)
+    }
+
+    /// # Panics
+    /// Panic if `bit` is out of range.
+    #[inline]
+    fn bit_cond(&mut self, bit: usize, value: bool) -> &mut Self {
+        match value {
+            true => self.set(bit),
+            false => self.reset(bit),
+        }
+    }
+
+    #[inline]
+    fn bit_all(&self) -> bool {
+        self.all()
+    }
+
+    #[inline]
+    fn bit_any(&self) -> bool {
+        self.any()
+    }
+
+    #[inline]
+    fn bit_none(&self) -> bool {
+        self.none()
+    }
+
+    #[inline]
+    fn bit_eq(&self, rhs: &Self) -> bool {
+        self == rhs
+    }
+
+    #[inline]
+    fn bit_disjoint(&self, rhs: &Self) -> bool {
+        self.is_disjoint(rhs)
+    }
+
+    #[inline]
+    fn bit_subset(&self, rhs: &Self) -> bool {
+        self.is_subset(rhs)
+    }
+
+    #[inline]
+    fn bit_superset(&self, rhs: &Self) -> bool {
+        self.is_superset(rhs)
+    }
+
+    #[inline]
+    fn bit_or(&mut self, rhs: &Self) -> &mut Self {
+        self.union_with(rhs)
+    }
+
+    #[inline]
+    fn bit_and(&mut self, rhs: &Self) -> &mut Self {
+        self.intersection_with(rhs)
+    }
+
+    #[inline]
+    fn bit_andnot(&mut self, rhs: &Self) -> &mut Self {
+        self.difference_with(rhs)
+    }
+
+    #[inline]
+    fn bit_xor(&mut self, rhs: &Self) -> &mut Self {
+        self.symmetric_difference_with(rhs)
+    }
+
+    #[inline]
+    fn bit_not(&mut self) -> &mut Self {
+        self.flip_all()
+    }
+
+    /// Take the bits of `rhs` where `mask` is '1', and keep the others.
+    #[inline]
+    fn bit_mask(&mut self, rhs: &Self, mask: &Self) -> &mut Self {
+        self.bits[..].bit_mask(&rhs.bits, &mask.bits);
+        self
+    }
+
+    #[inline]
+    fn bit_count(&self) -> usize {
+        self.count()
+    }
+}
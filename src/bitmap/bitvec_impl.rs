@@ -0,0 +1,110 @@
+//! Conversions between [`Bitmap`], [`DynBitmap`] and the [`bitvec`]
+//! types, with the `bitvec` feature.
+//!
+//! The index order of `cbitmap` is the one of [`Lsb0`] over [`u8`]: the
+//! bit `i` is the bit `i % 8`, counted from the LSB, of the byte `i / 8`.
+//! So the bytes are shared as they are, and a map can be viewed as a
+//! [`BitSlice<u8, Lsb0>`] without copying.
+//!
+//! Linking `bitvec` brings its `PartialEq<BitRef> for bool` impl into
+//! scope, so `Vec<bool> == vec![]` stops inferring the element type in
+//! crates enabling this feature. That comes from `bitvec` itself, not
+//! from the impls here.
+
+use super::*;
+use bitvec::order::Lsb0;
+use bitvec::slice::BitSlice;
+use bitvec::vec::BitVec;
+
+impl<const BYTES: usize> Bitmap<BYTES> {
+    /// View the map as a [`BitSlice<u8, Lsb0>`] of `BYTES * 8` bits,
+    /// without copying. The index `i` is the same bit in both.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let map = he_lang!(3 | 12; 16);
+    /// let bits = map.as_bitslice();
+    /// assert_eq!(bits.len(), 16);
+    /// assert_eq!(bits.iter_ones().collect::<Vec<_>>(), [3, 12]);
+    /// ```
+    pub fn as_bitslice(&self) -> &BitSlice<u8, Lsb0> {
+        BitSlice::from_slice(&self.bits)
+    }
+
+    /// Convert a [`BitSlice<u8, Lsb0>`] into `Bitmap<BYTES>`, keeping the
+    /// index of every bit. Like `TryFrom<&[bool]>`, the bits past the end
+    /// of the slice are '0'.
+    ///
+    /// # Examples
+    /// ```
+    /// use bitvec::prelude::*;
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let bits = bits![u8, Lsb0; 0, 1, 0, 1];
+    /// assert_eq!(Bitmap::<1>::from_bitslice(bits), Ok(he_lang!(1 | 3; 8)));
+    /// ```
+    ///
+    /// # Errors
+    /// [`BitmapError::LengthMismatch`] if the slice is longer than
+    /// `BYTES * 8`.
+    pub fn from_bitslice(bits: &BitSlice<u8, Lsb0>) -> Result<Self, BitmapError> {
+        if bits.len() > BYTES * 8 {
+            return Err(BitmapError::LengthMismatch {
+                expected: BYTES * 8,
+                found: bits.len(),
+            });
+        }
+        let mut map = Self::new();
+        map.as_bitslice_mut()[..bits.len()].copy_from_bitslice(bits);
+        Ok(map)
+    }
+
+    fn as_bitslice_mut(&mut self) -> &mut BitSlice<u8, Lsb0> {
+        BitSlice::from_slice_mut(&mut self.bits)
+    }
+}
+
+impl<const BYTES: usize> From<&Bitmap<BYTES>> for BitVec<u8, Lsb0> {
+    /// Copy a map into a [`BitVec`] of `BYTES * 8` bits.
+    fn from(map: &Bitmap<BYTES>) -> Self {
+        BitVec::from_slice(&map.bits)
+    }
+}
+
+impl DynBitmap {
+    /// View the map as a [`BitSlice<u8, Lsb0>`] of exactly `bit_len()`
+    /// bits, without copying.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let mut map = DynBitmap::new(13);
+    /// map.set(12);
+    /// assert_eq!(map.as_bitslice().len(), 13);
+    /// assert_eq!(map.as_bitslice().last_one(), Some(12));
+    /// ```
+    pub fn as_bitslice(&self) -> &BitSlice<u8, Lsb0> {
+        &BitSlice::from_slice(self.as_bytes())[..self.bit_len()]
+    }
+}
+
+impl From<&DynBitmap> for BitVec<u8, Lsb0> {
+    /// Copy a map into a [`BitVec`] of `bit_len()` bits.
+    fn from(map: &DynBitmap) -> Self {
+        map.as_bitslice().to_bitvec()
+    }
+}
+
+impl From<&BitSlice<u8, Lsb0>> for DynBitmap {
+    /// Copy a [`BitSlice<u8, Lsb0>`] into a map of the same length.
+    fn from(bits: &BitSlice<u8, Lsb0>) -> Self {
+        let mut map = DynBitmap::new(bits.len());
+        for index in bits.iter_ones() {
+            map.set(index);
+        }
+        map
+    }
+}
@@ -0,0 +1,169 @@
+//! Implementations of copying bits between bitmaps of different
+//! lengths, like [`Bitmap::concat()`].
+
+use super::*;
+
+impl<const BYTES: usize> Bitmap<BYTES> {
+    /// Join `self` and `other` into a larger map, with `self` in the low
+    /// bytes and `other` above it.
+    ///
+    /// The bit `i` of `self` is the bit `i` of the result, and the bit
+    /// `i` of `other` is the bit `BYTES * 8 + i` of the result.
+    ///
+    /// # Generics
+    /// * `OUT`: the byte length of the result, must be `BYTES + M`.
+    /// * `M`: the byte length of `other`.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let header = newmap!(0b_1; 8);
+    /// let body = newmap!(0b_10; 16);
+    /// let map = header.concat::<3, 2>(&body);
+    /// assert_eq!(map, he_lang!(0 | 9; 24));
+    /// ```
+    ///
+    /// # Panics
+    /// Panic if `OUT != BYTES + M`.
+    pub fn concat<const OUT: usize, const M: usize>(&self, other: &Bitmap<M>) -> Bitmap<OUT> {
+        if OUT != BYTES + M {
+            panic!("Bitmap: concatenating into a wrong length");
+        }
+        let mut res = Bitmap::<OUT>::new();
+        res.bits[..BYTES].copy_from_slice(&self.bits);
+        res.bits[BYTES..].copy_from_slice(&other.bits);
+        res
+    }
+
+    /// Split the map into two smaller maps: the low `A` bytes and the
+    /// high `B` bytes. The inverse of [`Bitmap::concat()`].
+    ///
+    /// The bit `i` of the first map is the bit `i` of `self`, and the
+    /// bit `i` of the second map is the bit `A * 8 + i` of `self`.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let map = he_lang!(0 | 9; 24);
+    /// let (lo, hi) = map.split_at_byte::<1, 2>();
+    /// assert_eq!(lo, newmap!(0b_1; 8));
+    /// assert_eq!(hi, newmap!(0b_10; 16));
+    /// ```
+    ///
+    /// # Panics
+    /// Panic if `A + B != BYTES`.
+    pub fn split_at_byte<const A: usize, const B: usize>(&self) -> (Bitmap<A>, Bitmap<B>) {
+        if A + B != BYTES {
+            panic!("Bitmap: splitting into wrong lengths");
+        }
+        let mut lo = Bitmap::<A>::new();
+        let mut hi = Bitmap::<B>::new();
+        lo.bits.copy_from_slice(&self.bits[..A]);
+        hi.bits.copy_from_slice(&self.bits[A..]);
+        (lo, hi)
+    }
+
+    /// Copy the map into a map of another length. Growing fills the
+    /// new high bytes with '0', and shrinking drops the high bytes.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let map = he_lang!(1 | 12; 16);
+    /// let large = map.resize::<8>();
+    /// assert_eq!(large, he_lang!(1 | 12; 64));
+    /// let small = map.resize::<1>();
+    /// assert_eq!(small, he_lang!(1; 8));
+    /// ```
+    pub fn resize<const NEW: usize>(&self) -> Bitmap<NEW> {
+        let size = NEW.min(BYTES);
+        let mut res = Bitmap::<NEW>::new();
+        res.bits[..size].copy_from_slice(&self.bits[..size]);
+        res
+    }
+
+    /// Like [`Bitmap::resize()`], but only if no '1' is dropped.
+    ///
+    /// # Return
+    /// [`None`] if any dropped byte holds a '1', otherwise
+    /// `Some(Bitmap<NEW>)`.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let map = he_lang!(1 | 12; 16);
+    /// assert!(map.checked_resize::<1>().is_none());
+    /// assert!(map.checked_resize::<2>().is_some());
+    /// ```
+    pub fn checked_resize<const NEW: usize>(&self) -> Option<Bitmap<NEW>> {
+        match self.bits[NEW.min(BYTES)..].iter().all(|&b| b == 0) {
+            true => Some(self.resize()),
+            false => None,
+        }
+    }
+
+    /// Copy `len` bits of `src` starting at `src_start` into `self`
+    /// starting at `dst_start`. The other bits of `self` are kept.
+    ///
+    /// The offsets need not share the same alignment inside a byte:
+    /// the bits are shifted into place and the edges are masked.
+    ///
+    /// # Return
+    /// `&mut self`, allowing a call chain.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let src = newmap!(0b_1011_0000; 8);
+    /// let mut dst = newmap!(0b_1; 16);
+    /// dst.copy_bits_from(&src, 4, 10, 4);
+    /// assert_eq!(dst, he_lang!(0 | 10 | 11 | 13; 16));
+    /// ```
+    ///
+    /// # Panics
+    /// Panic if either range exceeds its map.
+    pub fn copy_bits_from<const M: usize>(
+        &mut self,
+        src: &Bitmap<M>,
+        src_start: usize,
+        dst_start: usize,
+        len: usize,
+    ) -> &mut Self {
+        let src_end = src_start.checked_add(len);
+        let dst_end = dst_start.checked_add(len);
+        match (src_end, dst_end) {
+            (Some(s), Some(d)) if s <= M * 8 && d <= BYTES * 8 => {}
+            _ => panic!("Bitmap: copying out of range"),
+        }
+        let (mut from, mut to) = (src_start, dst_start);
+        let end = dst_start + len;
+        while to < end {
+            // Fill the destination one byte at a time; inside the range,
+            // every chunk but the first and last is a whole byte.
+            let (byte, bit) = __idx_1dto2d(to);
+            let n = (8 - bit).min(end - to);
+            let mask = (((1u16 << n) - 1) as u8) << bit;
+            let value = src.__get_bits_u8(from) << bit;
+            let dst = self.__get_mut_u8(byte);
+            *dst = (*dst & !mask) | (value & mask);
+            from += n;
+            to += n;
+        }
+        self
+    }
+
+    /// Read 8 bits starting at `start`, filling '0' past the end.
+    fn __get_bits_u8(&self, start: usize) -> u8 {
+        let (byte, bit) = __idx_1dto2d(start);
+        let lo = self.__copy_u8(byte) >> bit;
+        match bit != 0 && byte + 1 < BYTES {
+            true => lo | (self.__copy_u8(byte + 1) << (8 - bit)),
+            false => lo,
+        }
+    }
+}
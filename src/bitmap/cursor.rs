@@ -0,0 +1,120 @@
+//! Next-fit allocation over a bitmap.
+
+use super::*;
+
+/// A next-fit allocator over a borrowed [`Bitmap`]: each '0' is a free
+/// slot, and [`BitmapCursor::alloc()`] searches from where the last one
+/// stopped instead of from bit 0, so it does not rescan the filled low
+/// bits again and again.
+///
+/// It is not thread-safe: it holds the map by `&mut`, so only one thread
+/// can allocate at a time. To allocate from many threads at once, use
+/// [`AtomicBitmap::allocate()`] instead.
+///
+/// # Examples
+/// ```
+/// use cbitmap::bitmap::*;
+///
+/// let mut map = newmap!(;16);
+/// let mut slots = BitmapCursor::new(&mut map);
+/// assert_eq!(slots.alloc(), Some(0));
+/// assert_eq!(slots.alloc(), Some(1));
+/// slots.free(0, false);
+/// // The search goes on from 2, not back to the freed 0.
+/// assert_eq!(slots.alloc(), Some(2));
+/// ```
+#[derive(Debug)]
+pub struct BitmapCursor<'a, const BYTES: usize> {
+    map: &'a mut Bitmap<BYTES>,
+    position: usize,
+}
+
+impl<'a, const BYTES: usize> BitmapCursor<'a, BYTES> {
+    /// Allocate from `map`, starting at bit 0.
+    pub fn new(map: &'a mut Bitmap<BYTES>) -> Self {
+        Self { map, position: 0 }
+    }
+
+    /// Get the index where the next search starts.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Move the cursor, so that the next search starts at `index`.
+    ///
+    /// # Return
+    /// `&mut self`, allowing a call chain.
+    ///
+    /// # Panics
+    /// Panic if `index` is out of range.
+    pub fn seek(&mut self, index: usize) -> &mut Self {
+        if __out_bound(BYTES, index) {
+            panic!("Bitmap: seeking out of range");
+        }
+        self.position = index;
+        self
+    }
+
+    /// Get the map.
+    pub fn as_bitmap(&self) -> &Bitmap<BYTES> {
+        self.map
+    }
+
+    /// Find a '0' at or after the cursor, wrapping around to bit 0 once,
+    /// and set it to '1'. The cursor moves just past it.
+    ///
+    /// # Return
+    /// The index of the allocated bit, or [`None`] if the map is full.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let mut map = he_lang!(1 | 2; 8);
+    /// let mut slots = BitmapCursor::new(&mut map);
+    /// slots.seek(6);
+    /// assert_eq!(slots.alloc(), Some(6));
+    /// assert_eq!(slots.alloc(), Some(7));
+    /// // Wrap around.
+    /// assert_eq!(slots.alloc(), Some(0));
+    /// assert_eq!(slots.alloc(), Some(3));
+    /// ```
+    pub fn alloc(&mut self) -> Option<usize> {
+        let index = __find_first_bit_wrapping(&self.map.bits, false, self.position)?;
+        self.map.set(index);
+        self.position = (index + 1) % (BYTES * 8);
+        Some(index)
+    }
+
+    /// Set the indexed bit to '0', making it free again. If `rewind` is
+    /// `true` and the bit is before the cursor, the cursor moves back to
+    /// it, so that it is the next one allocated.
+    ///
+    /// # Return
+    /// The old value of the bit: `false` means it was not allocated.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let mut map = newmap!(;8);
+    /// let mut slots = BitmapCursor::new(&mut map);
+    /// let slot = slots.alloc().unwrap();
+    /// assert_eq!(slots.free(slot, true), true);
+    /// assert_eq!(slots.alloc(), Some(slot));
+    /// ```
+    ///
+    /// # Panics
+    /// Panic if `index` is out of range.
+    pub fn free(&mut self, index: usize, rewind: bool) -> bool {
+        if __out_bound(BYTES, index) {
+            panic!("Bitmap: resetting out of range");
+        }
+        let old = self.map.get_bool(index);
+        self.map.reset(index);
+        if rewind && index < self.position {
+            self.position = index;
+        }
+        old
+    }
+}
@@ -0,0 +1,583 @@
+//! A bitmap on the heap whose length is chosen at runtime.
+
+use super::fmt::DebugBits;
+use super::view::__out_of_range;
+use super::*;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not};
+
+/// A bitmap of `bit_len()` bits on the heap, for sizes only known at
+/// runtime, like the size of a pool read from a config file. It has the
+/// same layout as [`Bitmap`]: the bit `i` is the bit `i % 8` of the byte
+/// `i / 8`.
+///
+/// Unlike [`Bitmap`], the length need not be a multiple of 8. The bits of
+/// the last byte beyond `bit_len()` are always '0', and are never seen by
+/// any method.
+///
+/// The map can also grow and shrink, by [`DynBitmap::push_bit()`],
+/// [`DynBitmap::resize_bits()`] and [`DynBitmap::truncate_bits()`]. Like a
+/// [`Vec`], its buffer grows with an amortized cost, so its capacity may
+/// be larger than its length.
+///
+/// # Examples
+/// ```
+/// use cbitmap::bitmap::*;
+///
+/// let mut map = DynBitmap::new(1000);
+/// assert_eq!(map.bit_len(), 1000);
+/// map.set(3).set(999);
+/// assert_eq!(map.count(), 2);
+/// assert_eq!(map.iter_ones().collect::<Vec<_>>(), [3, 999]);
+/// map.set_all();
+/// assert_eq!(map.count(), 1000);
+/// assert_eq!(map.find_first_zero(), None);
+/// ```
+#[derive(Clone, PartialEq, Eq, Default)]
+pub struct DynBitmap {
+    bits: Vec<u8>,
+    len: usize,
+}
+
+impl DynBitmap {
+    /// Create a map of `bits` bits, all set to '0'.
+    pub fn new(bits: usize) -> Self {
+        Self {
+            bits: alloc::vec![0u8; bits.div_ceil(8)],
+            len: bits,
+        }
+    }
+
+    /// Create a map of `bits` bits, all set to '1'.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let map = DynBitmap::full(13);
+    /// assert_eq!(map.count(), 13);
+    /// assert_eq!(map.as_bytes(), [0xff, 0b_1_1111]);
+    /// ```
+    pub fn full(bits: usize) -> Self {
+        let mut map = Self::new(bits);
+        map.set_all();
+        map
+    }
+
+    /// Create an empty map with room for at least `bits` bits, so that
+    /// growing up to `bits` does not reallocate.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let map = DynBitmap::with_capacity(100);
+    /// assert_eq!(map.len(), 0);
+    /// assert!(map.capacity() >= 100);
+    /// ```
+    pub fn with_capacity(bits: usize) -> Self {
+        Self {
+            bits: Vec::with_capacity(bits.div_ceil(8)),
+            len: 0,
+        }
+    }
+
+    /// Get the length of the map in bits.
+    #[inline]
+    pub fn bit_len(&self) -> usize {
+        self.len
+    }
+
+    /// Get the length of the map in bits, the same as
+    /// [`DynBitmap::bit_len()`].
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// `true` iff the map has no bits.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Get how many bits the map can hold without reallocating.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.bits.capacity() * 8
+    }
+
+    /// Append a bit to the end of the map.
+    ///
+    /// # Return
+    /// `&mut self`, allowing a call chain.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let mut seen = DynBitmap::new(0);
+    /// seen.push_bit(true).push_bit(false).push_bit(true);
+    /// assert_eq!(seen.len(), 3);
+    /// assert_eq!(seen.iter_ones().collect::<Vec<_>>(), [0, 2]);
+    /// ```
+    pub fn push_bit(&mut self, value: bool) -> &mut Self {
+        let (byte, bit) = __idx_1dto2d(self.len);
+        if bit == 0 {
+            self.bits.push(0);
+        }
+        self.bits[byte] |= (value as u8) << bit;
+        self.len += 1;
+        self
+    }
+
+    /// Resize the map to `new_len` bits. The new bits, if any, are set to
+    /// `value`; the bits beyond `new_len`, if any, are dropped.
+    ///
+    /// # Return
+    /// `&mut self`, allowing a call chain.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let mut map = DynBitmap::new(4);
+    /// map.resize_bits(10, true);
+    /// assert_eq!(&map.range_to_string(0, 10).unwrap(), "11 11110000");
+    /// map.resize_bits(6, false);
+    /// assert_eq!(map.count(), 2);
+    /// ```
+    pub fn resize_bits(&mut self, new_len: usize, value: bool) -> &mut Self {
+        if new_len <= self.len {
+            return self.truncate_bits(new_len);
+        }
+        let old = self.len;
+        self.bits.resize(new_len.div_ceil(8), 0);
+        self.len = new_len;
+        if value {
+            __set_range(&mut self.bits, old, new_len);
+        }
+        self
+    }
+
+    /// Shorten the map to `len` bits, dropping the rest. Does nothing if
+    /// the map is not longer than `len`. The capacity is kept.
+    ///
+    /// # Return
+    /// `&mut self`, allowing a call chain.
+    pub fn truncate_bits(&mut self, len: usize) -> &mut Self {
+        if len < self.len {
+            self.bits.truncate(len.div_ceil(8));
+            self.len = len;
+            self.__clear_padding();
+        }
+        self
+    }
+
+    /// Get the length of the map in bytes, including a partly used last
+    /// byte.
+    #[inline]
+    pub fn byte_len(&self) -> usize {
+        self.bits.len()
+    }
+
+    /// Get the bytes of the map. The bits of the last byte beyond
+    /// `bit_len()` are '0'.
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bits
+    }
+
+    /// Get a [`BitmapRef`] view of the bytes of the map.
+    pub fn as_bitmap_ref(&self) -> BitmapRef<'_> {
+        BitmapRef::new(&self.bits)
+    }
+
+    /// Iterate over the indices of the '1's, in ascending order.
+    pub fn iter_ones(&self) -> Ones<'_> {
+        Ones::new(&self.bits)
+    }
+
+    /// Count the '1's in a range of bits, like [`Bitmap::count_range()`].
+    ///
+    /// # Panics
+    /// Panic if the range is reversed or goes out of range.
+    pub fn count_range<R: core::ops::RangeBounds<usize>>(&self, range: R) -> usize {
+        let (start, end) = self.__range(&range, "counting");
+        __count_range(&self.bits, start, end)
+    }
+
+    /// Set a range of bits to '1', like [`Bitmap::set_range()`].
+    ///
+    /// # Return
+    /// `&mut self`, allowing a call chain.
+    ///
+    /// # Panics
+    /// Panic if the range is reversed or goes out of range.
+    pub fn set_range<R: core::ops::RangeBounds<usize>>(&mut self, range: R) -> &mut Self {
+        let (start, end) = self.__range(&range, "setting");
+        __set_range(&mut self.bits, start, end);
+        self
+    }
+
+    /// Set a range of bits to '0', like `set_range(..)` does to '1'.
+    ///
+    /// # Return
+    /// `&mut self`, allowing a call chain.
+    ///
+    /// # Panics
+    /// Panic if the range is reversed or goes out of range.
+    pub fn reset_range<R: core::ops::RangeBounds<usize>>(&mut self, range: R) -> &mut Self {
+        let (start, end) = self.__range(&range, "resetting");
+        __reset_range(&mut self.bits, start, end);
+        self
+    }
+
+    /// Find the last '1', returns its index.
+    ///
+    /// # Return
+    /// [`None`] if there is no '1', `Some(usize)` otherwise.
+    pub fn find_last_one(&self) -> Option<usize> {
+        __find_last_bit(&self.bits, true)
+    }
+
+    /// Find the first '0' and set it to '1', like
+    /// [`Bitmap::set_first_zero()`].
+    ///
+    /// # Return
+    /// The index of the bit, or [`None`] if there is no '0'.
+    pub fn set_first_zero(&mut self) -> Option<usize> {
+        let index = self.find_first_zero()?;
+        self.set(index);
+        Some(index)
+    }
+
+    /// Find the first '1' and set it to '0', like
+    /// [`Bitmap::reset_first_one()`].
+    ///
+    /// # Return
+    /// The index of the bit, or [`None`] if there is no '1'.
+    pub fn reset_first_one(&mut self) -> Option<usize> {
+        __flip_first_bit(&mut self.bits, true)
+    }
+
+    /// Find the first '0' at or after `from`, wrapping around to bit 0
+    /// once, like [`Bitmap::next_zero_wrapping()`].
+    ///
+    /// # Panics
+    /// Panic if `from` is out of range.
+    pub fn next_zero_wrapping(&self, from: usize) -> Option<usize> {
+        self.__check(from, "searching");
+        // The padding bits are '0', so skip a hit on them.
+        match __find_first_bit_from(&self.bits, false, from) {
+            Some(index) if index < self.len => Some(index),
+            _ => __find_first_zero(&self.bits).filter(|&index| index < from),
+        }
+    }
+
+    /// Find the first '1' at or after `from`, wrapping around to bit 0
+    /// once, like [`Bitmap::next_one_wrapping()`].
+    ///
+    /// # Panics
+    /// Panic if `from` is out of range.
+    pub fn next_one_wrapping(&self, from: usize) -> Option<usize> {
+        self.__check(from, "searching");
+        __find_first_bit_wrapping(&self.bits, true, from)
+    }
+
+    /// Format a range of bits `[start, end)` into a [`Option<String>`],
+    /// like [`Bitmap::range_to_string()`].
+    ///
+    /// # Return
+    /// [`None`] if the range is empty or goes out of range.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let mut map = DynBitmap::new(12);
+    /// map.set(0).set(9);
+    /// assert_eq!(&map.range_to_string(0, 12).unwrap(), "0010 00000001");
+    /// ```
+    pub fn range_to_string(&self, start: usize, end: usize) -> Option<String> {
+        if end > self.len {
+            return None;
+        }
+        let mut s = String::new();
+        __write_range(&self.bits, &mut s, start, end, 8, ' ').ok()?;
+        Some(s)
+    }
+
+    /// Panic with `verb` if `index` is out of range.
+    #[inline]
+    fn __check(&self, index: usize, verb: &str) {
+        if index >= self.len {
+            __out_of_range("DynBitmap", verb, index, self.len);
+        }
+    }
+
+    fn __range<R: core::ops::RangeBounds<usize>>(&self, range: &R, verb: &str) -> (usize, usize) {
+        match __range_to_pair(range, self.len) {
+            Some(pair) => pair,
+            None => panic!("DynBitmap: {verb} out of range"),
+        }
+    }
+
+    /// Set the bits of the last byte beyond the length back to '0'.
+    #[inline]
+    fn __clear_padding(&mut self) {
+        let used = __idx_get_bit(self.len);
+        if used != 0 {
+            if let Some(last) = self.bits.last_mut() {
+                *last &= (1u8 << used) - 1;
+            }
+        }
+    }
+
+    /// Panic if `other` has another length.
+    #[inline]
+    fn __check_len(&self, other: &Self) {
+        if self.len != other.len {
+            panic!(
+                "DynBitmap: length mismatch, {} bits and {} bits",
+                self.len, other.len
+            );
+        }
+    }
+}
+
+impl BitsManage for DynBitmap {
+    #[inline]
+    fn count(&self) -> usize {
+        __count_ones(&self.bits)
+    }
+
+    #[inline]
+    fn find_first_one(&self) -> Option<usize> {
+        __find_first_one(&self.bits)
+    }
+
+    /// Find the first '0'. The padding bits are never given.
+    #[inline]
+    fn find_first_zero(&self) -> Option<usize> {
+        __find_first_zero(&self.bits).filter(|&index| index < self.len)
+    }
+
+    /// Get the bool value of indexed bit.
+    ///
+    /// # Panics
+    /// Panic if `index >= bit_len()`.
+    fn get_bool(&self, index: usize) -> bool {
+        self.__check(index, "getting");
+        self.bits[__idx_get_byte(index)] & (1 << __idx_get_bit(index)) != 0
+    }
+
+    /// Set the indexed bit to '1'.
+    ///
+    /// # Panics
+    /// Panic if `index >= bit_len()`.
+    fn set(&mut self, index: usize) -> &mut Self {
+        self.__check(index, "setting");
+        self.bits[__idx_get_byte(index)] |= 1 << __idx_get_bit(index);
+        self
+    }
+
+    /// Set the indexed bit to '0'.
+    ///
+    /// # Panics
+    /// Panic if `index >= bit_len()`.
+    fn reset(&mut self, index: usize) -> &mut Self {
+        self.__check(index, "resetting");
+        self.bits[__idx_get_byte(index)] &= !(1 << __idx_get_bit(index));
+        self
+    }
+
+    /// Flip the indexed bit.
+    ///
+    /// # Panics
+    /// Panic if `index >= bit_len()`.
+    fn flip(&mut self, index: usize) -> &mut Self {
+        self.__check(index, "flipping");
+        self.bits[__idx_get_byte(index)] ^= 1 << __idx_get_bit(index);
+        self
+    }
+
+    fn set_all(&mut self) -> &mut Self {
+        self.bits.fill(0xff);
+        self.__clear_padding();
+        self
+    }
+
+    fn reset_all(&mut self) -> &mut Self {
+        self.bits.fill(0);
+        self
+    }
+
+    fn flip_all(&mut self) -> &mut Self {
+        __flip_all(&mut self.bits);
+        self.__clear_padding();
+        self
+    }
+}
+
+// Bitwise ops with another map of the same length
+
+macro_rules! impl_dyn_op {
+    ($op:ident, $f:ident, $op_assign:ident, $f_assign:ident, $sym:tt) => {
+        impl $op_assign<&DynBitmap> for DynBitmap {
+            /// # Panics
+            /// Panic if the maps have different lengths.
+            fn $f_assign(&mut self, rhs: &DynBitmap) {
+                self.__check_len(rhs);
+                __zip_words_with(&mut self.bits, &rhs.bits, |a, b| a $sym b);
+            }
+        }
+
+        impl $op<&DynBitmap> for &DynBitmap {
+            type Output = DynBitmap;
+
+            /// # Panics
+            /// Panic if the maps have different lengths.
+            fn $f(self, rhs: &DynBitmap) -> DynBitmap {
+                let mut res = self.clone();
+                res.$f_assign(rhs);
+                res
+            }
+        }
+    };
+}
+
+impl_dyn_op!(BitAnd, bitand, BitAndAssign, bitand_assign, &);
+impl_dyn_op!(BitOr, bitor, BitOrAssign, bitor_assign, |);
+impl_dyn_op!(BitXor, bitxor, BitXorAssign, bitxor_assign, ^);
+
+impl Not for &DynBitmap {
+    type Output = DynBitmap;
+
+    /// Give a new map with every bit flipped.
+    fn not(self) -> DynBitmap {
+        let mut res = self.clone();
+        res.flip_all();
+        res
+    }
+}
+
+// Conversions
+
+impl<const BYTES: usize> From<&Bitmap<BYTES>> for DynBitmap {
+    /// Copy a map into a [`DynBitmap`] of `BYTES * 8` bits.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let map = DynBitmap::from(&he_lang!(1 | 14; 16));
+    /// assert_eq!(map.bit_len(), 16);
+    /// assert_eq!(map.iter_ones().collect::<Vec<_>>(), [1, 14]);
+    /// ```
+    fn from(map: &Bitmap<BYTES>) -> Self {
+        Self {
+            bits: map.bits.to_vec(),
+            len: BYTES * 8,
+        }
+    }
+}
+
+impl<const BYTES: usize> From<Bitmap<BYTES>> for DynBitmap {
+    fn from(map: Bitmap<BYTES>) -> Self {
+        Self::from(&map)
+    }
+}
+
+impl<const BYTES: usize> TryFrom<&DynBitmap> for Bitmap<BYTES> {
+    type Error = BitmapError;
+
+    /// Copy a [`DynBitmap`] into a map, which must have exactly the same
+    /// length in bits.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let mut map = DynBitmap::new(16);
+    /// map.set(9);
+    /// assert_eq!(Bitmap::<2>::try_from(&map), Ok(he_lang!(9; 16)));
+    /// assert_eq!(
+    ///     Bitmap::<3>::try_from(&map),
+    ///     Err(BitmapError::LengthMismatch { expected: 24, found: 16 })
+    /// );
+    /// ```
+    fn try_from(map: &DynBitmap) -> Result<Self, Self::Error> {
+        match map.len == BYTES * 8 {
+            true => Ok(Bitmap::from(<[u8; BYTES]>::try_from(&map.bits[..]).unwrap())),
+            false => Err(BitmapError::LengthMismatch {
+                expected: BYTES * 8,
+                found: map.len,
+            }),
+        }
+    }
+}
+
+// Formatting
+
+impl core::fmt::Display for DynBitmap {
+    /// Formats a map like [`Bitmap`] does: the lowest 2 bytes, or all of
+    /// them with `{:#}`, after a `"[N bits]"` bracket. A partly used last
+    /// byte only shows its used bits.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let mut map = DynBitmap::new(20);
+    /// map.set(0).set(8).set(19);
+    /// assert_eq!(format!("{map}"), "[20 bits] ...00000001 00000001");
+    /// assert_eq!(format!("{map:#}"), "[20 bits] 1000 00000001 00000001");
+    /// ```
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "[{} bits] ", self.len)?;
+        let end = match f.alternate() {
+            true => self.len,
+            false => self.len.min(16),
+        };
+        if end < self.len {
+            f.write_str("...")?;
+        }
+        match end {
+            0 => Ok(()),
+            _ => __write_range(&self.bits, f, 0, end, 8, ' '),
+        }
+    }
+}
+
+impl core::fmt::Debug for DynBitmap {
+    /// Formats a map like [`Bitmap`] does, with its byte and bit length,
+    /// and all its bits if the map is at most [`DEBUG_SUMMARY_BYTES`]
+    /// long. A longer map shows a summary of its '1's, and only its 2
+    /// highest and 2 lowest bytes.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let mut map = DynBitmap::new(12);
+    /// map.set(0);
+    /// assert_eq!(
+    ///     format!("{map:?}"),
+    ///     "DynBitmap { #bytes: 2, #bits: 12, bits: \"0000 00000001\" }"
+    /// );
+    /// ```
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let mut debug = f.debug_struct("DynBitmap");
+        debug.field("#bytes", &self.byte_len()).field("#bits", &self.len);
+        if self.byte_len() > DEBUG_SUMMARY_BYTES {
+            debug
+                .field("#ones", &self.count())
+                .field("first_one", &self.find_first_one())
+                .field("last_one", &self.find_last_one());
+        }
+        debug.field("bits", &DebugBits::new(&self.bits, self.len)).finish()
+    }
+}
+
@@ -0,0 +1,45 @@
+//! The error type of fallible conversions, like
+//! [`TryFrom<&[u8]>`](TryFrom) for [`Bitmap`](super::Bitmap).
+
+use core::fmt;
+
+/// Errors given by the fallible conversions of [`Bitmap`](super::Bitmap).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BitmapError {
+    /// The input does not fit the map.
+    ///
+    /// * `expected`: the length the input should have (at most).
+    /// * `found`: the actual length of the input.
+    LengthMismatch { expected: usize, found: usize },
+    /// A character that is not a digit of the expected radix.
+    ///
+    /// * `found`: the character.
+    /// * `index`: its byte index in the input string.
+    InvalidDigit { found: char, index: usize },
+    /// The input is not a valid encoding, like a cut-off run length of
+    /// [`Bitmap::from_rle()`](super::Bitmap::from_rle), or misplaced
+    /// padding of [`Bitmap::from_base64()`](super::Bitmap::from_base64).
+    ///
+    /// * `index`: the byte index in the input where the invalid part
+    ///   starts.
+    InvalidEncoding { index: usize },
+}
+
+impl fmt::Display for BitmapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BitmapError::LengthMismatch { expected, found } => {
+                write!(f, "Bitmap: length mismatch, expected {expected}, found {found}")
+            }
+            BitmapError::InvalidDigit { found, index } => {
+                write!(f, "Bitmap: invalid digit {found:?} at {index}")
+            }
+            BitmapError::InvalidEncoding { index } => {
+                write!(f, "Bitmap: invalid encoding at {index}")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for BitmapError {}
@@ -0,0 +1,70 @@
+//! Conversions between [`Bitmap`] and [`fixedbitset::FixedBitSet`], with
+//! the `fixedbitset` feature.
+//!
+//! Every bit keeps its index: the bit `i` of a map is the bit `i` of the
+//! set, and the other way round.
+
+use super::*;
+use fixedbitset::{Block, FixedBitSet};
+
+/// The bytes in a [`Block`] of a [`FixedBitSet`].
+const BLOCK_BYTES: usize = core::mem::size_of::<Block>();
+
+impl<const BYTES: usize> From<&Bitmap<BYTES>> for FixedBitSet {
+    /// Copy a map into a [`FixedBitSet`] of `BYTES * 8` bits.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    /// use fixedbitset::FixedBitSet;
+    ///
+    /// let set = FixedBitSet::from(&he_lang!(2 | 13; 16));
+    /// assert_eq!(set.len(), 16);
+    /// assert_eq!(set.ones().collect::<Vec<_>>(), [2, 13]);
+    /// ```
+    fn from(map: &Bitmap<BYTES>) -> Self {
+        // A block holds its lowest bits in its first byte, as a map does.
+        let blocks = map.bits.chunks(BLOCK_BYTES).map(|chunk| {
+            let mut block = [0u8; BLOCK_BYTES];
+            block[..chunk.len()].copy_from_slice(chunk);
+            Block::from_le_bytes(block)
+        });
+        FixedBitSet::with_capacity_and_blocks(BYTES * 8, blocks)
+    }
+}
+
+impl<const BYTES: usize> TryFrom<&FixedBitSet> for Bitmap<BYTES> {
+    type Error = BitmapError;
+
+    /// Copy a [`FixedBitSet`] into a map, which must have exactly the same
+    /// length in bits.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    /// use fixedbitset::FixedBitSet;
+    ///
+    /// let mut set = FixedBitSet::with_capacity(16);
+    /// set.insert(9);
+    /// assert_eq!(Bitmap::<2>::try_from(&set), Ok(he_lang!(9; 16)));
+    /// assert!(Bitmap::<1>::try_from(&set).is_err());
+    /// ```
+    ///
+    /// # Errors
+    /// [`BitmapError::LengthMismatch`] if the set is not `BYTES * 8` bits
+    /// long.
+    fn try_from(set: &FixedBitSet) -> Result<Self, Self::Error> {
+        if set.len() != BYTES * 8 {
+            return Err(BitmapError::LengthMismatch {
+                expected: BYTES * 8,
+                found: set.len(),
+            });
+        }
+        let mut map = Self::new();
+        for (chunk, block) in map.bits.chunks_mut(BLOCK_BYTES).zip(set.as_slice()) {
+            let len = chunk.len();
+            chunk.copy_from_slice(&block.to_le_bytes()[..len]);
+        }
+        Ok(map)
+    }
+}
@@ -1,6 +1,10 @@
 //! Implementations of formating methods for `Bitmap`, including [`Debug`].
+//! Also including [`Display`](core::fmt::Display) and [`Debug`] of `BitRef`, `BitRefMut`
+//! and `BitMut`.
 
-use alloc::{string::{String, ToString}, format};
+use core::fmt::Write;
+
+use alloc::{format, string::String};
 
 use crate::bitmap::*;
 
@@ -14,6 +18,10 @@ impl<const BYTES: usize> core::fmt::Display for Bitmap<BYTES> {
     /// like `"[N bits]"`). A space `' '` will be between the bit contents and this
     /// bracket.
     /// 
+    /// The alternate form `{:#}` shows all the bytes instead.
+    ///
+    /// Nothing is allocated: the contents are written right into the formatter.
+    ///
     /// # Examples
     /// ```
     /// use cbitmap::bitmap::*;
@@ -23,47 +31,441 @@ impl<const BYTES: usize> core::fmt::Display for Bitmap<BYTES> {
     /// map.set(8);
     /// let str = &format!("{map}");
     /// assert_eq!(str, "[24 bits] ...00000001 00000001");
+    /// let str = &format!("{map:#}");
+    /// assert_eq!(str, "[24 bits] 00000000 00000001 00000001");
     /// ```
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        let mut contents = String::new();
-        contents.push_str(&format!("[{} bits] ", BYTES * 8));
-        let size = 2.min(BYTES);
+        write!(f, "[{} bits] ", BYTES * 8)?;
+        let size = match f.alternate() {
+            true => BYTES,
+            false => 2.min(BYTES),
+        };
         if BYTES > size {
-            contents.push_str("...")
+            f.write_str("...")?;
         }
         for i in 0..size {
             if i > 0 {
-                contents.push_str(" ");
+                f.write_str(" ")?;
             }
-            contents.push_str(&format!("{:08b}", self.__copy_u8(size - i - 1)));
+            write!(f, "{:08b}", self.__copy_u8(size - i - 1))?;
         }
-        write!(f, "{contents}")
+        Ok(())
+    }
+}
+
+impl<'map, const BYTES: usize> core::fmt::Display for BitRef<'map, BYTES> {
+    /// Formats the referenced bit as `"bit[index] = value"`, where the value
+    /// is `0` or `1`.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let map = newmap!(0b_0100_0000_0000; 16);
+    /// assert_eq!(format!("{}", map.at(10)), "bit[10] = 1");
+    /// ```
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "bit[{}] = {}", self.idx, self.value as u8)
+    }
+}
+
+impl<'map, const BYTES: usize> core::fmt::Debug for BitRef<'map, BYTES> {
+    /// Shows the index and the value of the bit, but not the map.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let map = newmap!(0b_0100_0000_0000; 16);
+    /// assert_eq!(format!("{:?}", map.at(3)), "BitRef { index: 3, value: false }");
+    /// ```
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("BitRef")
+            .field("index", &self.idx)
+            .field("value", &self.value)
+            .finish()
+    }
+}
+
+impl<'map, const BYTES: usize> core::fmt::Display for BitRefMut<'map, BYTES> {
+    /// Formats the referenced bit as `"bit[index] = value"`, where the value
+    /// is `0` or `1`.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let mut map = newmap!(;16);
+    /// let mut bitmut = map.at_mut(10);
+    /// bitmut.set();
+    /// assert_eq!(format!("{bitmut}"), "bit[10] = 1");
+    /// ```
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "bit[{}] = {}", self.idx, self.value as u8)
+    }
+}
+
+impl<'map, const BYTES: usize> core::fmt::Debug for BitRefMut<'map, BYTES> {
+    /// Shows the index and the value of the bit, but not the map.
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("BitRefMut")
+            .field("index", &self.idx)
+            .field("value", &self.value)
+            .finish()
+    }
+}
+
+impl<'a> core::fmt::Display for BitMut<'a> {
+    /// Formats the referenced bit as `"bit[index] = value"`, like
+    /// [`BitRefMut`].
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "bit[{}] = {}", self.idx, self.value as u8)
+    }
+}
+
+impl<'a> core::fmt::Debug for BitMut<'a> {
+    /// Shows the index and the value of the bit, but not the byte.
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("BitMut")
+            .field("index", &self.idx)
+            .field("value", &self.value)
+            .finish()
+    }
+}
+
+impl<const BYTES: usize> core::fmt::Binary for Bitmap<BYTES> {
+    /// Formats all the bits of a bitmap, the highest bit on the left, with
+    /// no separators. `{:#b}` adds a `"0b"` prefix, and the width, fill,
+    /// alignment and `0` flags work like for integers.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let map = he_lang!(0 | 9; 16);
+    /// assert_eq!(format!("{map:b}"), "0000001000000001");
+    /// assert_eq!(format!("{map:#b}"), "0b0000001000000001");
+    /// ```
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.__fmt_radix(f, "0b", BYTES * 8, |f, byte| write!(f, "{byte:08b}"))
+    }
+}
+
+impl<const BYTES: usize> core::fmt::LowerHex for Bitmap<BYTES> {
+    /// Formats all the bytes of a bitmap in hex, the highest byte on the
+    /// left. `{:#x}` adds a `"0x"` prefix, and the width, fill, alignment
+    /// and `0` flags work like for integers.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let map = Bitmap::<3>::from(0x_0a_b0_0c_u32);
+    /// assert_eq!(format!("{map:x}"), "0ab00c");
+    /// assert_eq!(format!("{map:#010x}"), "0x000ab00c");
+    /// ```
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.__fmt_radix(f, "0x", BYTES * 2, |f, byte| write!(f, "{byte:02x}"))
+    }
+}
+
+impl<const BYTES: usize> core::fmt::UpperHex for Bitmap<BYTES> {
+    /// Like [`LowerHex`](core::fmt::LowerHex), with upper-case digits.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let map = Bitmap::<3>::from(0x_0a_b0_0c_u32);
+    /// assert_eq!(format!("{map:#X}"), "0x0AB00C");
+    /// ```
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.__fmt_radix(f, "0x", BYTES * 2, |f, byte| write!(f, "{byte:02X}"))
     }
 }
 
+/// The largest map, in bytes, that [`Debug`] shows in full. Longer maps are
+/// summarized by their popcount, their first and last '1', and their first
+/// and last 2 bytes, so that a large map does not flood a log.
+pub const DEBUG_SUMMARY_BYTES: usize = 16;
+
 impl<const BYTES: usize> core::fmt::Debug for Bitmap<BYTES> {
+    /// Formats a bitmap with its byte and bit length. A map of at most
+    /// [`DEBUG_SUMMARY_BYTES`] bytes shows all its bytes, in a form like
+    /// `Bitmap { #bytes: 2, #bits: 16, bits: "00000001 00000001" }`.
+    ///
+    /// A longer map shows a summary instead, in a form like
+    /// `Bitmap { #bytes: 1024, #bits: 8192, #ones: 2, first_one: Some(0),
+    /// last_one: Some(8191), bits: "10000000 00000000 ... 00000000 00000001" }`,
+    /// where `bits` holds the 2 highest and the 2 lowest bytes.
+    ///
+    /// Nothing is allocated.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let map = he_lang!(0 | 8 | 23; 24);
+    /// assert_eq!(
+    ///     format!("{map:?}"),
+    ///     "Bitmap { #bytes: 3, #bits: 24, bits: \"10000000 00000001 00000001\" }"
+    /// );
+    /// let map = he_lang!(3 | 8000; 8192);
+    /// assert!(format!("{map:?}").contains("#ones: 2, first_one: Some(3), last_one: Some(8000)"));
+    /// ```
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        let mut contents = String::new();
-        let size = 2.min(BYTES);
-        if BYTES > size {
-            contents.push_str("...")
+        let mut debug = f.debug_struct("Bitmap");
+        debug.field("#bytes", &BYTES).field("#bits", &(BYTES * 8));
+        if BYTES > DEBUG_SUMMARY_BYTES {
+            debug
+                .field("#ones", &self.count())
+                .field("first_one", &self.find_first_one())
+                .field("last_one", &self.__find_last_one());
         }
-        for i in 0..size {
-            if i > 0 {
-                contents.push_str(" ");
+        debug.field("bits", &DebugBits::new(&self.bits, BYTES * 8)).finish()
+    }
+}
+
+/// The `bits` field in [`Debug`] of a [`Bitmap`] or a [`DynBitmap`],
+/// written as a quoted string without allocating. A map longer than
+/// [`DEBUG_SUMMARY_BYTES`] only shows its 2 highest and 2 lowest bytes.
+pub(super) struct DebugBits<'map> {
+    bits: &'map [u8],
+    len: usize,
+}
+
+impl<'map> DebugBits<'map> {
+    /// The `bits` field of the first `len` bits of `bits`.
+    pub(super) fn new(bits: &'map [u8], len: usize) -> Self {
+        Self { bits, len }
+    }
+}
+
+impl<'map> core::fmt::Debug for DebugBits<'map> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_char('"')?;
+        let bytes = self.bits.len();
+        if bytes > DEBUG_SUMMARY_BYTES {
+            __write_range(self.bits, f, (bytes - 2) * 8, self.len, 8, ' ')?;
+            f.write_str(" ... ")?;
+            __write_range(self.bits, f, 0, 16, 8, ' ')?;
+        } else if self.len > 0 {
+            __write_range(self.bits, f, 0, self.len, 8, ' ')?;
+        }
+        f.write_char('"')
+    }
+}
+
+/// A configurable [`Display`](core::fmt::Display) of a bitmap, created
+/// by [`Bitmap::display()`].
+///
+/// By default it shows the same as the bitmap's own `Display`: the last 2
+/// bytes, the highest bit on the left, a space between bytes, and a
+/// `"[N bits]"` bracket. Nothing is allocated when formatting.
+///
+/// # Examples
+/// ```
+/// use cbitmap::bitmap::*;
+///
+/// let map = Bitmap::<4>::from(0x_0f_00_01_80_u32);
+/// assert_eq!(format!("{}", map.display()), format!("{map}"));
+/// assert_eq!(
+///     format!("{}", map.display().bytes_shown(4).group(4)),
+///     "[32 bits] 0000 1111 0000 0000 0000 0001 1000 0000"
+/// );
+/// assert_eq!(
+///     format!("{}", map.display().lsb_first(true).group(0)),
+///     "[32 bits] 0000000110000000..."
+/// );
+/// ```
+#[derive(Clone, Copy)]
+pub struct BitmapDisplay<'map, const BYTES: usize> {
+    map: &'map Bitmap<BYTES>,
+    bytes_shown: usize,
+    group: usize,
+    lsb_first: bool,
+    header: bool,
+}
+
+impl<'map, const BYTES: usize> BitmapDisplay<'map, BYTES> {
+    /// Show at most `bytes` bytes, always the lowest ones, like `Display`
+    /// of [`Bitmap`] does. A `"..."` marks the hidden higher ones: on the
+    /// left, or on the right if [`lsb_first`](Self::lsb_first). Use
+    /// [`usize::MAX`] to show the whole map.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let map = Bitmap::<2>::from(0x_8001_u16);
+    /// assert_eq!(format!("{}", map.display().bytes_shown(1)), "[16 bits] ...00000001");
+    /// assert_eq!(
+    ///     format!("{}", map.display().bytes_shown(1).lsb_first(true)),
+    ///     "[16 bits] 10000000..."
+    /// );
+    /// ```
+    pub fn bytes_shown(mut self, bytes: usize) -> Self {
+        self.bytes_shown = bytes;
+        self
+    }
+
+    /// Put a space between every `bits` bits. `0` means no spaces.
+    pub fn group(mut self, bits: usize) -> Self {
+        self.group = bits;
+        self
+    }
+
+    /// Show the bit 0 on the left, and the higher bits on its right.
+    pub fn lsb_first(mut self, lsb_first: bool) -> Self {
+        self.lsb_first = lsb_first;
+        self
+    }
+
+    /// Show the `"[N bits]"` bracket or not.
+    pub fn header(mut self, header: bool) -> Self {
+        self.header = header;
+        self
+    }
+}
+
+impl<'map, const BYTES: usize> core::fmt::Display for BitmapDisplay<'map, BYTES> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if self.header {
+            write!(f, "[{} bits] ", BYTES * 8)?;
+        }
+        let shown = self.bytes_shown.min(BYTES) * 8;
+        let hidden = shown < BYTES * 8;
+        if hidden && !self.lsb_first {
+            f.write_str("...")?;
+        }
+        for k in 0..shown {
+            if k > 0 && self.group > 0 && k % self.group == 0 {
+                f.write_str(" ")?;
             }
-            contents.push_str(&format!("{:08b}", self.__copy_u8(size - i - 1)));
+            let index = match self.lsb_first {
+                true => k,
+                false => shown - 1 - k,
+            };
+            f.write_str(if self.map.get_bool(index) { "1" } else { "0" })?;
+        }
+        if hidden && self.lsb_first {
+            f.write_str("...")?;
         }
+        Ok(())
+    }
+}
 
-        f.debug_struct("Bitmap")
-            .field("#bytes", &BYTES)
-            .field("#bits", &(BYTES * 8))
-            .field("bits", &contents)
-            .finish()
+/// A [`Display`](core::fmt::Display) of the indices of the '1's in a
+/// bitmap, like a set: `"{3, 71, 400}"`. Created by
+/// [`Bitmap::display_ones()`]. Nothing is allocated when formatting.
+///
+/// # Examples
+/// ```
+/// use cbitmap::bitmap::*;
+///
+/// let map = he_lang!(3 | 71 | 400; 512);
+/// assert_eq!(format!("{}", map.display_ones()), "{3, 71, 400}");
+/// ```
+#[derive(Clone, Copy)]
+pub struct OnesDisplay<'map, const BYTES: usize> {
+    map: &'map Bitmap<BYTES>,
+}
+
+impl<'map, const BYTES: usize> core::fmt::Display for OnesDisplay<'map, BYTES> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_char('{')?;
+        let mut first = true;
+        for byte in 0..BYTES {
+            let mut bits = self.map.__copy_u8(byte);
+            while bits != 0 {
+                if !first {
+                    f.write_str(", ")?;
+                }
+                first = false;
+                write!(f, "{}", byte * 8 + bits.trailing_zeros() as usize)?;
+                bits &= bits - 1;
+            }
+        }
+        f.write_char('}')
     }
 }
 
 impl<const BYTES: usize> Bitmap<BYTES> {
+    /// Give a [`BitmapDisplay`], to choose how the map is formatted at
+    /// the call site.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let map = he_lang!(0 | 12; 16);
+    /// let shown = format!("{}", map.display().group(4).header(false));
+    /// assert_eq!(shown, "0001 0000 0000 0001");
+    /// ```
+    pub fn display(&self) -> BitmapDisplay<'_, BYTES> {
+        BitmapDisplay {
+            map: self,
+            bytes_shown: 2,
+            group: 8,
+            lsb_first: false,
+            header: true,
+        }
+    }
+
+    /// Give an [`OnesDisplay`], which shows the indices of the '1's in
+    /// ascending order, like `"{3, 71, 400}"`, without allocating.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let map = he_lang!(0 | 15; 16);
+    /// assert_eq!(format!("{}", map.display_ones()), "{0, 15}");
+    /// assert_eq!(format!("{}", newmap!(;16).display_ones()), "{}");
+    /// ```
+    pub fn display_ones(&self) -> OnesDisplay<'_, BYTES> {
+        OnesDisplay { map: self }
+    }
+
+    /// Give the indices of the '1's in ascending order, like
+    /// `"{3, 71, 400}"`, or `"{}"` if there is no '1'.
+    ///
+    /// # See
+    /// [`Bitmap::display_ones()`], which does not allocate.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let map = he_lang!(3 | 71 | 400; 512);
+    /// assert_eq!(map.indices_to_string(), "{3, 71, 400}");
+    /// ```
+    pub fn indices_to_string(&self) -> String {
+        format!("{}", self.display_ones())
+    }
+
+    /// Format a range of bits into a [`Option<String>`], like
+    /// `format_range(4..10)` or `format_range(..)`. The same as
+    /// [`Bitmap::range_to_string()`], but taking any range of indexes.
+    ///
+    /// # Return
+    /// [`None`] if the range is reversed, empty or out of the bitmap.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let map: Bitmap<2> = 0b_011_11001100.into();
+    /// assert_eq!(&map.format_range(2..11).unwrap(), "011 110011");
+    /// assert_eq!(&map.format_range(..=3).unwrap(), "1100");
+    /// assert!(map.format_range(5..5).is_none());
+    /// assert!(map.format_range(..17).is_none());
+    /// ```
+    pub fn format_range<R: core::ops::RangeBounds<usize>>(&self, range: R) -> Option<String> {
+        let (start, end) = __range_to_pair(&range, BYTES * 8)?;
+        self.range_to_string(start, end)
+    }
+
     /// Format a range of bits into a [`Option<String>`].
     /// 
     /// An array of `'0'`/`'1'` will show in the String. The bits are separated 
@@ -86,35 +488,194 @@ impl<const BYTES: usize> Bitmap<BYTES> {
     /// assert!(map.range_to_string(2, 1).is_none());
     /// ```
     pub fn range_to_string(&self, start: usize, end: usize) -> Option<String> {
-        if start >= end || __out_bound(BYTES, start) || __out_bound(BYTES, end - 1) {
-            return None;
-        }
+        self.range_to_string_with(start, end, 8, ' ')
+    }
+
+    /// Write a range of bits into `w`, giving exactly the same characters
+    /// as [`Bitmap::range_to_string()`], but without allocating.
+    ///
+    /// # Return
+    /// [`Err`] if the range is invalid (out of the bitmap, or length is less
+    /// than 1), in which case nothing is written.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    /// use core::fmt::Write;
+    ///
+    /// let map: Bitmap<2> = 0b_011_11001100.into();
+    /// let mut out = String::new();
+    /// map.write_range(&mut out, 2, 11).unwrap();
+    /// assert_eq!(&out, "011 110011");
+    /// assert!(map.write_range(&mut out, 2, 1).is_err());
+    /// ```
+    pub fn write_range<W: core::fmt::Write>(
+        &self,
+        w: &mut W,
+        start: usize,
+        end: usize,
+    ) -> core::fmt::Result {
+        self.write_range_with(w, start, end, 8, ' ')
+    }
 
+    /// Format a range of bits like [`Bitmap::range_to_string()`], but with
+    /// `sep` at the edge of every `group` bits instead of a `' '` at the
+    /// edge of bytes. The edges are counted from the bit 0, not from
+    /// `start`. `group == 0` means no separators.
+    ///
+    /// # Return
+    /// [`None`] if the range is invalid (out of the bitmap, or length is less
+    /// than 1), `Some(String)` otherwise.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let map: Bitmap<2> = 0b_011_11001100.into();
+    /// let string = map.range_to_string_with(2, 11, 4, '_').unwrap();
+    /// assert_eq!(&string, "011_1100_11");
+    /// let string = map.range_to_string_with(2, 11, 0, '_').unwrap();
+    /// assert_eq!(&string, "011110011");
+    /// ```
+    pub fn range_to_string_with(
+        &self,
+        start: usize,
+        end: usize,
+        group: usize,
+        sep: char,
+    ) -> Option<String> {
         let mut contents = String::new();
-        let mut i = end - 1;
-        while __idx_get_byte(i) > __idx_get_byte(start) {
-            let bit = __idx_get_bit(i);
-            match bit {
-                7 => {
-                    contents.push_str(&format!("{:08b}", self.__copy_u8(__idx_get_byte(i))));
-                    contents.push_str(" ");
-                    i -= 8;
-                }
-                _ => {
-                    contents.push_str(&self.get_01(i).to_string());
-                    if bit == 0 {
-                        contents.push_str(" ");
-                    }
-                    i -= 1;
+        self.write_range_with(&mut contents, start, end, group, sep).ok()?;
+        Some(contents)
+    }
+
+    /// Write a range of bits into `w`, giving exactly the same characters
+    /// as [`Bitmap::range_to_string_with()`], but without allocating.
+    ///
+    /// # Return
+    /// [`Err`] if the range is invalid, in which case nothing is written.
+    pub fn write_range_with<W: core::fmt::Write>(
+        &self,
+        w: &mut W,
+        start: usize,
+        end: usize,
+        group: usize,
+        sep: char,
+    ) -> core::fmt::Result {
+        __write_range(&self.bits, w, start, end, group, sep)
+    }
+}
+
+impl<const BYTES: usize> Bitmap<BYTES> {
+    /// Give all the bytes in lower-case hex, the highest byte first, the
+    /// same as `format!("{map:x}")`.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let map = Bitmap::<3>::from(0x_0a_b0_0c_u32);
+    /// assert_eq!(&map.to_hex_string(), "0ab00c");
+    /// ```
+    pub fn to_hex_string(&self) -> String {
+        format!("{self:x}")
+    }
+
+    /// Parse a hex string, the highest byte first, as given by
+    /// [`Bitmap::to_hex_string()`].
+    ///
+    /// An optional `"0x"` or `"0X"` prefix and any `'_'`s are allowed, and
+    /// digits may be in either case. An odd number of digits is read as if
+    /// there were a leading `'0'`.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let map = Bitmap::<3>::from_hex_str("0x0A_b00c").unwrap();
+    /// assert_eq!(map, Bitmap::<3>::from(0x_0a_b0_0c_u32));
+    /// assert_eq!(Bitmap::<3>::from_hex_str("ab00c"), Ok(map));
+    /// ```
+    ///
+    /// # Errors
+    /// * [`BitmapError::InvalidDigit`] on a character that is not a hex
+    ///   digit or `'_'`.
+    /// * [`BitmapError::LengthMismatch`] if there are not `BYTES * 2`
+    ///   digits (or one fewer). The lengths are in digits.
+    pub fn from_hex_str(s: &str) -> Result<Self, BitmapError> {
+        let (offset, digits) = match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+            Some(rest) => (2, rest),
+            None => (0, s),
+        };
+        let mut map = Self::new();
+        let mut cnt = 0;
+        for (index, c) in digits.char_indices().rev() {
+            if c == '_' {
+                continue;
+            }
+            let nibble = match c.to_digit(16) {
+                Some(nibble) => nibble as u8,
+                None => {
+                    return Err(BitmapError::InvalidDigit {
+                        found: c,
+                        index: offset + index,
+                    })
                 }
+            };
+            if let Some(byte) = map.bits.get_mut(cnt / 2) {
+                *byte |= nibble << (4 * (cnt % 2));
             }
+            cnt += 1;
         }
-        while i > start {
-            contents.push_str(&self.get_01(i).to_string());
-            i -= 1;
+        match cnt.div_ceil(2) == BYTES {
+            true => Ok(map),
+            false => Err(BitmapError::LengthMismatch {
+                expected: BYTES * 2,
+                found: cnt,
+            }),
         }
-        contents.push_str(&self.get_01(i).to_string());
+    }
 
-        Some(contents)
+    /// Write all the bytes, the highest first, padded as an integer of
+    /// `digits` digits would be. Nothing is allocated.
+    fn __fmt_radix<F>(
+        &self,
+        f: &mut core::fmt::Formatter<'_>,
+        prefix: &str,
+        digits: usize,
+        mut write_byte: F,
+    ) -> core::fmt::Result
+    where
+        F: FnMut(&mut core::fmt::Formatter<'_>, u8) -> core::fmt::Result,
+    {
+        use core::fmt::{Alignment, Write};
+
+        let prefix = match f.alternate() {
+            true => prefix,
+            false => "",
+        };
+        let pad = f.width().unwrap_or(0).saturating_sub(prefix.len() + digits);
+        let (before, after, fill) = match (f.sign_aware_zero_pad(), f.align()) {
+            (true, _) => (0, 0, '0'),
+            (false, Some(Alignment::Left)) => (0, pad, f.fill()),
+            (false, Some(Alignment::Center)) => (pad / 2, pad - pad / 2, f.fill()),
+            (false, _) => (pad, 0, f.fill()),
+        };
+        for _ in 0..before {
+            f.write_char(fill)?;
+        }
+        f.write_str(prefix)?;
+        if f.sign_aware_zero_pad() {
+            for _ in 0..pad {
+                f.write_char('0')?;
+            }
+        }
+        for &byte in self.bits.iter().rev() {
+            write_byte(f, byte)?;
+        }
+        for _ in 0..after {
+            f.write_char(fill)?;
+        }
+        Ok(())
     }
-}
\ No newline at end of file
+}
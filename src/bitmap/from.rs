@@ -3,6 +3,7 @@
 //! Allowing converting between them and a set of native types like [`bool`].
 
 use crate::bitmap::*;
+use alloc::vec::Vec;
 
 impl<const BYTES: usize> Default for Bitmap<BYTES> {
     /// Default bitmap. All the bits are set to 0.
@@ -13,35 +14,35 @@ impl<const BYTES: usize> Default for Bitmap<BYTES> {
 
 // Into
 
-impl<const BYTES: usize> Into<[u8; BYTES]> for Bitmap<BYTES> {
+impl<const BYTES: usize> From<Bitmap<BYTES>> for [u8; BYTES] {
     /// Give the inner array of bitmap.
     ///
     /// # See
     /// [`Bitmap`].
-    fn into(self) -> [u8; BYTES] {
+    fn from(map: Bitmap<BYTES>) -> [u8; BYTES] {
         match BYTES == 0 {
             true => [0; BYTES],
-            false => self.bits,
+            false => map.bits,
         }
     }
 }
 
-impl<'map, const BYTES: usize> Into<bool> for BitRef<'map, BYTES> {
+impl<'map, const BYTES: usize> From<BitRef<'map, BYTES>> for bool {
     /// Give the value of the referenced bit.
     ///
     /// # See
     /// [`BitRef`].
-    fn into(self) -> bool {
-        self.value
+    fn from(bit: BitRef<'map, BYTES>) -> bool {
+        bit.value
     }
 }
 
-impl<'map, const BYTES: usize> Into<bool> for BitRefMut<'map, BYTES> {
+impl<'map, const BYTES: usize> From<BitRefMut<'map, BYTES>> for bool {
     /// Give the value of the reverence bit.
     /// # See
     /// [`BitRefMut`].
-    fn into(self) -> bool {
-        self.value
+    fn from(bit: BitRefMut<'map, BYTES>) -> bool {
+        bit.value
     }
 }
 // From
@@ -60,12 +61,18 @@ impl<const BYTES: usize, const N: usize> From<[u8; N]> for Bitmap<BYTES> {
     /// let map = Bitmap::<2>::from([0u8; 2]);
     /// ```
     /// 
-    /// There are also aliases for integer types:
+    /// There are also aliases for integer types. They are little-endian
+    /// on every target: the bit 0 of the map is the LSB of the integer.
     /// ```
     /// use cbitmap::bitmap::*;
     /// 
     /// let map = Bitmap::<1>::from(0u8);
+    /// let map = Bitmap::<4>::from(1u32);
+    /// assert_eq!(map.find_first_one(), Some(0));
     /// ```
+    ///
+    /// # See
+    /// [`Bitmap::from_be()`] for the big-endian order.
     fn from(value: [u8; N]) -> Self {
         match BYTES == 0 {
             true => Bitmap::<BYTES>::new(),
@@ -76,12 +83,224 @@ impl<const BYTES: usize, const N: usize> From<[u8; N]> for Bitmap<BYTES> {
     }
 }
 
+impl<const BYTES: usize> TryFrom<&[u8]> for Bitmap<BYTES> {
+    type Error = BitmapError;
+
+    /// Convert a slice of [`u8`] into `Bitmap<BYTES>`, like
+    /// [`From<[u8; N]>`](From) does for arrays.
+    ///
+    /// The slice may be shorter than `BYTES`, then the high bytes of the
+    /// map are '0'.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let bytes: &[u8] = &[0b_1, 0b_10];
+    /// let map = Bitmap::<4>::try_from(bytes).unwrap();
+    /// assert_eq!(map, he_lang!(0 | 9; 32));
+    /// assert!(Bitmap::<1>::try_from(bytes).is_err());
+    /// ```
+    ///
+    /// # Errors
+    /// [`BitmapError::LengthMismatch`] if the slice is longer than `BYTES`.
+    ///
+    /// # See
+    /// [`Bitmap::from_slice_exact()`].
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        if value.len() > BYTES {
+            return Err(BitmapError::LengthMismatch {
+                expected: BYTES,
+                found: value.len(),
+            });
+        }
+        let mut map = Bitmap::<BYTES>::new();
+        map.bits[..value.len()].copy_from_slice(value);
+        Ok(map)
+    }
+}
+
+impl<const BYTES: usize> Bitmap<BYTES> {
+    /// Convert a slice of exactly `BYTES` [`u8`] into `Bitmap<BYTES>`.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let bytes: &[u8] = &[0b_1, 0b_10];
+    /// assert!(Bitmap::<2>::from_slice_exact(bytes).is_ok());
+    /// assert!(Bitmap::<4>::from_slice_exact(bytes).is_err());
+    /// ```
+    ///
+    /// # Errors
+    /// [`BitmapError::LengthMismatch`] if `value.len() != BYTES`.
+    pub fn from_slice_exact(value: &[u8]) -> Result<Self, BitmapError> {
+        match value.len() == BYTES {
+            true => Self::try_from(value),
+            false => Err(BitmapError::LengthMismatch {
+                expected: BYTES,
+                found: value.len(),
+            }),
+        }
+    }
+
+    /// Give the bits of the map as an array of [`bool`], where the
+    /// element `i` is [`get_bool(i)`](BitsManage::get_bool).
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let map = he_lang!(0 | 2; 8);
+    /// let flags: [bool; 8] = map.to_bool_array();
+    /// assert_eq!(flags[..3], [true, false, true]);
+    /// ```
+    ///
+    /// # Panics
+    /// Panic if `N != BYTES * 8`.
+    pub fn to_bool_array<const N: usize>(&self) -> [bool; N] {
+        if N != BYTES * 8 {
+            panic!("Bitmap: converting into a wrong length");
+        }
+        core::array::from_fn(|i| self.get_bool(i))
+    }
+
+    /// Give the bits of the map as a [`Vec`] of [`bool`], where the
+    /// element `i` is [`get_bool(i)`](BitsManage::get_bool).
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let map = he_lang!(0 | 2; 8);
+    /// assert_eq!(map.to_bool_vec()[..3], [true, false, true]);
+    /// ```
+    pub fn to_bool_vec(&self) -> Vec<bool> {
+        (0..BYTES * 8).map(|i| self.get_bool(i)).collect()
+    }
+}
+
+impl<const BYTES: usize> core::str::FromStr for Bitmap<BYTES> {
+    type Err = BitmapError;
+
+    /// Parse a binary string, the leftmost digit being the highest bit.
+    /// `'_'`s and whitespaces are ignored, so the output of
+    /// `range_to_string(0, bit_len())` can be parsed back.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let map: Bitmap<2> = "0110_1000 0000_0001".parse().unwrap();
+    /// assert_eq!(map, he_lang!(0 | 11 | 13 | 14; 16));
+    /// ```
+    ///
+    /// # Errors
+    /// * [`BitmapError::InvalidDigit`] on a character other than `'0'`,
+    ///   `'1'`, `'_'` or a whitespace.
+    /// * [`BitmapError::LengthMismatch`] if there are not exactly
+    ///   `BYTES * 8` digits.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut map = Bitmap::<BYTES>::new();
+        let mut cnt = 0;
+        for (index, c) in s.char_indices().rev() {
+            match c {
+                '0' | '1' => {
+                    if c == '1' && cnt < BYTES * 8 {
+                        map.set(cnt);
+                    }
+                    cnt += 1;
+                }
+                '_' => {}
+                c if c.is_whitespace() => {}
+                found => return Err(BitmapError::InvalidDigit { found, index }),
+            }
+        }
+        match cnt == BYTES * 8 {
+            true => Ok(map),
+            false => Err(BitmapError::LengthMismatch {
+                expected: BYTES * 8,
+                found: cnt,
+            }),
+        }
+    }
+}
+
+impl<const BYTES: usize> TryFrom<&[bool]> for Bitmap<BYTES> {
+    type Error = BitmapError;
+
+    /// Convert a slice of [`bool`] into `Bitmap<BYTES>`. The bit `i` of
+    /// the map takes the value `value[i]`, and the bits past the end of
+    /// the slice are '0'.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let flags: &[bool] = &[true, false, true];
+    /// let map = Bitmap::<1>::try_from(flags).unwrap();
+    /// assert_eq!(map, he_lang!(0 | 2; 8));
+    /// ```
+    ///
+    /// # Errors
+    /// [`BitmapError::LengthMismatch`] if the slice is longer than
+    /// `BYTES * 8`.
+    fn try_from(value: &[bool]) -> Result<Self, Self::Error> {
+        if value.len() > BYTES * 8 {
+            return Err(BitmapError::LengthMismatch {
+                expected: BYTES * 8,
+                found: value.len(),
+            });
+        }
+        let mut map = Bitmap::<BYTES>::new();
+        for (byte, chunk) in map.bits.iter_mut().zip(value.chunks(8)) {
+            for (bit, &flag) in chunk.iter().enumerate() {
+                *byte |= (flag as u8) << bit;
+            }
+        }
+        Ok(map)
+    }
+}
+
+impl<const BYTES: usize, const N: usize> From<[bool; N]> for Bitmap<BYTES> {
+    /// Convert an array of [`bool`] into `Bitmap<BYTES>`. The bit `i` of
+    /// the map takes the value `value[i]`.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let map = Bitmap::<1>::from([true, false, true]);
+    /// assert_eq!(map, he_lang!(0 | 2; 8));
+    /// ```
+    ///
+    /// # Panics
+    /// Panic if `N > BYTES * 8`.
+    ///
+    /// # Type inference
+    /// With this impl, an empty array literal has more than one candidate
+    /// element type, so `Bitmap::<0>::from([])` no longer compiles. Spell
+    /// the element type out instead, e.g. `Bitmap::<0>::from([0u8; 0])`.
+    fn from(value: [bool; N]) -> Self {
+        match Self::try_from(&value[..]) {
+            Ok(map) => map,
+            Err(_) => panic!("Bitmap: converting from a too long array"),
+        }
+    }
+}
+
 macro_rules! impl_from {
+    (char) => {
+        impl<const BYTES: usize> From<char> for Bitmap<BYTES> {
+            fn from(value: char) -> Self {
+                Bitmap::<BYTES>::from(u32::from(value).to_le_bytes())
+            }
+        }
+    };
     ($t:ty) => {
         impl<const BYTES: usize> From<$t> for Bitmap<BYTES> {
             fn from(value: $t) -> Self {
-                let arr: [u8; core::mem::size_of::<$t>()] = unsafe { core::mem::transmute(value) };
-                Bitmap::<BYTES>::from(arr)
+                Bitmap::<BYTES>::from(value.to_le_bytes())
             }
         }
     };
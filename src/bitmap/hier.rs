@@ -0,0 +1,282 @@
+//! A bitmap with a summary of its full blocks, for searching huge maps.
+
+use super::*;
+
+/// The bytes in a block of a [`HierBitmap`], summarized by one bit.
+pub const HIER_BLOCK_BYTES: usize = 64;
+
+/// The `L1_BYTES` of a [`HierBitmap`] of `bytes` bytes: one summary bit
+/// per block of [`HIER_BLOCK_BYTES`], rounded up to whole bytes.
+///
+/// # Examples
+/// ```
+/// use cbitmap::bitmap::*;
+///
+/// assert_eq!(hier_summary_bytes(512), 1);
+/// assert_eq!(hier_summary_bytes(513), 2);
+/// assert_eq!(hier_summary_bytes(2 << 20), 4096);
+/// ```
+pub const fn hier_summary_bytes(bytes: usize) -> usize {
+    bytes.div_ceil(HIER_BLOCK_BYTES).div_ceil(8)
+}
+
+/// A [`Bitmap<BYTES>`] with a summary bitmap of `L1_BYTES` bytes, whose
+/// bit `i` is '1' iff the block `i`, i.e. the bytes `64 * i` up to
+/// `64 * (i + 1)`, is all '1'.
+///
+/// The summary is kept up to date by every method that changes the map,
+/// so [`HierBitmap::find_first_zero()`] only scans the summary and then
+/// the first block not full, instead of the whole map. This suits huge
+/// allocators that are nearly full most of the time.
+///
+/// `L1_BYTES` must be [`hier_summary_bytes(BYTES)`](hier_summary_bytes).
+/// Any other value fails to compile:
+/// ```compile_fail
+/// use cbitmap::bitmap::*;
+///
+/// let map = HierBitmap::<1024, 1>::new();
+/// ```
+///
+/// # Examples
+/// ```
+/// use cbitmap::bitmap::*;
+///
+/// const BYTES: usize = 1024;
+/// let mut map = HierBitmap::<BYTES, { hier_summary_bytes(BYTES) }>::new();
+/// map.set_range(..8000);
+/// assert_eq!(map.find_first_zero(), Some(8000));
+/// map.reset(17);
+/// assert_eq!(map.find_first_zero(), Some(17));
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[repr(C)]
+pub struct HierBitmap<const BYTES: usize, const L1_BYTES: usize> {
+    map: Bitmap<BYTES>,
+    full: Bitmap<L1_BYTES>,
+}
+
+impl<const BYTES: usize, const L1_BYTES: usize> HierBitmap<BYTES, L1_BYTES> {
+    /// The count of blocks, including a short last one.
+    const BLOCKS: usize = BYTES.div_ceil(HIER_BLOCK_BYTES);
+
+    const __SUMMARY_FITS: () = assert!(
+        L1_BYTES == hier_summary_bytes(BYTES),
+        "HierBitmap: L1_BYTES must be hier_summary_bytes(BYTES)"
+    );
+
+    /// Create a map whose bits are all '0'.
+    pub const fn new() -> Self {
+        let () = Self::__SUMMARY_FITS;
+        HierBitmap {
+            map: Bitmap::new(),
+            full: Bitmap::new(),
+        }
+    }
+
+    /// Create a boxed map whose bits are all '0', right on the heap, like
+    /// [`Bitmap::new_boxed()`].
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// const BYTES: usize = 2 << 20;
+    /// let map = HierBitmap::<BYTES, { hier_summary_bytes(BYTES) }>::new_boxed();
+    /// assert_eq!(map.find_first_zero(), Some(0));
+    /// ```
+    pub fn new_boxed() -> Box<Self> {
+        let () = Self::__SUMMARY_FITS;
+        let layout = Layout::new::<Self>();
+        // SAFETY: a `HierBitmap` is nothing but two byte arrays, so all
+        // zeros is a valid, empty map. A dangling pointer is valid for a
+        // zero-sized box.
+        unsafe {
+            if layout.size() == 0 {
+                return Box::from_raw(core::ptr::NonNull::<Self>::dangling().as_ptr());
+            }
+            let ptr = alloc_zeroed(layout);
+            if ptr.is_null() {
+                handle_alloc_error(layout);
+            }
+            Box::from_raw(ptr.cast::<Self>())
+        }
+    }
+
+    /// Wrap `map`, building its summary.
+    pub fn from_bitmap(map: Bitmap<BYTES>) -> Self {
+        let mut hier = Self::new();
+        hier.map = map;
+        hier.__refresh(0, Self::BLOCKS);
+        hier
+    }
+
+    /// Get the map. There is no mutable access to it, as that would
+    /// bypass the summary.
+    pub fn as_bitmap(&self) -> &Bitmap<BYTES> {
+        &self.map
+    }
+
+    /// Take the map out, dropping the summary.
+    pub fn into_bitmap(self) -> Bitmap<BYTES> {
+        self.map
+    }
+
+    /// Get the summary: its bit `i` is '1' iff the block `i` is all '1'.
+    /// The bits after the last block are '0'.
+    pub fn summary(&self) -> &Bitmap<L1_BYTES> {
+        &self.full
+    }
+
+    /// Set a range of bits to '1', like [`Bitmap::set_range()`].
+    ///
+    /// # Return
+    /// `&mut self`, allowing a call chain.
+    ///
+    /// # Panics
+    /// Panic if the range is reversed or goes out of range.
+    pub fn set_range<R: core::ops::RangeBounds<usize>>(&mut self, range: R) -> &mut Self {
+        let (start, end) = self.__range(&range, "setting");
+        __set_range(&mut self.map.bits, start, end);
+        self.__refresh_bits(start, end)
+    }
+
+    /// Set a range of bits to '0', like `set_range(..)` does to '1'.
+    ///
+    /// # Return
+    /// `&mut self`, allowing a call chain.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let mut map = HierBitmap::<128, 1>::new();
+    /// map.set_all().reset_range(512..=515);
+    /// assert_eq!(map.find_first_zero(), Some(512));
+    /// assert_eq!(map.count(), 1020);
+    /// ```
+    ///
+    /// # Panics
+    /// Panic if the range is reversed or goes out of range.
+    pub fn reset_range<R: core::ops::RangeBounds<usize>>(&mut self, range: R) -> &mut Self {
+        let (start, end) = self.__range(&range, "resetting");
+        __reset_range(&mut self.map.bits, start, end);
+        self.__refresh_bits(start, end)
+    }
+
+    fn __range<R: core::ops::RangeBounds<usize>>(&self, range: &R, verb: &str) -> (usize, usize) {
+        match __range_to_pair(range, BYTES * 8) {
+            Some(pair) => pair,
+            None => panic!("Bitmap: {verb} out of range"),
+        }
+    }
+
+    /// Update the summary after the bit `index` changed.
+    fn __update(&mut self, index: usize) -> &mut Self {
+        let byte = __idx_get_byte(index);
+        match self.map.bits[byte] {
+            // Only a byte turning full can make its block full.
+            0xff => self.__refresh_bits(index, index + 1),
+            _ => {
+                self.full.reset(byte / HIER_BLOCK_BYTES);
+                self
+            }
+        }
+    }
+
+    /// Refresh the summary of the blocks holding the bits `[start, end)`.
+    fn __refresh_bits(&mut self, start: usize, end: usize) -> &mut Self {
+        if start < end {
+            let block = |bit: usize| bit / 8 / HIER_BLOCK_BYTES;
+            self.__refresh(block(start), block(end - 1) + 1);
+        }
+        self
+    }
+
+    /// Refresh the summary of the blocks `[first, last)`.
+    fn __refresh(&mut self, first: usize, last: usize) {
+        for block in first..last {
+            let start = block * HIER_BLOCK_BYTES;
+            let end = BYTES.min(start + HIER_BLOCK_BYTES);
+            match __find_first_zero(&self.map.bits[start..end]) {
+                None => self.full.set(block),
+                Some(_) => self.full.reset(block),
+            };
+        }
+    }
+}
+
+impl<const BYTES: usize, const L1_BYTES: usize> Default for HierBitmap<BYTES, L1_BYTES> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const BYTES: usize, const L1_BYTES: usize> From<Bitmap<BYTES>>
+    for HierBitmap<BYTES, L1_BYTES>
+{
+    fn from(map: Bitmap<BYTES>) -> Self {
+        Self::from_bitmap(map)
+    }
+}
+
+impl<const BYTES: usize, const L1_BYTES: usize> BitsManage for HierBitmap<BYTES, L1_BYTES> {
+    #[inline]
+    fn count(&self) -> usize {
+        self.map.count()
+    }
+
+    #[inline]
+    fn find_first_one(&self) -> Option<usize> {
+        self.map.find_first_one()
+    }
+
+    /// Find the first '0' by the summary: only the first block not full
+    /// is scanned.
+    fn find_first_zero(&self) -> Option<usize> {
+        let block = self.full.find_first_zero()?;
+        if block >= Self::BLOCKS {
+            return None;
+        }
+        let start = block * HIER_BLOCK_BYTES;
+        let end = BYTES.min(start + HIER_BLOCK_BYTES);
+        __find_first_zero(&self.map.bits[start..end]).map(|index| start * 8 + index)
+    }
+
+    #[inline]
+    fn get_bool(&self, index: usize) -> bool {
+        self.map.get_bool(index)
+    }
+
+    fn set(&mut self, index: usize) -> &mut Self {
+        self.map.set(index);
+        self.__update(index)
+    }
+
+    fn reset(&mut self, index: usize) -> &mut Self {
+        self.map.reset(index);
+        self.__update(index)
+    }
+
+    fn flip(&mut self, index: usize) -> &mut Self {
+        self.map.flip(index);
+        self.__update(index)
+    }
+
+    fn set_all(&mut self) -> &mut Self {
+        self.map.set_all();
+        __set_range(&mut self.full.bits, 0, Self::BLOCKS);
+        self
+    }
+
+    fn reset_all(&mut self) -> &mut Self {
+        self.map.reset_all();
+        self.full.reset_all();
+        self
+    }
+
+    fn flip_all(&mut self) -> &mut Self {
+        self.map.flip_all();
+        self.__refresh(0, Self::BLOCKS);
+        self
+    }
+}
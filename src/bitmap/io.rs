@@ -0,0 +1,50 @@
+//! Reading and writing a [`Bitmap`] through [`std::io`], with the `std`
+//! feature.
+//!
+//! A map is read and written as its `BYTES` bytes, in the order of
+//! [`Bitmap::to_le_bytes()`].
+
+use super::*;
+use std::io;
+
+impl<const BYTES: usize> Bitmap<BYTES> {
+    /// Write the `BYTES` bytes of the map to `w`.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let mut out = Vec::new();
+    /// he_lang!(0 | 9; 16).write_to(&mut out).unwrap();
+    /// assert_eq!(out, [0b_1, 0b_10]);
+    /// ```
+    ///
+    /// # Errors
+    /// Any error of [`io::Write::write_all()`].
+    pub fn write_to<W: io::Write>(&self, w: &mut W) -> io::Result<()> {
+        w.write_all(&self.bits)
+    }
+
+    /// Read a map from exactly `BYTES` bytes of `r`, right into the
+    /// buffer of the map.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let mut input: &[u8] = &[0b_1, 0b_10, 0xff];
+    /// let map = Bitmap::<2>::read_from(&mut input).unwrap();
+    /// assert_eq!(map, he_lang!(0 | 9; 16));
+    /// assert_eq!(input, [0xff]);
+    /// ```
+    ///
+    /// # Errors
+    /// Any error of [`io::Read::read_exact()`]. In particular, if `r`
+    /// ends before `BYTES` bytes, it is an error of the kind
+    /// [`io::ErrorKind::UnexpectedEof`].
+    pub fn read_from<R: io::Read>(r: &mut R) -> io::Result<Self> {
+        let mut map = Self::new();
+        r.read_exact(&mut map.bits)?;
+        Ok(map)
+    }
+}
@@ -0,0 +1,125 @@
+//! Iterators over the bits of a bitmap.
+
+use super::*;
+use core::iter::FusedIterator;
+
+/// An iterator over the indices of the '1's in a bitmap, in ascending
+/// order. Created by [`Bitmap::iter_ones()`] and [`BitmapRef::iter_ones()`].
+///
+/// # Examples
+/// ```
+/// use cbitmap::bitmap::*;
+///
+/// let map = he_lang!(3 | 71 | 400; 512);
+/// let mut ones = map.iter_ones();
+/// assert_eq!(ones.next(), Some(3));
+/// assert_eq!(ones.next(), Some(71));
+/// assert_eq!(ones.next(), Some(400));
+/// assert_eq!(ones.next(), None);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Ones<'map> {
+    bits: &'map [u8],
+    byte: usize,
+    rest: u8,
+}
+
+impl<'map> Ones<'map> {
+    pub(super) fn new(bits: &'map [u8]) -> Self {
+        Self {
+            bits,
+            byte: 0,
+            rest: bits.first().copied().unwrap_or(0),
+        }
+    }
+}
+
+impl<'map> Iterator for Ones<'map> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        while self.rest == 0 {
+            self.byte += 1;
+            self.rest = *self.bits.get(self.byte)?;
+        }
+        let index = self.byte * 8 + self.rest.trailing_zeros() as usize;
+        self.rest &= self.rest - 1;
+        Some(index)
+    }
+}
+
+impl<'map> FusedIterator for Ones<'map> {}
+
+/// An iterator over the indices of the '1's in a bitmap, in ascending
+/// order, which sets each '1' to '0' as it is yielded. Created by
+/// [`Bitmap::drain_ones()`] and [`BitmapMut::drain_ones()`].
+///
+/// Dropping it early stops the draining there: the '1's already yielded
+/// are '0', and the rest are left as they were.
+///
+/// # Examples
+/// ```
+/// use cbitmap::bitmap::*;
+///
+/// let mut pending = he_lang!(3 | 71 | 400; 512);
+/// let first: Vec<usize> = pending.drain_ones().take(2).collect();
+/// assert_eq!(first, [3, 71]);
+/// assert_eq!(pending.iter_ones().collect::<Vec<_>>(), [400]);
+/// ```
+#[derive(Debug)]
+pub struct DrainOnes<'map> {
+    bits: &'map mut [u8],
+    next: usize,
+}
+
+impl<'map> DrainOnes<'map> {
+    pub(super) fn new(bits: &'map mut [u8]) -> Self {
+        Self { bits, next: 0 }
+    }
+}
+
+impl<'map> Iterator for DrainOnes<'map> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let index = __find_first_bit_from(self.bits, true, self.next)?;
+        let (byte, bit) = __idx_1dto2d(index);
+        self.bits[byte] &= !(1 << bit);
+        self.next = index + 1;
+        Some(index)
+    }
+}
+
+impl<'map> FusedIterator for DrainOnes<'map> {}
+
+impl<const BYTES: usize> Bitmap<BYTES> {
+    /// Iterate over the indices of the '1's, in ascending order.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let map = he_lang!(0 | 9 | 15; 16);
+    /// let ones: Vec<usize> = map.iter_ones().collect();
+    /// assert_eq!(ones, [0, 9, 15]);
+    /// ```
+    pub fn iter_ones(&self) -> Ones<'_> {
+        Ones::new(&self.bits)
+    }
+
+    /// Iterate over the indices of the '1's, in ascending order, setting
+    /// each to '0' as it is yielded. See [`DrainOnes`].
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let mut pending = he_lang!(0 | 9 | 15; 16);
+    /// let done: Vec<usize> = pending.drain_ones().collect();
+    /// assert_eq!(done, [0, 9, 15]);
+    /// assert_eq!(pending.none(), true);
+    /// ```
+    pub fn drain_ones(&mut self) -> DrainOnes<'_> {
+        DrainOnes::new(&mut self.bits)
+    }
+}
@@ -30,7 +30,7 @@
 /// assert_eq!(map.test(0), true);
 /// assert_eq!(map.test(5), true);
 /// ```
-/// You can also use variables, but you cannot use exprs:
+/// You can also use variables:
 /// ```
 /// use cbitmap::bitmap::*;
 /// let a = 1u64 << 34;
@@ -38,8 +38,26 @@
 /// let map = newmap!(a | b; 48);
 /// assert_eq!(map.test(34), true);
 /// assert_eq!(map.test(47), true);
-/// // Not allowed!
-/// // let map = newmap!((1 << 12) | (1 << 13); 14);
+/// ```
+/// Byte arrays and byte-string literals are also accepted as flags.
+/// The byte `i` holds the bits `8 * i` to `8 * i + 7`, and bytes beyond
+/// the map are ignored:
+/// ```
+/// use cbitmap::bitmap::*;
+/// let map = newmap!(b"\xff\x0f"; 16);
+/// assert_eq!(&map.range_to_string(0, 16).unwrap(), "00001111 11111111");
+/// let map = newmap!([0x01, 0x80, 0xff]; 16);
+/// assert_eq!(&map.range_to_string(0, 16).unwrap(), "10000000 00000001");
+/// ```
+/// To use arbitrary exprs as flags, separate them with `,` instead
+/// of `|`:
+/// ```
+/// use cbitmap::bitmap::*;
+/// const FLAG_A: u64 = 1 << 40;
+/// const FLAG_B: u64 = 1 << 41;
+/// let map = newmap!((1u64 << 12), FLAG_A | FLAG_B, 1u16 << 13; 64);
+/// assert_eq!(map.count(), 4);
+/// assert_eq!(map.test_all_of(&[12, 13, 40, 41]), true);
 /// ```
 /// # See also
 /// [`crate::he_lang`]
@@ -85,6 +103,95 @@ macro_rules! newmap {
             map
         }
     };
+    (
+        $($a:expr),+$(,)?
+        ;$n:expr
+    ) => {
+        {
+            let mut map = Bitmap::<{(($n) + 7) >> 3}>::new();
+            $(
+                // The flags are often wrapped in parens to be readable.
+                #[allow(unused_parens)]
+                {
+                    map |= $a;
+                }
+            )+
+            map
+        }
+    };
+}
+
+/// Create a boxed `cbitmap::bitmap::Bitmap` with all zero bits,
+/// specifying its (expected) bit length like [`newmap`].
+///
+/// The map is allocated right on the heap, without being built on
+/// the stack first. See `Bitmap::new_boxed()`.
+///
+/// # Examples
+/// ```
+/// use cbitmap::bitmap::*;
+/// // A 4MB map.
+/// let map = boxmap!(;4 * 1024 * 1024 * 8);
+/// assert_eq!(map.byte_len(), 4 * 1024 * 1024);
+/// assert_eq!(core::mem::size_of_val(&*map), 4 * 1024 * 1024);
+/// ```
+#[macro_export]
+macro_rules! boxmap {
+    () => {
+        Bitmap::<0>::new_boxed()
+    };
+    (;$n:expr) => {
+        Bitmap::<{(($n) + 7) >> 3}>::new_boxed()
+    };
+}
+
+/// Create a `cbitmap::bitmap::Bitmap` in a const context, like a
+/// `static` or `const` item. The syntax is the same as [`newmap`].
+///
+/// The masks are folded into the map by const fns, so they must be
+/// const themselves. Each mask is taken as an integer of its own type,
+/// the same as [`crate::newmap`]: its bit `i` sets the bit `i` of the
+/// map, so a negative `i8` sets 8 bits, not more.
+///
+/// # Examples
+/// ```
+/// use cbitmap::bitmap::*;
+///
+/// static DEFAULT_FLAGS: Bitmap<8> = constmap!(0b1 | 0b1000_0000u64; 64);
+/// assert_eq!(DEFAULT_FLAGS.test(0), true);
+/// assert_eq!(DEFAULT_FLAGS.test(7), true);
+///
+/// const HIGH: u64 = 1 << 63;
+/// const MAP: Bitmap<8> = constmap!(HIGH; 64);
+/// assert_eq!(MAP.test(63), true);
+///
+/// const EMPTY: Bitmap<2> = constmap!(;16);
+/// assert_eq!(EMPTY.none(), true);
+/// ```
+/// # See also
+/// [`crate::newmap`]
+#[macro_export]
+macro_rules! constmap {
+    () => {
+        Bitmap::<0>::new()
+    };
+    (;$n:expr) => {
+        Bitmap::<{($n + 7) >> 3}>::new()
+    };
+    (
+        $($a:literal)|*$(|)?
+        ;$n:expr
+    ) => {
+        Bitmap::<{(($n) + 7) >> 3}>::new()
+            $(.with_bytes_or($crate::bitmap::macros::__mask_bytes(&($a), ($a) as u128)))*
+    };
+    (
+        $($a:ident)|*$(|)?
+        ;$n:expr
+    ) => {
+        Bitmap::<{(($n) + 7) >> 3}>::new()
+            $(.with_bytes_or($crate::bitmap::macros::__mask_bytes(&($a), ($a) as u128)))*
+    };
 }
 
 /// A wrapper of [`newmap`], which is a painted eggshell. 
@@ -99,7 +206,19 @@ macro_rules! newmap {
 /// assert_eq!(map.test(1), true);
 /// assert_eq!(map.test(4), true);
 /// ```
+/// Exprs are also allowed. A `|` inside an index expr must be wrapped
+/// in parens, otherwise it is taken as a separator:
+/// ```
+/// use cbitmap::bitmap::*;
+/// const START: usize = 10;
+/// let step = 3;
+/// let map = he_lang!(START | START + 1 | 2 * step | (1 | 4); 16);
+/// assert_eq!(map.count(), 4);
+/// assert_eq!(map.test_all_of(&[5, 6, 10, 11]), true);
+/// ```
 /// 
+/// # Panics
+/// Panic if any index is out of range, like `set()`.
 #[macro_export]
 macro_rules! he_lang {
     (
@@ -108,8 +227,254 @@ macro_rules! he_lang {
     ) => {
         {
             let mut map = Bitmap::<{(($n) + 7) >> 3}>::new();
-            map$(.set($a))*;
+            $(map.set($a);)*
+            map
+        }
+    };
+    (@build [$([$($idx:tt)+])*] $n:expr) => {
+        {
+            let mut map = Bitmap::<{(($n) + 7) >> 3}>::new();
+            $(map.set($($idx)+);)*
+            map
+        }
+    };
+    // An index of at most 3 tokens is taken in one step, so that the
+    // recursion is about as deep as the number of indexes.
+    (@split [$($g:tt)*] [] $a:tt | $($rest:tt)*) => {
+        $crate::he_lang!(@split [$($g)* [$a]] [] $($rest)*)
+    };
+    (@split [$($g:tt)*] [] $a:tt $b:tt | $($rest:tt)*) => {
+        $crate::he_lang!(@split [$($g)* [$a $b]] [] $($rest)*)
+    };
+    (@split [$($g:tt)*] [] $a:tt $b:tt $c:tt | $($rest:tt)*) => {
+        $crate::he_lang!(@split [$($g)* [$a $b $c]] [] $($rest)*)
+    };
+    (@split [$($g:tt)*] [] ;$n:expr) => {
+        $crate::he_lang!(@build [$($g)*] $n)
+    };
+    (@split [$($g:tt)*] [] $a:tt ;$n:expr) => {
+        $crate::he_lang!(@build [$($g)* [$a]] $n)
+    };
+    (@split [$($g:tt)*] [] $a:tt $b:tt ;$n:expr) => {
+        $crate::he_lang!(@build [$($g)* [$a $b]] $n)
+    };
+    (@split [$($g:tt)*] [] $a:tt $b:tt $c:tt ;$n:expr) => {
+        $crate::he_lang!(@build [$($g)* [$a $b $c]] $n)
+    };
+    // A longer index is taken token by token.
+    (@split [$($g:tt)*] [$($idx:tt)+] | $($rest:tt)*) => {
+        $crate::he_lang!(@split [$($g)* [$($idx)+]] [] $($rest)*)
+    };
+    (@split [$($g:tt)*] [$($idx:tt)+] ;$n:expr) => {
+        $crate::he_lang!(@build [$($g)* [$($idx)+]] $n)
+    };
+    (@split [$($g:tt)*] [$($idx:tt)*] $t:tt $($rest:tt)*) => {
+        $crate::he_lang!(@split [$($g)*] [$($idx)* $t] $($rest)*)
+    };
+    ($($t:tt)*) => {
+        $crate::he_lang!(@split [] [] $($t)*)
+    };
+}
+
+/// Create a bitmap with a `,` separated list of indexes to set, and the
+/// (expected) bit length after `;`. Both the indexes and the length can
+/// be exprs, and a trailing `,` is allowed.
+///
+/// The map is built by const fns, so the macro also works in const
+/// contexts, like a `static` item.
+///
+/// # Examples
+/// ```
+/// use cbitmap::bitmap::*;
+/// let map = bits![3, 5, 17; 32];
+/// assert_eq!(map.bit_len(), 32);
+/// assert_eq!(map.test_all_of(&[3, 5, 17]), true);
+///
+/// const BASE: usize = 8;
+/// static MAP: Bitmap<2> = bits![BASE, BASE + 2,; 2 * BASE];
+/// assert_eq!(MAP.count(), 2);
+/// ```
+///
+/// # Panics
+/// Panic if any index is out of range. In a const context, this is a
+/// compile error.
+#[macro_export]
+macro_rules! bits {
+    ($($idx:expr),*$(,)?; $n:expr) => {
+        Bitmap::<{(($n) + 7) >> 3}>::new()$(.with_bit_set($idx))*
+    };
+}
+
+/// Create a bitmap with a `,` separated list of ranges and indexes to
+/// set, and the (expected) bit length after `;`. A range is written as
+/// `start..end` or `start..=end`, where both ends can be exprs.
+///
+/// # Examples
+/// ```
+/// use cbitmap::bitmap::*;
+/// let map = rangemap!(8..16, 30..=31; 32);
+/// assert_eq!(&map.range_to_string(0, 32).unwrap(),
+///            "11000000 00000000 11111111 00000000");
+///
+/// // Ranges and single indexes can be mixed:
+/// let start = 2;
+/// let map = rangemap!(start..start + 3, 7; 8);
+/// assert_eq!(&map.range_to_string(0, 8).unwrap(), "10011100");
+/// ```
+///
+/// # Panics
+/// Panic if any range or index is out of range.
+#[macro_export]
+macro_rules! rangemap {
+    (@item $map:ident [$($s:tt)+] []) => {
+        $map.set($($s)+);
+    };
+    (@item $map:ident [$($s:tt)+] [.. $($e:tt)+]) => {
+        $map.set_range(($($s)+)..($($e)+));
+    };
+    (@item $map:ident [$($s:tt)+] [..= $($e:tt)+]) => {
+        $map.set_range(($($s)+)..=($($e)+));
+    };
+    (@range $map:ident [$($s:tt)*] ..= $($e:tt)*) => {
+        $crate::rangemap!(@item $map [$($s)*] [..= $($e)*]);
+    };
+    (@range $map:ident [$($s:tt)*] .. $($e:tt)*) => {
+        $crate::rangemap!(@item $map [$($s)*] [.. $($e)*]);
+    };
+    (@range $map:ident [$($s:tt)*] $t:tt $($rest:tt)*) => {
+        $crate::rangemap!(@range $map [$($s)* $t] $($rest)*);
+    };
+    (@range $map:ident [$($s:tt)*]) => {
+        $crate::rangemap!(@item $map [$($s)*] []);
+    };
+    (@build [$([$($item:tt)+])*] $n:expr) => {
+        {
+            let mut map = Bitmap::<{(($n) + 7) >> 3}>::new();
+            $($crate::rangemap!(@range map [] $($item)+);)*
             map
         }
     };
-}
\ No newline at end of file
+    // An item of at most 3 tokens, like `a..b`, is taken in one step, so
+    // that the recursion is about as deep as the number of items.
+    (@split [$($g:tt)*] [] $a:tt , $($rest:tt)*) => {
+        $crate::rangemap!(@split [$($g)* [$a]] [] $($rest)*)
+    };
+    (@split [$($g:tt)*] [] $a:tt $b:tt , $($rest:tt)*) => {
+        $crate::rangemap!(@split [$($g)* [$a $b]] [] $($rest)*)
+    };
+    (@split [$($g:tt)*] [] $a:tt $b:tt $c:tt , $($rest:tt)*) => {
+        $crate::rangemap!(@split [$($g)* [$a $b $c]] [] $($rest)*)
+    };
+    (@split [$($g:tt)*] [] ;$n:expr) => {
+        $crate::rangemap!(@build [$($g)*] $n)
+    };
+    (@split [$($g:tt)*] [] $a:tt ;$n:expr) => {
+        $crate::rangemap!(@build [$($g)* [$a]] $n)
+    };
+    (@split [$($g:tt)*] [] $a:tt $b:tt ;$n:expr) => {
+        $crate::rangemap!(@build [$($g)* [$a $b]] $n)
+    };
+    (@split [$($g:tt)*] [] $a:tt $b:tt $c:tt ;$n:expr) => {
+        $crate::rangemap!(@build [$($g)* [$a $b $c]] $n)
+    };
+    // A longer item is taken token by token.
+    (@split [$($g:tt)*] [$($item:tt)+] , $($rest:tt)*) => {
+        $crate::rangemap!(@split [$($g)* [$($item)+]] [] $($rest)*)
+    };
+    (@split [$($g:tt)*] [$($item:tt)+] ;$n:expr) => {
+        $crate::rangemap!(@build [$($g)* [$($item)+]] $n)
+    };
+    (@split [$($g:tt)*] [$($item:tt)*] $t:tt $($rest:tt)*) => {
+        $crate::rangemap!(@split [$($g)*] [$($item)* $t] $($rest)*)
+    };
+    ($($t:tt)*) => {
+        $crate::rangemap!(@split [] [] $($t)*)
+    };
+}
+
+/// Create a bitmap from a binary-string literal, parsed at compile time.
+///
+/// The leftmost digit is the highest bit, the same as how a map is
+/// formatted by `range_to_string()`. The `'_'`s and spaces are ignored,
+/// and the length is the number of digits rounded up to a multiple of 8.
+/// Any other character is a compile error.
+///
+/// # Examples
+/// ```
+/// use cbitmap::bitmap::*;
+/// let map = bitstr!("0110_1000 1111_0000");
+/// assert_eq!(map.bit_len(), 16);
+/// assert_eq!(&map.range_to_string(0, 16).unwrap(), "01101000 11110000");
+///
+/// // Usable in const contexts:
+/// const MAP: Bitmap<1> = bitstr!("101");
+/// assert_eq!(&MAP.range_to_string(0, 8).unwrap(), "00000101");
+/// ```
+/// ```compile_fail
+/// use cbitmap::bitmap::*;
+/// let map = bitstr!("0120");
+/// ```
+#[macro_export]
+macro_rules! bitstr {
+    ($s:literal) => {
+        {
+            const BYTES: usize = ($crate::bitmap::macros::__bitstr_len($s) + 7) >> 3;
+            const MAP: Bitmap<BYTES> =
+                Bitmap::from_array($crate::bitmap::macros::__bitstr_parse::<BYTES>($s));
+            MAP
+        }
+    };
+}
+
+/// Count the digits of a binary string for [`bitstr`]. Not a public API.
+#[doc(hidden)]
+pub const fn __bitstr_len(s: &str) -> usize {
+    let bytes = s.as_bytes();
+    let mut cnt = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'0' | b'1' => cnt += 1,
+            b'_' | b' ' => {}
+            _ => panic!("bitstr: only '0', '1', '_' and ' ' are allowed"),
+        }
+        i += 1;
+    }
+    cnt
+}
+
+/// Parse a binary string into bytes for [`bitstr`]. Not a public API.
+#[doc(hidden)]
+pub const fn __bitstr_parse<const BYTES: usize>(s: &str) -> [u8; BYTES] {
+    let bytes = s.as_bytes();
+    let mut arr = [0u8; BYTES];
+    let mut idx = 0;
+    let mut i = bytes.len();
+    while i > 0 {
+        i -= 1;
+        match bytes[i] {
+            b'0' => idx += 1,
+            b'1' => {
+                arr[idx >> 3] |= 1 << (idx & 0b111);
+                idx += 1;
+            }
+            b'_' | b' ' => {}
+            _ => panic!("bitstr: only '0', '1', '_' and ' ' are allowed"),
+        }
+    }
+    arr
+}
+
+/// Give the bytes of a [`constmap`] mask `value`, cast from `mask`, cut to
+/// the size of the type of `mask`. So a signed mask is not sign-extended.
+/// Not a public API.
+#[doc(hidden)]
+pub const fn __mask_bytes<T>(_mask: &T, value: u128) -> [u8; 16] {
+    let mut bytes = value.to_le_bytes();
+    let mut i = core::mem::size_of::<T>();
+    while i < 16 {
+        bytes[i] = 0;
+        i += 1;
+    }
+    bytes
+}
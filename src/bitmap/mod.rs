@@ -1,10 +1,45 @@
+#[cfg(feature = "arbitrary")]
+mod arbitrary_impl;
+#[cfg(all(target_has_atomic = "8", target_has_atomic = "ptr"))]
+pub mod atomic;
+mod base64;
+#[cfg(feature = "bitset-core")]
+mod bitset_impl;
+#[cfg(feature = "bitvec")]
+mod bitvec_impl;
+pub mod copy;
+pub mod cursor;
+pub mod dynamic;
+pub mod error;
+#[cfg(feature = "fixedbitset")]
+mod fixedbitset_impl;
 pub mod fmt;
 pub mod from;
+pub mod hier;
+#[cfg(feature = "std")]
+mod io;
+pub mod iter;
 pub mod macros;
 pub mod ops;
+#[cfg(feature = "bytemuck")]
+mod pod;
 pub mod refs;
+mod rle;
 pub mod ptr;
+#[cfg(feature = "rand")]
+pub mod random;
+#[cfg(feature = "proptest")]
+pub mod strategy;
+#[cfg(feature = "serde")]
+mod serde_impl;
+pub mod set;
+pub mod shift;
+mod slice;
 mod traits;
+#[cfg(feature = "ufmt")]
+mod ufmt_impl;
+pub mod view;
+pub mod volatile;
 
 /// A size-fixed bitmap with croase-granularity (byte) and conventional
 /// interfaces.
@@ -16,7 +51,7 @@ mod traits;
 ///
 /// # Fields
 /// * `bits`: [`Option<Box>`]. The box holds an array of [`u8`] with
-/// fixed length `BYTES`.
+///   fixed length `BYTES`.
 ///
 /// It is allowed to have `BYTES == 0`. In this case, `bits = None`.
 ///
@@ -39,6 +74,10 @@ mod traits;
 /// // specified:
 /// let map: Bitmap<2> = 0b_10000000_00000001.into();
 /// ```
+///
+/// Integers are always read as little-endian, whatever the target is:
+/// the bit 0 of the map is the LSB of the integer. Use
+/// [`Bitmap::from_be()`] for the other order.
 /// ## Create a new bitmap using macros (recommend)
 /// ```
 /// use cbitmap::bitmap::*;
@@ -57,6 +96,9 @@ mod traits;
 /// let b = 1u128 << 47;
 /// let map = newmap!(a | b; 48);
 ///
+/// // newmap!(expr, expr, ...; bits)
+/// let map = newmap!(1u64 << 34, b; 48);
+///
 /// // he_lang!(idx | idx | ...; bits)
 /// let map = he_lang!(1 | 2; 8);
 /// ```
@@ -138,14 +180,63 @@ mod traits;
 /// map &= 1u8 << 7;
 /// assert_eq!(&map & !0u8, 1u8 << 7);
 /// ```
-#[derive(Clone)]
+/// ## Compare bitmaps
+/// ```
+/// use cbitmap::bitmap::*;
+///
+/// let mut a = newmap!(;16);
+/// let b = he_lang!(1 | 9; 16);
+/// assert_ne!(a, b);
+/// a.set(9).set(1);
+/// assert_eq!(a, b);
+/// ```
+///
+/// ## Layout
+/// `Bitmap<BYTES>` is `#[repr(transparent)]` over `[u8; BYTES]`, so a
+/// map can be placed over any `BYTES` bytes of memory.
+///
+/// With the `bytemuck` feature, the map is also `Copy`, as required by
+/// [`bytemuck::Pod`]. It is not `Copy` otherwise, so that large maps are
+/// never copied by accident.
+#[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "bytemuck", derive(Copy))]
+#[repr(transparent)]
 pub struct Bitmap<const BYTES: usize> {
     bits: [u8; BYTES],
 }
 
 /// A general trait, structs which implemented this 
 /// trait provide interfaces to access a range of bits.
+///
+/// The bit `i` is the bit `i % 8` of the byte `i / 8`, for every
+/// implementor. Besides [`Bitmap`] and [`HierBitmap`], it is implemented
+/// for `[u8]`, so any byte slice can be managed as a bitmap in place, and
+/// for `Vec<u8>` and `Box<[u8]>`, which never grow on an out-of-range
+/// index but panic like the fixed types.
+/// Generic code over it should allow `?Sized` to accept slices.
+///
+/// To implement it for your own storage, only the required methods are
+/// needed; the others are built on them.
+///
+/// # Examples
+/// ```
+/// use cbitmap::bitmap::*;
+///
+/// fn claim<T: BitsManage + ?Sized>(map: &mut T) -> Option<usize> {
+///     let index = map.find_first_zero()?;
+///     map.set(index);
+///     Some(index)
+/// }
+///
+/// let mut map = newmap!(0b_0111; 8);
+/// assert_eq!(claim(&mut map), Some(3));
+///
+/// let mut buffer = [0xffu8, 0b_1011, 0];
+/// assert_eq!(claim(&mut buffer[1..]), Some(2));
+/// assert_eq!(buffer, [0xff, 0b_1111, 0]);
+/// ```
 pub trait BitsManage {
+    /// Count how many '1's are there.
     fn count(&self) -> usize;
 
     /// Find the first '1', returns its index.
@@ -285,9 +376,77 @@ pub trait BitsManage {
     fn test(&self, index: usize) -> bool {
         self.get_bool(index)
     }
+
+    /// Test whether all the indexed bits are '1'.
+    ///
+    /// The bits are tested in order, and the test stops at
+    /// the first '0'. An empty `indices` gives `true`.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let map = newmap!(0b_1011; 8);
+    /// assert_eq!(map.test_all_of(&[0, 1, 3]), true);
+    /// assert_eq!(map.test_all_of(&[0, 2]), false);
+    /// assert_eq!(map.test_all_of(&[]), true);
+    /// ```
+    ///
+    /// # Panics
+    /// Panic if an index tested before the first '0' is out
+    /// of range.
+    #[inline]
+    fn test_all_of(&self, indices: &[usize]) -> bool {
+        indices.iter().all(|&index| self.test(index))
+    }
+
+    /// Test whether any of the indexed bits is '1'.
+    ///
+    /// The bits are tested in order, and the test stops at
+    /// the first '1'. An empty `indices` gives `false`.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let map = newmap!(0b_1000; 8);
+    /// assert_eq!(map.test_any_of(&[0, 3]), true);
+    /// assert_eq!(map.test_any_of(&[0, 2]), false);
+    /// assert_eq!(map.test_any_of(&[]), false);
+    /// ```
+    ///
+    /// # Panics
+    /// Panic if an index tested before the first '1' is out
+    /// of range.
+    #[inline]
+    fn test_any_of(&self, indices: &[usize]) -> bool {
+        indices.iter().any(|&index| self.test(index))
+    }
 }
 
 impl<const BYTES: usize> Bitmap<BYTES> {
+    /// The map whose flags are all 0, same as [`Bitmap::new()`].
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// static EMPTY: Bitmap<8> = Bitmap::ZERO;
+    /// assert_eq!(EMPTY.none(), true);
+    /// ```
+    pub const ZERO: Self = Self::new();
+
+    /// The map whose flags are all 1, same as [`Bitmap::full()`].
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// static SATURATED: Bitmap<8> = Bitmap::FULL;
+    /// assert_eq!(SATURATED.all(), true);
+    /// ```
+    pub const FULL: Self = Self::splat(true);
+
     /// Create a `Bitmap<BYTES>` whose flags are all set to 0.
     ///
     /// # Examples
@@ -298,10 +457,327 @@ impl<const BYTES: usize> Bitmap<BYTES> {
     /// assert_eq!(&map.range_to_string(0, 16).unwrap(),
     ///            "00000000 00000000");
     /// ```
-    pub fn new() -> Self {
+    pub const fn new() -> Self {
         Bitmap { bits: [0; BYTES] }
     }
 
+    /// Create a `Bitmap<BYTES>` whose flags are all set to 1.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let map = Bitmap::<2>::full();
+    /// assert_eq!(map.count(), 16);
+    /// ```
+    pub const fn full() -> Self {
+        Self::splat(true)
+    }
+
+    /// Create a `Bitmap<BYTES>` whose flags are all set to `value`.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// const ONES: Bitmap<2> = Bitmap::splat(true);
+    /// assert_eq!(ONES.all(), true);
+    /// assert_eq!(Bitmap::<2>::splat(false).none(), true);
+    /// ```
+    pub const fn splat(value: bool) -> Self {
+        match value {
+            true => Self::repeat_byte(0xff),
+            false => Self::new(),
+        }
+    }
+
+    /// Create a `Bitmap<BYTES>` with every byte set to `pattern`.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let map = Bitmap::<2>::repeat_byte(0b_1010_1010);
+    /// assert_eq!(&map.range_to_string(0, 16).unwrap(),
+    ///            "10101010 10101010");
+    /// ```
+    pub const fn repeat_byte(pattern: u8) -> Self {
+        Bitmap {
+            bits: [pattern; BYTES],
+        }
+    }
+
+    /// Create a boxed `Bitmap<BYTES>` whose flags are all set to 0.
+    ///
+    /// The map is allocated as zeroed memory right on the heap, unlike
+    /// `Box::new(Bitmap::new())`, which may build the map on the stack
+    /// first. So it is safe to create very large maps.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let map = Bitmap::<{ 2 * 1024 * 1024 }>::new_boxed();
+    /// assert_eq!(map.none(), true);
+    /// ```
+    pub fn new_boxed() -> Box<Self> {
+        Self::__boxed_with(0)
+    }
+
+    /// Create a boxed `Bitmap<BYTES>` whose flags are all set to 1.
+    ///
+    /// Like [`Bitmap::new_boxed()`], the map is filled right on the heap
+    /// and never built on the stack.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let map = Bitmap::<{ 2 * 1024 * 1024 }>::full_boxed();
+    /// assert_eq!(map.all(), true);
+    /// ```
+    pub fn full_boxed() -> Box<Self> {
+        Self::__boxed_with(0xff)
+    }
+
+    /// Create a `Bitmap<BYTES>` holding the given bytes. The byte `i`
+    /// holds the bits `8 * i` to `8 * i + 7`.
+    ///
+    /// Unlike [`From`], this is a `const fn`, so it can be used to
+    /// build `static` or `const` maps.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// const MAP: Bitmap<2> = Bitmap::from_array([0b_1, 0b_10]);
+    /// assert_eq!(MAP.test(0), true);
+    /// assert_eq!(MAP.test(9), true);
+    /// ```
+    pub const fn from_array(bits: [u8; BYTES]) -> Self {
+        Bitmap { bits }
+    }
+
+    /// Create a `Bitmap<BYTES>` where the bit `i` is `f(i)`.
+    ///
+    /// `f` is called once per bit, in ascending order of index.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let primes = Bitmap::<2>::from_fn(|i| i > 1 && (2..i).all(|d| i % d != 0));
+    /// assert_eq!(primes, he_lang!(2 | 3 | 5 | 7 | 11 | 13; 16));
+    /// ```
+    pub fn from_fn<F: FnMut(usize) -> bool>(mut f: F) -> Self {
+        let mut map = Self::new();
+        for (i, byte) in map.bits.iter_mut().enumerate() {
+            let mut value = 0u8;
+            for bit in 0..8 {
+                value |= (f(i * 8 + bit) as u8) << bit;
+            }
+            *byte = value;
+        }
+        map
+    }
+
+    /// Create a `Bitmap<BYTES>` from a big-endian byte array, like the
+    /// one from [`u32::to_be_bytes()`]: the last byte of the array holds
+    /// the bits 0 to 7.
+    ///
+    /// If `N > BYTES`, the first `N - BYTES` bytes are dropped; if
+    /// `N < BYTES`, the high bytes of the map are '0'.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let map = Bitmap::<4>::from_be(1u32.to_be_bytes());
+    /// assert_eq!(map, Bitmap::<4>::from(1u32));
+    /// ```
+    pub fn from_be<const N: usize>(mut bytes: [u8; N]) -> Self {
+        bytes.reverse();
+        Self::from(bytes)
+    }
+
+    /// Fill the first bytes of the map with a big-endian byte array, like
+    /// [`FillPrefix`] does with a little-endian one.
+    ///
+    /// # Return
+    /// `&mut self`, allowing a call chain.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let mut map = newmap!(;32);
+    /// map.fill_prefix_be(0x_0102_u16.to_be_bytes());
+    /// assert_eq!(map, Bitmap::<4>::from(0x_0102_u32));
+    /// ```
+    pub fn fill_prefix_be<const N: usize>(&mut self, mut bytes: [u8; N]) -> &mut Self {
+        bytes.reverse();
+        self.fill_prefix(bytes)
+    }
+
+    /// Create a `Bitmap<BYTES>` from its bytes in little-endian order:
+    /// the byte 0 holds the bits 0 to 7, the byte 1 the bits 8 to 15, and
+    /// so on. The same as [`Bitmap::from_array()`].
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let map = Bitmap::from_le_bytes(0x_0102_u16.to_le_bytes());
+    /// assert_eq!(map, Bitmap::<2>::from(0x_0102_u16));
+    /// ```
+    pub const fn from_le_bytes(bytes: [u8; BYTES]) -> Self {
+        Self::from_array(bytes)
+    }
+
+    /// Create a `Bitmap<BYTES>` from its bytes in big-endian order: the
+    /// last byte holds the bits 0 to 7. Unlike [`Bitmap::from_be()`], the
+    /// array must have exactly `BYTES` bytes.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let map = Bitmap::from_be_bytes(0x_0102_u16.to_be_bytes());
+    /// assert_eq!(map, Bitmap::<2>::from(0x_0102_u16));
+    /// ```
+    pub const fn from_be_bytes(bytes: [u8; BYTES]) -> Self {
+        Self::from_array(__reversed(bytes))
+    }
+
+    /// Give the bytes of the map in little-endian order: the byte 0 holds
+    /// the bits 0 to 7, like the layout of the map itself.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let map = he_lang!(0 | 9; 16);
+    /// assert_eq!(map.to_le_bytes(), [0b_1, 0b_10]);
+    /// ```
+    pub const fn to_le_bytes(&self) -> [u8; BYTES] {
+        self.bits
+    }
+
+    /// Give the bytes of the map in big-endian order: the last byte holds
+    /// the bits 0 to 7. This is `to_le_bytes()` reversed.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let map = he_lang!(0 | 9; 16);
+    /// assert_eq!(map.to_be_bytes(), [0b_10, 0b_1]);
+    /// ```
+    pub const fn to_be_bytes(&self) -> [u8; BYTES] {
+        __reversed(self.bits)
+    }
+
+    /// Give back the map with the indexed bit set to 1. Usable in
+    /// const contexts, so a `static` map can be built at compile time.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// static VALID: Bitmap<4> = Bitmap::new()
+    ///     .with_bit_set(1)
+    ///     .with_bit_set(20)
+    ///     .with_bit_set(31);
+    ///
+    /// assert_eq!(VALID.test(20), true);
+    /// assert_eq!(VALID.count(), 3);
+    /// ```
+    /// An out-of-range index is rejected at compile time:
+    /// ```compile_fail
+    /// use cbitmap::bitmap::*;
+    ///
+    /// static BAD: Bitmap<1> = Bitmap::new().with_bit_set(8);
+    /// ```
+    ///
+    /// # Panics
+    /// Panic if `index` is out of range. In a const context, this is
+    /// a compile error.
+    pub const fn with_bit_set(mut self, index: usize) -> Self {
+        if __out_bound(BYTES, index) {
+            panic!("Bitmap: setting out of range");
+        }
+        let (byte, bit) = __idx_1dto2d(index);
+        self.bits[byte] |= 1 << bit;
+        self
+    }
+
+    /// Give back the map with the indexed bit set to 0. Usable in
+    /// const contexts.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// const MAP: Bitmap<1> = Bitmap::from_array([0xff]).with_bit_reset(3);
+    /// assert_eq!(&MAP.range_to_string(0, 8).unwrap(), "11110111");
+    /// ```
+    ///
+    /// # Panics
+    /// Panic if `index` is out of range. In a const context, this is
+    /// a compile error.
+    pub const fn with_bit_reset(mut self, index: usize) -> Self {
+        if __out_bound(BYTES, index) {
+            panic!("Bitmap: resetting out of range");
+        }
+        let (byte, bit) = __idx_1dto2d(index);
+        self.bits[byte] &= !(1 << bit);
+        self
+    }
+
+    /// Give back the map with the indexed bit flipped. Usable in
+    /// const contexts.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// const MAP: Bitmap<1> = Bitmap::new().with_bit_flipped(2);
+    /// assert_eq!(&MAP.range_to_string(0, 8).unwrap(), "00000100");
+    /// ```
+    ///
+    /// # Panics
+    /// Panic if `index` is out of range. In a const context, this is
+    /// a compile error.
+    pub const fn with_bit_flipped(mut self, index: usize) -> Self {
+        if __out_bound(BYTES, index) {
+            panic!("Bitmap: flipping out of range");
+        }
+        let (byte, bit) = __idx_1dto2d(index);
+        self.bits[byte] ^= 1 << bit;
+        self
+    }
+
+    /// Give back the map OR-ed with an array of [`u8`] values. Usable in
+    /// const contexts.
+    ///
+    /// Like `|=`, the bytes of the array beyond the bitmap are ignored.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// const MAP: Bitmap<2> = Bitmap::new().with_bytes_or([0b_1, 0b_1, 0xff]);
+    /// assert_eq!(&MAP.range_to_string(0, 16).unwrap(), "00000001 00000001");
+    /// ```
+    pub const fn with_bytes_or<const N: usize>(mut self, bytes: [u8; N]) -> Self {
+        let mut i = 0;
+        while i < N && i < BYTES {
+            self.bits[i] |= bytes[i];
+            i += 1;
+        }
+        self
+    }
+
     /// Get the length of the bitmap in bits.
     ///
     /// # Examples
@@ -329,6 +805,524 @@ impl<const BYTES: usize> Bitmap<BYTES> {
     pub const fn byte_len(&self) -> usize {
         BYTES
     }
+
+    /// Set a batch of bits to 1 by specifying their indexes.
+    ///
+    /// All the indexes are checked before any bit is modified, so
+    /// the map is left untouched if the call panics. Duplicated
+    /// indexes are allowed.
+    ///
+    /// # Return
+    /// `&mut self`, allowing a call chain.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let mut map = newmap!(;16);
+    /// map.set_many(&[0, 3, 9, 3]);
+    /// assert_eq!(&map.range_to_string(0, 16).unwrap(), "00000010 00001001");
+    /// ```
+    ///
+    /// # Panics
+    /// Panic if any of the `indices` is out of range.
+    pub fn set_many(&mut self, indices: &[usize]) -> &mut Self {
+        if indices.iter().any(|&index| __out_bound(BYTES, index)) {
+            panic!("Bitmap: setting out of range");
+        }
+        for &index in indices {
+            let (byte, bit) = __idx_1dto2d(index);
+            __byte_or_u8(self.__get_mut_u8(byte), 1u8 << bit);
+        }
+        self
+    }
+
+    /// Set a batch of bits to 0 by specifying their indexes.
+    ///
+    /// All the indexes are checked before any bit is modified, so
+    /// the map is left untouched if the call panics. Duplicated
+    /// indexes are allowed.
+    ///
+    /// # Return
+    /// `&mut self`, allowing a call chain.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let mut map = newmap!(0b_11111111; 8);
+    /// map.reset_many(&[1, 7, 1]);
+    /// assert_eq!(&map.range_to_string(0, 8).unwrap(), "01111101");
+    /// ```
+    ///
+    /// # Panics
+    /// Panic if any of the `indices` is out of range.
+    pub fn reset_many(&mut self, indices: &[usize]) -> &mut Self {
+        if indices.iter().any(|&index| __out_bound(BYTES, index)) {
+            panic!("Bitmap: resetting out of range");
+        }
+        for &index in indices {
+            let (byte, bit) = __idx_1dto2d(index);
+            __byte_and_u8(self.__get_mut_u8(byte), !(1u8 << bit));
+        }
+        self
+    }
+
+    /// Set a range of bits to 1, like `set_range(8..16)` or
+    /// `set_range(30..=31)`. An empty range does nothing.
+    ///
+    /// # Return
+    /// `&mut self`, allowing a call chain.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let mut map = newmap!(;16);
+    /// map.set_range(3..10);
+    /// assert_eq!(&map.range_to_string(0, 16).unwrap(), "00000011 11111000");
+    /// map.set_range(14..);
+    /// assert_eq!(&map.range_to_string(0, 16).unwrap(), "11000011 11111000");
+    /// ```
+    ///
+    /// # Panics
+    /// Panic if the range is reversed or goes out of range.
+    pub fn set_range<R: core::ops::RangeBounds<usize>>(&mut self, range: R) -> &mut Self {
+        let (start, end) = match __range_to_pair(&range, BYTES * 8) {
+            Some(pair) => pair,
+            None => panic!("Bitmap: setting out of range"),
+        };
+        __set_range(&mut self.bits, start, end);
+        self
+    }
+
+    /// Find the first '0' and set it to '1', in one scan and one byte
+    /// write. The same as `find_first_zero()` and then `set()`.
+    ///
+    /// # Return
+    /// The index of the bit, or [`None`] if there is no '0'.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let mut map = he_lang!(0 | 1 | 3; 8);
+    /// assert_eq!(map.set_first_zero(), Some(2));
+    /// assert_eq!(map.set_first_zero(), Some(4));
+    /// map.set_all();
+    /// assert_eq!(map.set_first_zero(), None);
+    /// ```
+    pub fn set_first_zero(&mut self) -> Option<usize> {
+        __flip_first_bit(&mut self.bits, false)
+    }
+
+    /// Find the first '1' and set it to '0', like
+    /// [`Bitmap::set_first_zero()`].
+    ///
+    /// # Return
+    /// The index of the bit, or [`None`] if there is no '1'.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let mut map = he_lang!(3 | 9; 16);
+    /// assert_eq!(map.reset_first_one(), Some(3));
+    /// assert_eq!(map.reset_first_one(), Some(9));
+    /// assert_eq!(map.reset_first_one(), None);
+    /// ```
+    pub fn reset_first_one(&mut self) -> Option<usize> {
+        __flip_first_bit(&mut self.bits, true)
+    }
+
+    /// Take the lowest '1': set it to '0' and give its index. The same
+    /// as [`Bitmap::reset_first_one()`], for a map used as a queue of
+    /// ready indexes.
+    ///
+    /// # Return
+    /// The index of the bit, or [`None`] if there is no '1'.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let mut ready = he_lang!(2 | 5 | 11; 16);
+    /// assert_eq!(ready.pop_first(), Some(2));
+    /// assert_eq!(ready.pop_first(), Some(5));
+    /// assert_eq!(ready.count(), 1);
+    /// ```
+    #[inline]
+    pub fn pop_first(&mut self) -> Option<usize> {
+        self.reset_first_one()
+    }
+
+    /// Take the highest '1': set it to '0' and give its index.
+    ///
+    /// # Return
+    /// The index of the bit, or [`None`] if there is no '1'.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let mut ready = he_lang!(2 | 5 | 11; 16);
+    /// assert_eq!(ready.pop_last(), Some(11));
+    /// assert_eq!(ready.pop_last(), Some(5));
+    /// assert_eq!(ready.count(), 1);
+    /// ```
+    pub fn pop_last(&mut self) -> Option<usize> {
+        __flip_last_bit(&mut self.bits, true)
+    }
+
+    /// Find the first '0' at or after `from`, wrapping around to bit 0
+    /// once, for round-robin allocation. The bits before `from` are
+    /// searched last, so `from` itself is given first if it is '0'.
+    ///
+    /// # Return
+    /// The index of the bit, or [`None`] if there is no '0' in the map.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let map = he_lang!(0 | 1 | 2 | 5 | 6 | 7; 8);
+    /// assert_eq!(map.next_zero_wrapping(3), Some(3));
+    /// assert_eq!(map.next_zero_wrapping(5), Some(3));
+    /// assert_eq!(Bitmap::<1>::full().next_zero_wrapping(5), None);
+    /// ```
+    ///
+    /// # Panics
+    /// Panic if `from` is out of range.
+    pub fn next_zero_wrapping(&self, from: usize) -> Option<usize> {
+        if __out_bound(BYTES, from) {
+            panic!("Bitmap: searching out of range");
+        }
+        __find_first_bit_wrapping(&self.bits, false, from)
+    }
+
+    /// Find the first '1' at or after `from`, wrapping around to bit 0
+    /// once, like [`Bitmap::next_zero_wrapping()`].
+    ///
+    /// # Return
+    /// The index of the bit, or [`None`] if there is no '1' in the map.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let map = he_lang!(2 | 9; 16);
+    /// assert_eq!(map.next_one_wrapping(2), Some(2));
+    /// assert_eq!(map.next_one_wrapping(3), Some(9));
+    /// assert_eq!(map.next_one_wrapping(10), Some(2));
+    /// ```
+    ///
+    /// # Panics
+    /// Panic if `from` is out of range.
+    pub fn next_one_wrapping(&self, from: usize) -> Option<usize> {
+        if __out_bound(BYTES, from) {
+            panic!("Bitmap: searching out of range");
+        }
+        __find_first_bit_wrapping(&self.bits, true, from)
+    }
+
+    /// Count the '1's in a range of bits, like `count_range(8..16)` or
+    /// `count_range(..)`. An empty range gives 0.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let map = he_lang!(1 | 3 | 9 | 15; 16);
+    /// assert_eq!(map.count_range(..), 4);
+    /// assert_eq!(map.count_range(2..=9), 2);
+    /// assert_eq!(map.count_range(10..), 1);
+    /// ```
+    ///
+    /// # Panics
+    /// Panic if the range is reversed or goes out of range.
+    pub fn count_range<R: core::ops::RangeBounds<usize>>(&self, range: R) -> usize {
+        let (start, end) = match __range_to_pair(&range, BYTES * 8) {
+            Some(pair) => pair,
+            None => panic!("Bitmap: counting out of range"),
+        };
+        __count_range(&self.bits, start, end)
+    }
+
+    /// Exchange the values of two bits by specifying their indexes.
+    ///
+    /// Swapping a bit with itself does nothing.
+    ///
+    /// # Return
+    /// `&mut self`, allowing a call chain.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let mut map = newmap!(0b_0001; 16);
+    /// map.swap(0, 3);
+    /// assert_eq!(&map.range_to_string(0, 8).unwrap(), "00001000");
+    /// map.swap(3, 12).swap(0, 0);
+    /// assert_eq!(&map.range_to_string(0, 16).unwrap(), "00010000 00000000");
+    /// ```
+    ///
+    /// # Panics
+    /// Panic if `i` or `j` is out of range.
+    pub fn swap(&mut self, i: usize, j: usize) -> &mut Self {
+        if __out_bound(BYTES, i) || __out_bound(BYTES, j) {
+            panic!("Bitmap: swapping out of range");
+        }
+        let (ibyte, ibit) = __idx_1dto2d(i);
+        let (jbyte, jbit) = __idx_1dto2d(j);
+        // Only flip both bits when they differ, so the same-byte and
+        // `i == j` cases need no special care.
+        if self.__get_bool(ibyte, ibit) != self.__get_bool(jbyte, jbit) {
+            *self.__get_mut_u8(ibyte) ^= 1u8 << ibit;
+            *self.__get_mut_u8(jbyte) ^= 1u8 << jbit;
+        }
+        self
+    }
+
+    /// Reverse the byte order of the map, like [`u32::swap_bytes()`].
+    ///
+    /// The order of bits inside each byte is kept. If `BYTES` is odd,
+    /// the middle byte stays where it is.
+    ///
+    /// # Return
+    /// `&mut self`, allowing a call chain.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let mut map: Bitmap<3> = 0x_01_02_03_u32.into();
+    /// map.reverse_bytes();
+    /// assert_eq!(Into::<[u8; 3]>::into(map), [0x01, 0x02, 0x03]);
+    /// ```
+    pub fn reverse_bytes(&mut self) -> &mut Self {
+        self.bits.reverse();
+        self
+    }
+
+    /// Get a whole byte of the map. The byte `i` covers the bits
+    /// `8*i .. 8*i+8`, with the bit `8*i` as its LSB.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let map = he_lang!(1 | 9 | 15; 16);
+    /// assert_eq!(map.get_byte(0), 0b_10);
+    /// assert_eq!(map.get_byte(1), 0b_1000_0010);
+    /// ```
+    ///
+    /// # Panics
+    /// Panic if `i >= BYTES`.
+    pub fn get_byte(&self, i: usize) -> u8 {
+        match self.try_get_byte(i) {
+            Some(byte) => byte,
+            None => panic!("Bitmap: getting out of range"),
+        }
+    }
+
+    /// Like [`Bitmap::get_byte()`], but gives [`None`] if `i >= BYTES`.
+    pub fn try_get_byte(&self, i: usize) -> Option<u8> {
+        self.bits.get(i).copied()
+    }
+
+    /// Overwrite a whole byte of the map. The byte `i` covers the bits
+    /// `8*i .. 8*i+8`, with the bit `8*i` as its LSB.
+    ///
+    /// # Return
+    /// `&mut self`, allowing a call chain.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let mut map = newmap!(;16);
+    /// map.set_byte(1, 0b_1000_0001);
+    /// assert_eq!(map, he_lang!(8 | 15; 16));
+    /// ```
+    ///
+    /// # Panics
+    /// Panic if `i >= BYTES`.
+    pub fn set_byte(&mut self, i: usize, value: u8) -> &mut Self {
+        match self.try_set_byte(i, value) {
+            Some(map) => map,
+            None => panic!("Bitmap: setting out of range"),
+        }
+    }
+
+    /// Like [`Bitmap::set_byte()`], but does nothing and gives [`None`]
+    /// if `i >= BYTES`.
+    ///
+    /// # Return
+    /// `Some(&mut self)` if the byte is written.
+    pub fn try_set_byte(&mut self, i: usize, value: u8) -> Option<&mut Self> {
+        *self.bits.get_mut(i)? = value;
+        Some(self)
+    }
+
+    /// Split the map into the bytes before the first `usize`-aligned one,
+    /// the aligned words, and the bytes after them, like
+    /// [`slice::align_to()`]. A map has no alignment of its own, so the
+    /// split depends on where it is.
+    ///
+    /// The words are in memory order. On a little-endian target, the bit
+    /// `j` of the word `k`, counted from the LSB, is the bit
+    /// `8 * prefix.len() + usize::BITS * k + j` of the map. On a
+    /// big-endian one, read the words with [`usize::from_le()`] first.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let map = Bitmap::<64>::full();
+    /// let (prefix, words, suffix) = map.as_words();
+    /// assert_eq!(prefix.len() + words.len() * usize::BITS as usize / 8 + suffix.len(), 64);
+    /// assert!(words.iter().all(|&word| word == usize::MAX));
+    /// ```
+    pub fn as_words(&self) -> (&[u8], &[usize], &[u8]) {
+        __as_words(&self.bits)
+    }
+
+    /// The mutable version of [`Bitmap::as_words()`].
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let mut map = Bitmap::<64>::new();
+    /// let (prefix, words, _) = map.as_mut_words();
+    /// let start = prefix.len() * 8;
+    /// if let Some(word) = words.first_mut() {
+    ///     *word = usize::from_le(1);
+    ///     assert_eq!(map.find_first_one(), Some(start));
+    /// }
+    /// ```
+    pub fn as_mut_words(&mut self) -> (&mut [u8], &mut [usize], &mut [u8]) {
+        __as_words_mut(&mut self.bits)
+    }
+
+    /// Give the map as words if it is exactly made of them: `BYTES` is a
+    /// multiple of the size of [`usize`], and the map is aligned like a
+    /// `usize`. The bit order is the same as [`Bitmap::as_words()`].
+    ///
+    /// # Return
+    /// [`None`] if the map is not made of whole aligned words.
+    pub fn try_as_words(&self) -> Option<&[usize]> {
+        match self.as_words() {
+            ([], words, []) => Some(words),
+            _ => None,
+        }
+    }
+
+    /// The mutable version of [`Bitmap::try_as_words()`].
+    pub fn try_as_mut_words(&mut self) -> Option<&mut [usize]> {
+        match self.as_mut_words() {
+            ([], words, []) => Some(words),
+            _ => None,
+        }
+    }
+
+    /// Extract the bits `[offset, offset + width)` as an integer, with
+    /// the bit `offset` as its LSB.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// // A 3-bit field at 6 holding 0b_101, crossing a byte.
+    /// let map = he_lang!(6 | 8; 16);
+    /// assert_eq!(map.get_bits(6, 3), 0b_101);
+    /// assert_eq!(map.get_bits(0, 16), (1 << 6) | (1 << 8));
+    /// ```
+    ///
+    /// # Panics
+    /// Panic if `width > 64` or `offset + width > BYTES * 8`.
+    pub fn get_bits(&self, offset: usize, width: usize) -> u64 {
+        let end = match offset.checked_add(width) {
+            Some(end) if width <= 64 && end <= BYTES * 8 => end,
+            _ => panic!("Bitmap: getting out of range"),
+        };
+        if width == 0 {
+            return 0;
+        }
+        // A field of at most 64 bits spans at most 9 bytes.
+        let (sbyte, sbit) = __idx_1dto2d(offset);
+        let ebyte = __idx_get_byte(end - 1);
+        let mut acc = 0u128;
+        for (k, &byte) in self.bits[sbyte..=ebyte].iter().enumerate() {
+            acc |= (byte as u128) << (8 * k);
+        }
+        ((acc >> sbit) & ((1u128 << width) - 1)) as u64
+    }
+
+    /// Write the low `width` bits of `value` into the bits
+    /// `[offset, offset + width)`, with the LSB of `value` at the bit
+    /// `offset`. Higher bits of `value` are ignored, and the bits out of
+    /// the field are kept.
+    ///
+    /// # Return
+    /// `&mut self`, allowing a call chain.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let mut map = newmap!(0x_ffff; 16);
+    /// map.set_bits(6, 3, 0b_010);
+    /// assert_eq!(map.get_bits(6, 3), 0b_010);
+    /// assert_eq!(map.count(), 14);
+    /// ```
+    ///
+    /// # Panics
+    /// Panic if `width > 64` or `offset + width > BYTES * 8`.
+    pub fn set_bits(&mut self, offset: usize, width: usize, value: u64) -> &mut Self {
+        let end = match offset.checked_add(width) {
+            Some(end) if width <= 64 && end <= BYTES * 8 => end,
+            _ => panic!("Bitmap: setting out of range"),
+        };
+        if width == 0 {
+            return self;
+        }
+        let (sbyte, sbit) = __idx_1dto2d(offset);
+        let ebyte = __idx_get_byte(end - 1);
+        let mask = ((1u128 << width) - 1) << sbit;
+        let value = ((value as u128) << sbit) & mask;
+        for (k, byte) in self.bits[sbyte..=ebyte].iter_mut().enumerate() {
+            let m = (mask >> (8 * k)) as u8;
+            *byte = (*byte & !m) | ((value >> (8 * k)) as u8 & m);
+        }
+        self
+    }
+
+    /// Like [`Bitmap::set_bits()`], but does nothing if `value` has more
+    /// than `width` bits, or the field is out of range.
+    ///
+    /// # Return
+    /// `Some(&mut self)` if the field is written, otherwise [`None`].
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let mut map = newmap!(;16);
+    /// assert!(map.checked_set_bits(6, 3, 0b_1000).is_none());
+    /// assert!(map.checked_set_bits(14, 3, 0b_100).is_none());
+    /// assert!(map.checked_set_bits(6, 3, 0b_100).is_some());
+    /// assert_eq!(map, he_lang!(8; 16));
+    /// ```
+    pub fn checked_set_bits(&mut self, offset: usize, width: usize, value: u64) -> Option<&mut Self> {
+        let fits = width >= 64 || value >> width == 0;
+        match offset.checked_add(width) {
+            Some(end) if fits && width <= 64 && end <= BYTES * 8 => {
+                Some(self.set_bits(offset, width, value))
+            }
+            _ => None,
+        }
+    }
 }
 
 impl<const BYTES: usize> BitsManage for Bitmap<BYTES> {
@@ -374,32 +1368,7 @@ impl<const BYTES: usize> BitsManage for Bitmap<BYTES> {
     /// ```
     #[inline]
     fn find_first_one(&self) -> Option<usize> {
-        let mut index: usize = 0;
-        for b in &self.bits {
-            let test = (*b) & (!(*b)).wrapping_add(1);
-            index += match test {
-                // When test == 0, we jump to next byte.
-                0 => 8,
-                // Make a list for speed up.
-                1 => 0,
-                2 => 1,
-                4 => 2,
-                8 => 3,
-                16 => 4,
-                32 => 5,
-                64 => 6,
-                128 => 7,
-                // Unreachable.
-                _ => panic!("Unexpected arithmetic error"),
-            };
-            if test > 0 {
-                break;
-            }
-        }
-        match index >= BYTES * 8 {
-            true => None,
-            false => Some(index),
-        }
+        __find_first_one(&self.bits)
     }
 
     /// Get the minimal index of a '0' in the bitmap.
@@ -424,32 +1393,7 @@ impl<const BYTES: usize> BitsManage for Bitmap<BYTES> {
     /// ```
     #[inline]
     fn find_first_zero(&self) -> Option<usize> {
-        let mut index: usize = 0;
-        for b in &self.bits {
-            let test = (*b).wrapping_add(1) & !(*b);
-            index += match test {
-                // When test == 0, we jump to next byte.
-                0 => 8,
-                // Make a list for speed up.
-                1 => 0,
-                2 => 1,
-                4 => 2,
-                8 => 3,
-                16 => 4,
-                32 => 5,
-                64 => 6,
-                128 => 7,
-                // Unreachable.
-                _ => panic!("Unexpected arithmetic error"),
-            };
-            if test > 0 {
-                break;
-            }
-        }
-        match index >= BYTES * 8 {
-            true => None,
-            false => Some(index),
-        }
+        __find_first_zero(&self.bits)
     }
 
     /// Count how many '1's are in the bitmap.
@@ -472,15 +1416,7 @@ impl<const BYTES: usize> BitsManage for Bitmap<BYTES> {
     /// ```
     #[inline]
     fn count(&self) -> usize {
-        let mut cnt: usize = 0;
-        for b in &self.bits {
-            let mut temp = *b;
-            temp = ((temp & 0b10101010) >> 1) + (temp & 0b01010101);
-            temp = ((temp & 0b11001100) >> 2) + (temp & 0b00110011);
-            temp = (temp >> 4) + (temp & 0b1111);
-            cnt += temp as usize;
-        }
-        cnt
+        __count_ones(&self.bits)
     }
 
     /// Set a bit to 1 by specifying the index.
@@ -578,7 +1514,7 @@ impl<const BYTES: usize> BitsManage for Bitmap<BYTES> {
     /// assert_eq!(&map.range_to_string(0, 8).unwrap(), "11111101");
     /// ```
     fn set_all(&mut self) -> &mut Self {
-        *&mut self.bits = [255; BYTES];
+        self.bits.fill(0xff);
         self
     }
 
@@ -597,7 +1533,7 @@ impl<const BYTES: usize> BitsManage for Bitmap<BYTES> {
     /// assert_eq!(&map.range_to_string(0, 8).unwrap(), "00000010");
     /// ```
     fn reset_all(&mut self) -> &mut Self {
-        *&mut self.bits = [0; BYTES];
+        self.bits.fill(0);
         self
     }
 
@@ -619,10 +1555,7 @@ impl<const BYTES: usize> BitsManage for Bitmap<BYTES> {
     /// assert_eq!(&map.range_to_string(0, 8).unwrap(), "10101011");
     /// ```
     fn flip_all(&mut self) -> &mut Self {
-        let arr = &mut self.bits;
-        for i in arr {
-            *i = !*i;
-        }
+        __flip_all(&mut self.bits);
         self
     }
 }
@@ -632,22 +1565,63 @@ impl<const BYTES: usize> BitsManage for Bitmap<BYTES> {
 impl<const BYTES: usize> Bitmap<BYTES> {
     #[inline]
     fn __get_bool(&self, byte: usize, bit: usize) -> bool {
-        &self.bits[byte] & (1 << bit) != 0
+        self.bits[byte] & (1 << bit) != 0
     }
 
     #[inline]
-    fn __copy_u8<'map>(&'map self, byte: usize) -> u8 {
-        self.bits[byte].clone()
+    fn __copy_u8(&self, byte: usize) -> u8 {
+        self.bits[byte]
     }
 
     #[inline]
-    fn __get_mut_u8<'map>(&'map mut self, byte: usize) -> &'map mut u8 {
+    fn __get_mut_u8(&mut self, byte: usize) -> &mut u8 {
         &mut self.bits[byte]
     }
+
+    /// Find the last '1', returns its index.
+    fn __find_last_one(&self) -> Option<usize> {
+        __find_last_bit(&self.bits, true)
+    }
+
+    /// Allocate a map on the heap with every byte set to `pattern`.
+    fn __boxed_with(pattern: u8) -> Box<Self> {
+        let layout = Layout::new::<Self>();
+        // SAFETY: a `Bitmap` is nothing but its `[u8; BYTES]`, so it has
+        // no padding and any bytes are a valid map once all of them are
+        // written. A dangling pointer is valid for a zero-sized box.
+        unsafe {
+            if layout.size() == 0 {
+                return Box::from_raw(core::ptr::NonNull::<Self>::dangling().as_ptr());
+            }
+            let ptr = match pattern {
+                0 => alloc_zeroed(layout),
+                _ => alloc(layout),
+            };
+            if ptr.is_null() {
+                handle_alloc_error(layout);
+            }
+            if pattern != 0 {
+                ptr.write_bytes(pattern, layout.size());
+            }
+            Box::from_raw(ptr.cast::<Self>())
+        }
+    }
 }
 
 use crate::tools::inner_use::*;
+use alloc::alloc::{alloc, alloc_zeroed, handle_alloc_error, Layout};
+use alloc::boxed::Box;
 
-pub use crate::{he_lang, newmap};
+pub use crate::{bits, bitstr, boxmap, constmap, he_lang, newmap, rangemap};
+#[cfg(all(target_has_atomic = "8", target_has_atomic = "ptr"))]
+pub use atomic::AtomicBitmap;
+pub use cursor::BitmapCursor;
+pub use dynamic::DynBitmap;
+pub use error::BitmapError;
+pub use fmt::{BitmapDisplay, OnesDisplay, DEBUG_SUMMARY_BYTES};
+pub use hier::{hier_summary_bytes, HierBitmap, HIER_BLOCK_BYTES};
+pub use iter::{DrainOnes, Ones};
 pub use refs::*;
-pub use traits::FillPrefix;
+pub use traits::{FillAt, FillPrefix, FillSuffix};
+pub use view::{BitmapMut, BitmapRef};
+pub use volatile::VolatileBitmap;
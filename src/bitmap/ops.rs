@@ -1,14 +1,25 @@
 //! Implementations of logic operations like [`BitAnd`] and [`BitOrAssign`] 
-//! for `Bitmap`. 
+//! for `Bitmap`, and the shift operations like [`Shl`]. 
 //! 
-//! Also including [`Deref`] of `BitRef` and `BitRefMut`.
+//! Also including [`Index`] of bits and byte ranges, and [`Deref`] of
+//! `BitRef`, `BitRefMut` and `BitMut`.
 
 use super::{*, refs::*};
-use core::ops::{BitAnd, BitAndAssign, BitOrAssign, Deref, Index};
+use core::ops::{
+    BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Deref, Index, Range,
+    RangeFrom, RangeFull, RangeTo, Shl, ShlAssign, Shr, ShrAssign,
+};
+
+/// The interned values that [`Index`] of a bitmap refers to.
+static TRUE: bool = true;
+static FALSE: bool = false;
 
 impl<const BYTES: usize> Index<usize> for Bitmap<BYTES> {
   type Output = bool;
   /// Immutably index into a bit's bool value.
+  ///
+  /// The bits are not stored as [`bool`]s, so the reference is a
+  /// `&'static bool` to one of two interned values, not into the map.
   /// 
   /// > Inspired by [`bitvec`](https://docs.rs/bitvec).
   /// 
@@ -20,15 +31,64 @@ impl<const BYTES: usize> Index<usize> for Bitmap<BYTES> {
   /// assert_eq!(map[0], true);
   /// assert_eq!(map[4], false);
   /// assert_eq!(map[7], true);
+  ///
+  /// fn takes_ref(bit: &bool) -> bool { *bit }
+  /// assert_eq!(takes_ref(&map[7]), true);
   /// ```
+  ///
+  /// # Panics
+  /// Panic if `index` is out of range, like [`Bitmap::get_bool()`].
   fn index(&self, index: usize) -> &Self::Output {
-      match self.test(index) {
-        true => &true,
-        false => &false,
+      match self.get_bool(index) {
+        true => &TRUE,
+        false => &FALSE,
       }
   }
 }
 
+impl<const BYTES: usize> Index<Range<usize>> for Bitmap<BYTES> {
+    type Output = [u8];
+    /// Immutably index into a range of **bytes**, not bits: `&map[2..5]`
+    /// gives the bytes 2, 3 and 4, which hold the bits `16..40`.
+    ///
+    /// [`RangeTo`], [`RangeFrom`] and [`RangeFull`] are indexed the same
+    /// way.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let map = Bitmap::<4>::from(0x_0f_00_01_80_u32);
+    /// assert_eq!(&map[1..3], &[0x01, 0x00]);
+    /// assert_eq!(&map[..1], &[0x80]);
+    /// assert_eq!(&map[3..], &[0x0f]);
+    /// assert_eq!(map[..].len(), 4);
+    /// ```
+    ///
+    /// # Panics
+    /// Panic if the range is out of the bytes, like indexing a slice.
+    fn index(&self, range: Range<usize>) -> &Self::Output {
+        &self.bits[range]
+    }
+}
+
+macro_rules! impl_index_bytes {
+    ($t:ty) => {
+        impl<const BYTES: usize> Index<$t> for Bitmap<BYTES> {
+            type Output = [u8];
+            /// Immutably index into a range of **bytes**, not bits, like
+            /// `&map[2..5]`.
+            fn index(&self, range: $t) -> &Self::Output {
+                &self.bits[range]
+            }
+        }
+    };
+}
+
+impl_index_bytes!(RangeTo<usize>);
+impl_index_bytes!(RangeFrom<usize>);
+impl_index_bytes!(RangeFull);
+
 impl<'map, const BYTES: usize> Deref for BitRef<'map, BYTES> {
     type Target = bool;
     fn deref(&self) -> &Self::Target {
@@ -43,6 +103,13 @@ impl<'map, const BYTES: usize> Deref for BitRefMut<'map, BYTES> {
     }
 }
 
+impl<'a> Deref for BitMut<'a> {
+    type Target = bool;
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
 impl<const BYTES: usize, const N: usize> BitAnd<[u8; N]> for &Bitmap<BYTES> {
     type Output = [u8; N];
 
@@ -71,6 +138,20 @@ impl<const BYTES: usize, const N: usize> BitAnd<[u8; N]> for &Bitmap<BYTES> {
     /// let arr = 1u8;
     /// assert_eq!(&map & arr, 1u8);
     /// ```
+    /// [`char`] has no such alias, as the result may not be a valid
+    /// [`char`]. Convert it into a [`u32`] first:
+    /// ```compile_fail
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let map = Bitmap::<4>::from(0x_d800_u32);
+    /// let _ = &map & '\u{ffff}';
+    /// ```
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let map = Bitmap::<4>::from(0x_d800_u32);
+    /// assert_eq!(&map & u32::from('\u{ffff}'), 0x_d800);
+    /// ```
     /// # See
     /// About the asymmetry between `BYTES` and `N`, see
     /// `bitmap::Bitmap<BYTES>::bitand_assign`.
@@ -78,16 +159,10 @@ impl<const BYTES: usize, const N: usize> BitAnd<[u8; N]> for &Bitmap<BYTES> {
     /// In general, if `N > BYTES`, the
     /// returned array will have `（N - BYTES） * 8` leading zero flags.
     fn bitand(self, rhs: [u8; N]) -> Self::Output {
-        let size = N.min(BYTES);
-        let mut arr = rhs.clone();
-        for i in 0..size {
-            let byte = self.__copy_u8(i);
-            arr[i] &= byte;
-        }
+        let mut arr = rhs;
+        __zip_words_with(&mut arr, &self.bits, |a, b| a & b);
         if N > BYTES {
-            for i in size..N {
-                arr[i] = 0;
-            }
+            arr[BYTES..].fill(0);
         }
         arr
     }
@@ -130,14 +205,9 @@ impl<const BYTES: usize, const N: usize> BitAndAssign<[u8; N]> for Bitmap<BYTES>
     /// assert_eq!(&map.range_to_string(8, 16).unwrap(), "00000000");
     /// ```
     fn bitand_assign(&mut self, rhs: [u8; N]) {
-        let size = N.min(BYTES);
-        for i in 0..size {
-            __byte_and_u8(self.__get_mut_u8(i), rhs[i]);
-        }
+        __zip_words_with(&mut self.bits, &rhs, |a, b| a & b);
         if BYTES > N {
-            for i in size..BYTES {
-                *self.__get_mut_u8(i) = 0;
-            }
+            self.bits[N..].fill(0);
         }
     }
 }
@@ -168,46 +238,381 @@ impl<const BYTES: usize, const N: usize> BitOrAssign<[u8; N]> for Bitmap<BYTES>
     /// assert_eq!(map.test(0), true);
     /// ```
     fn bitor_assign(&mut self, rhs: [u8; N]) {
-        let size = N.min(BYTES);
-        for i in 0..size {
-            __byte_or_u8(self.__get_mut_u8(i), rhs[i]);
-        }
+        __zip_words_with(&mut self.bits, &rhs, |a, b| a | b);
+    }
+}
+
+impl<const BYTES: usize, const N: usize> BitOrAssign<&[u8; N]> for Bitmap<BYTES> {
+    /// OR the given bitmap with a borrowed array of [`u8`] values, like
+    /// a byte-string literal.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let mut map = Bitmap::<2>::new();
+    /// map |= b"\x01\x80";
+    /// assert_eq!(&map.range_to_string(0, 16).unwrap(), "10000000 00000001");
+    /// ```
+    fn bitor_assign(&mut self, rhs: &[u8; N]) {
+        *self |= *rhs;
+    }
+}
+
+impl<const BYTES: usize, const N: usize> BitXor<[u8; N]> for &Bitmap<BYTES> {
+    type Output = [u8; N];
+
+    /// XOR the given bitmap (in `ref`) to an array of [`u8`] values.
+    ///
+    /// # Generics
+    /// * `BYTES`: the byte length of the bitmap.
+    /// * `N`: the length of the [`u8`] array.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let map = Bitmap::<2>::from([0b_1100u8; 2]);
+    /// // NOTE: the bitmap shoule be in ref, and should be in left.
+    /// assert_eq!(&map ^ [0b_1010u8; 2], [0b_0110u8; 2]);
+    /// ```
+    /// There are also aliases for integer types:
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let map = Bitmap::<1>::from(0b_1100u8);
+    /// assert_eq!(&map ^ 0b_1010u8, 0b_0110u8);
+    /// ```
+    /// # See
+    /// If `N > BYTES`, the extra bytes of the array are given back
+    /// unchanged.
+    fn bitxor(self, rhs: [u8; N]) -> Self::Output {
+        let mut arr = rhs;
+        __zip_words_with(&mut arr, &self.bits, |a, b| a ^ b);
+        arr
+    }
+}
+
+impl<const BYTES: usize, const N: usize> BitXorAssign<[u8; N]> for Bitmap<BYTES> {
+    /// XOR the given bitmap with an array of [`u8`] values.
+    ///
+    /// # Generics
+    /// * `BYTES`: the byte length of the bitmap.
+    /// * `N`: the length of the [`u8`] array.
+    ///
+    /// # Examples
+    /// A simple example:
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let mut map = Bitmap::<1>::from([0b_00000001_u8; 1]);
+    /// map ^= [0b_00000011_u8; 1];
+    /// assert_eq!(&map.range_to_string(0, 8).unwrap(), "00000010");
+    /// ```
+    ///
+    /// There are also aliases for integer types:
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let mut map = Bitmap::<1>::from(0b_00000001_u8);
+    /// map ^= 0b_00000011_u8;
+    /// assert_eq!(&map.range_to_string(0, 8).unwrap(), "00000010");
+    /// ```
+    ///
+    /// Like `|=`, the bytes of the array beyond the bitmap are ignored.
+    fn bitxor_assign(&mut self, rhs: [u8; N]) {
+        __zip_words_with(&mut self.bits, &rhs, |a, b| a ^ b);
     }
 }
 
+impl<const BYTES: usize> ShlAssign<usize> for Bitmap<BYTES> {
+    /// Shift the bitmap toward higher indexes by `rhs` bits, as if it
+    /// were a wide integer. The top bits are discarded and the bottom
+    /// is filled with '0'.
+    ///
+    /// Shifting by [`Bitmap::bit_len()`] or more gives an all-zero map.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let mut map = newmap!(0b_11000001; 16);
+    /// map <<= 3;
+    /// assert_eq!(&map.range_to_string(0, 16).unwrap(), "00000110 00001000");
+    /// map <<= 16;
+    /// assert_eq!(map.none(), true);
+    /// ```
+    fn shl_assign(&mut self, rhs: usize) {
+        self.__shift_left(rhs);
+    }
+}
+
+impl<const BYTES: usize> ShrAssign<usize> for Bitmap<BYTES> {
+    /// Shift the bitmap toward lower indexes by `rhs` bits, as if it
+    /// were a wide integer. The bottom bits are discarded and the top
+    /// is filled with '0'.
+    ///
+    /// Shifting by [`Bitmap::bit_len()`] or more gives an all-zero map.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let mut map = newmap!(0b_00000110_00001000; 16);
+    /// map >>= 3;
+    /// assert_eq!(&map.range_to_string(0, 16).unwrap(), "00000000 11000001");
+    /// ```
+    fn shr_assign(&mut self, rhs: usize) {
+        self.__shift_right(rhs);
+    }
+}
+
+impl<const BYTES: usize> Shl<usize> for Bitmap<BYTES> {
+    type Output = Self;
+
+    /// Shift the bitmap toward higher indexes by `rhs` bits.
+    ///
+    /// # See
+    /// `bitmap::Bitmap<BYTES>::shl_assign`.
+    fn shl(mut self, rhs: usize) -> Self::Output {
+        self <<= rhs;
+        self
+    }
+}
+
+impl<const BYTES: usize> Shr<usize> for Bitmap<BYTES> {
+    type Output = Self;
+
+    /// Shift the bitmap toward lower indexes by `rhs` bits.
+    ///
+    /// # See
+    /// `bitmap::Bitmap<BYTES>::shr_assign`.
+    fn shr(mut self, rhs: usize) -> Self::Output {
+        self >>= rhs;
+        self
+    }
+}
+
+impl<const BYTES: usize> BitAnd<&Bitmap<BYTES>> for &Bitmap<BYTES> {
+    type Output = Bitmap<BYTES>;
+
+    /// AND two bitmaps of the same length, giving a new bitmap. Neither
+    /// of the operands is modified.
+    ///
+    /// Owned operands are also accepted, e.g. `a & b`, `a & &b` and
+    /// `&a & b`. An owned left operand is reused for the result.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let a = newmap!(0b_1100; 8);
+    /// let b = newmap!(0b_1010; 8);
+    /// let c = &a & &b;
+    /// assert_eq!(&c.range_to_string(0, 8).unwrap(), "00001000");
+    /// assert_eq!(&(a & b).range_to_string(0, 8).unwrap(), "00001000");
+    /// ```
+    fn bitand(self, rhs: &Bitmap<BYTES>) -> Self::Output {
+        Bitmap { bits: self.bits } & rhs
+    }
+}
+
+impl<const BYTES: usize> BitOr<&Bitmap<BYTES>> for &Bitmap<BYTES> {
+    type Output = Bitmap<BYTES>;
+
+    /// OR two bitmaps of the same length, giving a new bitmap. Neither
+    /// of the operands is modified.
+    ///
+    /// Owned operands are also accepted, e.g. `a | b`, `a | &b` and
+    /// `&a | b`. An owned left operand is reused for the result.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let a = newmap!(0b_1100; 8);
+    /// let b = newmap!(0b_1010; 8);
+    /// let c = &a | &b;
+    /// assert_eq!(&c.range_to_string(0, 8).unwrap(), "00001110");
+    /// assert_eq!(&(a | b).range_to_string(0, 8).unwrap(), "00001110");
+    /// ```
+    fn bitor(self, rhs: &Bitmap<BYTES>) -> Self::Output {
+        Bitmap { bits: self.bits } | rhs
+    }
+}
+
+impl<const BYTES: usize> BitXor<&Bitmap<BYTES>> for &Bitmap<BYTES> {
+    type Output = Bitmap<BYTES>;
+
+    /// XOR two bitmaps of the same length, giving a new bitmap. Neither
+    /// of the operands is modified.
+    ///
+    /// Owned operands are also accepted, e.g. `a ^ b`, `a ^ &b` and
+    /// `&a ^ b`. An owned left operand is reused for the result.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let a = newmap!(0b_1100; 8);
+    /// let b = newmap!(0b_1010; 8);
+    /// let c = &a ^ &b;
+    /// assert_eq!(&c.range_to_string(0, 8).unwrap(), "00000110");
+    /// assert_eq!(&(a ^ b).range_to_string(0, 8).unwrap(), "00000110");
+    /// ```
+    fn bitxor(self, rhs: &Bitmap<BYTES>) -> Self::Output {
+        Bitmap { bits: self.bits } ^ rhs
+    }
+}
+
+impl<const BYTES: usize> BitAndAssign<&Bitmap<BYTES>> for Bitmap<BYTES> {
+    /// AND the given bitmap with another bitmap of the same length,
+    /// byte by byte. The other map is only borrowed, so this also works
+    /// for very large maps.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let mut map = newmap!(0b_1100; 8);
+    /// map &= &newmap!(0b_1010; 8);
+    /// assert_eq!(&map.range_to_string(0, 8).unwrap(), "00001000");
+    /// ```
+    fn bitand_assign(&mut self, rhs: &Bitmap<BYTES>) {
+        __zip_words_with(&mut self.bits, &rhs.bits, |a, b| a & b);
+    }
+}
+
+impl<const BYTES: usize> BitOrAssign<&Bitmap<BYTES>> for Bitmap<BYTES> {
+    /// OR the given bitmap with another bitmap of the same length,
+    /// byte by byte. The other map is only borrowed, so this also works
+    /// for very large maps.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let mut map = newmap!(0b_1100; 8);
+    /// map |= &newmap!(0b_1010; 8);
+    /// assert_eq!(&map.range_to_string(0, 8).unwrap(), "00001110");
+    /// ```
+    fn bitor_assign(&mut self, rhs: &Bitmap<BYTES>) {
+        __zip_words_with(&mut self.bits, &rhs.bits, |a, b| a | b);
+    }
+}
+
+impl<const BYTES: usize> BitXorAssign<&Bitmap<BYTES>> for Bitmap<BYTES> {
+    /// XOR the given bitmap with another bitmap of the same length,
+    /// byte by byte. The other map is only borrowed, so this also works
+    /// for very large maps.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let mut map = newmap!(0b_1100; 8);
+    /// map ^= &newmap!(0b_1010; 8);
+    /// assert_eq!(&map.range_to_string(0, 8).unwrap(), "00000110");
+    /// ```
+    fn bitxor_assign(&mut self, rhs: &Bitmap<BYTES>) {
+        __zip_words_with(&mut self.bits, &rhs.bits, |a, b| a ^ b);
+    }
+}
+
+macro_rules! impl_map_op {
+    ($tr:ident, $f:ident, $op:tt, $aop:tt) => {
+        impl<const BYTES: usize> $tr<&Bitmap<BYTES>> for Bitmap<BYTES> {
+            type Output = Bitmap<BYTES>;
+            fn $f(mut self, rhs: &Bitmap<BYTES>) -> Self::Output {
+                self $aop rhs;
+                self
+            }
+        }
+
+        impl<const BYTES: usize> $tr<Bitmap<BYTES>> for Bitmap<BYTES> {
+            type Output = Bitmap<BYTES>;
+            fn $f(self, rhs: Bitmap<BYTES>) -> Self::Output {
+                self $op &rhs
+            }
+        }
+
+        impl<const BYTES: usize> $tr<Bitmap<BYTES>> for &Bitmap<BYTES> {
+            type Output = Bitmap<BYTES>;
+            fn $f(self, rhs: Bitmap<BYTES>) -> Self::Output {
+                // All the ops are commutative, so reuse the owned one.
+                rhs $op self
+            }
+        }
+    };
+}
+
+impl_map_op!(BitAnd, bitand, &, &=);
+impl_map_op!(BitOr, bitor, |, |=);
+impl_map_op!(BitXor, bitxor, ^, ^=);
+
 macro_rules! impl_bitand {
     ($t:ty) => {
         impl<const BYTES: usize> BitAnd<$t> for &Bitmap<BYTES> {
             type Output = $t;
             fn bitand(self, rhs: $t) -> Self::Output {
-                const SIZE: usize = core::mem::size_of::<$t>();
-                let arr:[u8; SIZE] = unsafe { core::mem::transmute(rhs) };
-                let res = self & arr;
-                unsafe { core::mem::transmute(res) }
+                let res = self & rhs.to_le_bytes();
+                <$t>::from_le_bytes(res)
             }
         }
     };
 }
 
 macro_rules! impl_bitand_assign {
+    (char) => {
+        impl<const BYTES: usize> BitAndAssign<char> for Bitmap<BYTES> {
+            fn bitand_assign(&mut self, rhs: char) {
+                *self &= u32::from(rhs).to_le_bytes()
+            }
+        }
+    };
     ($t:ty) => {
         impl<const BYTES: usize> BitAndAssign<$t> for Bitmap<BYTES> {
             fn bitand_assign(&mut self, rhs: $t) {
-                const SIZE: usize = core::mem::size_of::<$t>();
-                let arr:[u8; SIZE] = unsafe { core::mem::transmute(rhs) };
-                *self &= arr
+                *self &= rhs.to_le_bytes()
             }
         }
     };
 }
 
 macro_rules! impl_bitor_assign {
+    (char) => {
+        impl<const BYTES: usize> BitOrAssign<char> for Bitmap<BYTES> {
+            fn bitor_assign(&mut self, rhs: char) {
+                *self |= u32::from(rhs).to_le_bytes()
+            }
+        }
+    };
     ($t:ty) => {
         impl<const BYTES: usize> BitOrAssign<$t> for Bitmap<BYTES> {
             fn bitor_assign(&mut self, rhs: $t) {
-                const SIZE: usize = core::mem::size_of::<$t>();
-                let arr:[u8; SIZE] = unsafe { core::mem::transmute(rhs) };
-                *self |= arr
+                *self |= rhs.to_le_bytes()
+            }
+        }
+    };
+}
+
+macro_rules! impl_bitxor {
+    ($t:ty) => {
+        impl<const BYTES: usize> BitXor<$t> for &Bitmap<BYTES> {
+            type Output = $t;
+            fn bitxor(self, rhs: $t) -> Self::Output {
+                let res = self ^ rhs.to_le_bytes();
+                <$t>::from_le_bytes(res)
+            }
+        }
+    };
+}
+
+macro_rules! impl_bitxor_assign {
+    ($t:ty) => {
+        impl<const BYTES: usize> BitXorAssign<$t> for Bitmap<BYTES> {
+            fn bitxor_assign(&mut self, rhs: $t) {
+                *self ^= rhs.to_le_bytes()
             }
         }
     };
@@ -215,7 +620,6 @@ macro_rules! impl_bitor_assign {
 
 impl_bitand!(u8);
 impl_bitand!(i8);
-impl_bitand!(char);
 impl_bitand!(u16);
 impl_bitand!(i16);
 impl_bitand!(u32);
@@ -254,3 +658,29 @@ impl_bitor_assign!(u128);
 impl_bitor_assign!(i128);
 impl_bitor_assign!(usize);
 impl_bitor_assign!(isize);
+
+impl_bitxor!(u8);
+impl_bitxor!(i8);
+impl_bitxor!(u16);
+impl_bitxor!(i16);
+impl_bitxor!(u32);
+impl_bitxor!(i32);
+impl_bitxor!(u64);
+impl_bitxor!(i64);
+impl_bitxor!(u128);
+impl_bitxor!(i128);
+impl_bitxor!(usize);
+impl_bitxor!(isize);
+
+impl_bitxor_assign!(u8);
+impl_bitxor_assign!(i8);
+impl_bitxor_assign!(u16);
+impl_bitxor_assign!(i16);
+impl_bitxor_assign!(u32);
+impl_bitxor_assign!(i32);
+impl_bitxor_assign!(u64);
+impl_bitxor_assign!(i64);
+impl_bitxor_assign!(u128);
+impl_bitxor_assign!(i128);
+impl_bitxor_assign!(usize);
+impl_bitxor_assign!(isize);
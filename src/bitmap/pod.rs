@@ -0,0 +1,10 @@
+//! Implementations of [`bytemuck`] traits for [`Bitmap`], with the
+//! `bytemuck` feature.
+
+use super::*;
+
+// SAFETY: `Bitmap<BYTES>` is `#[repr(transparent)]` over `[u8; BYTES]`,
+// which is `Zeroable` and `Pod`.
+unsafe impl<const BYTES: usize> bytemuck::Zeroable for Bitmap<BYTES> {}
+
+unsafe impl<const BYTES: usize> bytemuck::Pod for Bitmap<BYTES> {}
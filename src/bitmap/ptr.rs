@@ -1,14 +1,120 @@
 use super::*;
 
 impl<const BYTES: usize> Bitmap<BYTES> {
+  #[allow(clippy::should_implement_trait)]
   pub fn as_ref(&self) -> &[u8; BYTES] {
     &self.bits
   }
 
+  #[allow(clippy::should_implement_trait)]
   pub fn as_mut(&mut self) -> &mut [u8; BYTES] {
     &mut self.bits
   }
 
+  /// Same as the inherent `as_ref`, but never confused with
+  /// [`AsRef::as_ref()`] when inferring.
+  pub fn as_array(&self) -> &[u8; BYTES] {
+    &self.bits
+  }
+
+  /// Same as the inherent `as_mut`, but never confused with
+  /// [`AsMut::as_mut()`] when inferring.
+  pub fn as_array_mut(&mut self) -> &mut [u8; BYTES] {
+    &mut self.bits
+  }
+
+  /// Initialize the memory at `ptr` as a map whose flags are all 0,
+  /// in place, without building the map on the stack first.
+  ///
+  /// # Safety
+  /// `ptr` must be valid for writes of `BYTES` bytes, and the memory must
+  /// not be accessed through any other pointer during `'a`. As a map is a
+  /// `[u8; BYTES]`, there is no alignment requirement.
+  ///
+  /// # Examples
+  /// ```
+  /// use cbitmap::bitmap::*;
+  ///
+  /// let mut page = Box::new([0xffu8; 4096]);
+  /// let map = unsafe { Bitmap::<64>::init_zeroed_at(page.as_mut_ptr().cast()) };
+  /// assert_eq!(map.none(), true);
+  /// ```
+  pub unsafe fn init_zeroed_at<'a>(ptr: *mut Bitmap<BYTES>) -> &'a mut Self {
+    ptr.cast::<u8>().write_bytes(0, BYTES);
+    &mut *ptr
+  }
+
+  /// Initialize the memory at `ptr` as a map holding `bytes`, in place.
+  /// The bytes past the end of `bytes` are set to 0.
+  ///
+  /// # Safety
+  /// The same as [`Bitmap::init_zeroed_at()`]. Also, `bytes` must not
+  /// overlap the map.
+  ///
+  /// # Examples
+  /// ```
+  /// use cbitmap::bitmap::*;
+  ///
+  /// let mut page = Box::new([0xffu8; 4096]);
+  /// let map = unsafe { Bitmap::<64>::init_from_at(page.as_mut_ptr().cast(), &[0b_1]) };
+  /// assert_eq!(map.count(), 1);
+  /// ```
+  ///
+  /// # Panics
+  /// Panic if `bytes.len() > BYTES`.
+  pub unsafe fn init_from_at<'a>(ptr: *mut Bitmap<BYTES>, bytes: &[u8]) -> &'a mut Self {
+    if bytes.len() > BYTES {
+      panic!("Bitmap: initializing from a too long slice");
+    }
+    let dst = ptr.cast::<u8>();
+    core::ptr::copy_nonoverlapping(bytes.as_ptr(), dst, bytes.len());
+    dst.add(bytes.len()).write_bytes(0, BYTES - bytes.len());
+    &mut *ptr
+  }
+
+  /// Reinterpret the `BYTES` bytes at `ptr` as a map, without copying.
+  /// As a map is `#[repr(transparent)]` over `[u8; BYTES]`, any bytes are
+  /// a valid map.
+  ///
+  /// # Safety
+  /// `ptr` must be non-null and valid for reads of `BYTES` bytes, all of
+  /// them initialized, for the whole `'a`. The memory must not be written
+  /// through any other pointer during `'a`. There is no alignment
+  /// requirement.
+  ///
+  /// # Examples
+  /// ```
+  /// use cbitmap::bitmap::*;
+  ///
+  /// let buffer = [0u8, 0b_1001, 0xff];
+  /// let map: &Bitmap<1> = unsafe { Bitmap::from_ptr(buffer[1..].as_ptr()) };
+  /// assert_eq!(map.count(), 2);
+  /// ```
+  pub unsafe fn from_ptr<'a>(ptr: *const u8) -> &'a Self {
+    &*ptr.cast::<Self>()
+  }
+
+  /// Reinterpret the `BYTES` bytes at `ptr` as a mutable map, without
+  /// copying.
+  ///
+  /// # Safety
+  /// The same as [`Bitmap::from_ptr()`], and `ptr` must also be valid for
+  /// writes. The memory must not be accessed through any other pointer
+  /// during `'a`.
+  ///
+  /// # Examples
+  /// ```
+  /// use cbitmap::bitmap::*;
+  ///
+  /// let mut buffer = [0u8; 4];
+  /// let map: &mut Bitmap<2> = unsafe { Bitmap::from_ptr_mut(buffer[2..].as_mut_ptr()) };
+  /// map.set(15);
+  /// assert_eq!(buffer, [0, 0, 0, 0x80]);
+  /// ```
+  pub unsafe fn from_ptr_mut<'a>(ptr: *mut u8) -> &'a mut Self {
+    &mut *ptr.cast::<Self>()
+  }
+
   pub fn as_ptr(&self) -> *const u8 {
     self.bits.as_ptr()
   }
@@ -16,4 +122,39 @@ impl<const BYTES: usize> Bitmap<BYTES> {
   pub fn as_mut_ptr(&mut self) -> *mut u8 {
     self.bits.as_mut_ptr()
   }
-}
\ No newline at end of file
+}
+impl<const BYTES: usize> AsRef<[u8]> for Bitmap<BYTES> {
+  /// Give the bytes of the map, so it can be passed to code generic
+  /// over `AsRef<[u8]>`.
+  ///
+  /// # Examples
+  /// ```
+  /// use cbitmap::bitmap::*;
+  ///
+  /// fn len_of<T: AsRef<[u8]>>(bytes: T) -> usize {
+  ///     bytes.as_ref().len()
+  /// }
+  /// assert_eq!(len_of(newmap!(;32)), 4);
+  /// ```
+  fn as_ref(&self) -> &[u8] {
+    &self.bits
+  }
+}
+
+impl<const BYTES: usize> AsMut<[u8]> for Bitmap<BYTES> {
+  fn as_mut(&mut self) -> &mut [u8] {
+    &mut self.bits
+  }
+}
+
+impl<const BYTES: usize> core::borrow::Borrow<[u8]> for Bitmap<BYTES> {
+  fn borrow(&self) -> &[u8] {
+    &self.bits
+  }
+}
+
+impl<const BYTES: usize> core::borrow::BorrowMut<[u8]> for Bitmap<BYTES> {
+  fn borrow_mut(&mut self) -> &mut [u8] {
+    &mut self.bits
+  }
+}
@@ -0,0 +1,109 @@
+//! Random bitmaps, with the `rand` feature. Only [`rand_core`] is needed,
+//! so any [`RngCore`] works, with or without `std`.
+
+use super::*;
+use rand_core::RngCore;
+
+impl<const BYTES: usize> Bitmap<BYTES> {
+    /// Create a `Bitmap<BYTES>` with uniformly random bits.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    /// use rand::{rngs::SmallRng, SeedableRng};
+    ///
+    /// let mut rng = SmallRng::seed_from_u64(42);
+    /// let map = Bitmap::<16>::random(&mut rng);
+    /// assert_eq!(map, Bitmap::<16>::random(&mut SmallRng::seed_from_u64(42)));
+    /// ```
+    pub fn random<R: RngCore + ?Sized>(rng: &mut R) -> Self {
+        let mut map = Self::new();
+        map.fill_random(rng);
+        map
+    }
+
+    /// Overwrite all the bits with uniformly random ones.
+    ///
+    /// # Return
+    /// `&mut self`, allowing a call chain.
+    pub fn fill_random<R: RngCore + ?Sized>(&mut self, rng: &mut R) -> &mut Self {
+        rng.fill_bytes(&mut self.bits);
+        self
+    }
+
+    /// Create a `Bitmap<BYTES>` where each bit is '1' with the
+    /// probability `p`, independently.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    /// use rand::{rngs::SmallRng, SeedableRng};
+    ///
+    /// let mut rng = SmallRng::seed_from_u64(42);
+    /// let map = Bitmap::<16>::random_with_density(&mut rng, 1.0);
+    /// assert_eq!(map.all(), true);
+    /// ```
+    ///
+    /// # Panics
+    /// Panic if `p` is not in `0.0..=1.0`.
+    pub fn random_with_density<R: RngCore + ?Sized>(rng: &mut R, p: f64) -> Self {
+        if !(0.0..=1.0).contains(&p) {
+            panic!("Bitmap: density out of range");
+        }
+        if p == 1.0 {
+            return Self::full();
+        }
+        // A bit is '1' iff a uniform u64 is below `p * 2^64`.
+        let threshold = (p * 18_446_744_073_709_551_616.0) as u64;
+        Self::from_fn(|_| rng.next_u64() < threshold)
+    }
+
+    /// Pick one of the '1's uniformly at random, without allocating.
+    ///
+    /// # Return
+    /// The index of the picked bit, or [`None`] if there is no '1'.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    /// use rand::{rngs::SmallRng, SeedableRng};
+    ///
+    /// let mut rng = SmallRng::seed_from_u64(42);
+    /// let map = he_lang!(3 | 10; 16);
+    /// assert!(matches!(map.random_one(&mut rng), Some(3 | 10)));
+    /// assert_eq!(newmap!(;16).random_one(&mut rng), None);
+    /// ```
+    pub fn random_one<R: RngCore + ?Sized>(&self, rng: &mut R) -> Option<usize> {
+        let count = self.count() as u64;
+        if count == 0 {
+            return None;
+        }
+        // Reject the top values, so every `k` in `0..count` is as likely.
+        let zone = u64::MAX - (u64::MAX - count + 1) % count;
+        let k = loop {
+            let x = rng.next_u64();
+            if x <= zone {
+                break x % count;
+            }
+        };
+        self.__find_nth_one(k as usize)
+    }
+
+    /// Find the index of the `n`-th '1' (counting from 0).
+    fn __find_nth_one(&self, mut n: usize) -> Option<usize> {
+        for (i, &byte) in self.bits.iter().enumerate() {
+            let ones = byte.count_ones() as usize;
+            if n >= ones {
+                n -= ones;
+                continue;
+            }
+            let mut byte = byte;
+            for _ in 0..n {
+                // Clear the lowest '1'.
+                byte &= byte - 1;
+            }
+            return Some(i * 8 + byte.trailing_zeros() as usize);
+        }
+        None
+    }
+}
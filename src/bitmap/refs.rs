@@ -43,8 +43,9 @@ use super::*;
 /// // Panic! Since bitmut is already moved:
 /// // assert_eq!(Into::<bool>::into(bit), false);
 /// ```
-#[derive(Debug, Clone, Copy)]
+#[derive(Clone, Copy)]
 pub struct BitRef<'map, const BYTES: usize> {
+    pub(super) idx: usize,
     pub(super) value: bool,
     _map: &'map Bitmap<BYTES>,
 }
@@ -96,13 +97,74 @@ pub struct BitRef<'map, const BYTES: usize> {
 /// // Panic! Since bitmut is already moved:
 /// // assert_eq!(Into::<bool>::into(bitmut), false);
 /// ```
-#[derive(Debug)]
 pub struct BitRefMut<'map, const BYTES: usize> {
-    idx: usize,
+    pub(super) idx: usize,
     pub(super) value: bool,
     map: &'map mut Bitmap<BYTES>,
 }
 
+/// A wrapper of the mutable reference to a bit in a [`BitmapMut`] view,
+/// the counterpart of [`BitRefMut`] for views. The wrapper owns a
+/// `mut ref` to the byte holding the bit.
+///
+/// # Examples
+/// ```
+/// use cbitmap::bitmap::*;
+///
+/// let mut buffer = [0u8; 2];
+/// let mut view = BitmapMut::new(&mut buffer);
+/// let mut bitmut = view.at_mut(10);
+/// bitmut.set().flip().flip();
+/// assert_eq!(*bitmut, true);
+/// assert_eq!(buffer[1], 0b_100);
+/// ```
+pub struct BitMut<'a> {
+    pub(super) idx: usize,
+    pub(super) value: bool,
+    byte: &'a mut u8,
+}
+
+impl<'a> BitMut<'a> {
+    pub(super) fn new(byte: &'a mut u8, index: usize) -> Self {
+        let value = *byte & (1 << __idx_get_bit(index)) != 0;
+        Self {
+            idx: index,
+            value,
+            byte,
+        }
+    }
+
+    /// Set the bit referenced by `self` to 1.
+    ///
+    /// # Return
+    /// `&mut self`, allowing a call chain.
+    pub fn set(&mut self) -> &mut Self {
+        __byte_or_u8(self.byte, 1 << __idx_get_bit(self.idx));
+        self.value = true;
+        self
+    }
+
+    /// Set the bit referenced by `self` to 0.
+    ///
+    /// # Return
+    /// `&mut self`, allowing a call chain.
+    pub fn reset(&mut self) -> &mut Self {
+        __byte_and_u8(self.byte, !(1 << __idx_get_bit(self.idx)));
+        self.value = false;
+        self
+    }
+
+    /// Flip the bit referenced by `self`.
+    ///
+    /// # Return
+    /// `&mut self`, allowing a call chain.
+    pub fn flip(&mut self) -> &mut Self {
+        *self.byte ^= 1 << __idx_get_bit(self.idx);
+        self.value = !self.value;
+        self
+    }
+}
+
 impl<'map, const BYTES: usize> BitRef<'map, BYTES> {
     /// Manually create a `BitRef` by specifying the map and index.
     ///
@@ -114,6 +176,7 @@ impl<'map, const BYTES: usize> BitRef<'map, BYTES> {
         }
         let (byte, bit) = __idx_1dto2d(index);
         Self {
+            idx: index,
             value: map.__get_bool(byte, bit),
             _map: map,
         }
@@ -139,8 +202,8 @@ impl<'map, const BYTES: usize> BitRefMut<'map, BYTES> {
         let value = map.__get_bool(byte, bit);
         Self {
             idx: index,
-            value: value,
-            map: map,
+            value,
+            map,
         }
     }
 
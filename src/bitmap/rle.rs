@@ -0,0 +1,125 @@
+//! Run-length encoding of a [`Bitmap`], compact for sparse (or dense)
+//! maps.
+
+use super::*;
+use alloc::vec::Vec;
+
+impl<const BYTES: usize> Bitmap<BYTES> {
+    /// Encode the map as the lengths of its runs of equal bits, which is
+    /// compact for sparse (or dense) maps.
+    ///
+    /// The runs go from bit 0 up, alternating between runs of '0's and
+    /// runs of '1's, starting with '0's. So the first run is empty if bit 0
+    /// is '1', and no other run is empty. Each length is a LEB128 varint:
+    /// 7 bits per byte, the lowest first, with the highest bit of a byte
+    /// set if more bytes follow, and no longer than needed. The empty map
+    /// is encoded as no bytes. So each map has exactly one encoding.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// // 3 '0's, 2 '1's, then 195 '0's.
+    /// let map = rangemap!(3..5; 200);
+    /// assert_eq!(map.to_rle(), [3, 2, 0xc3, 0x01]);
+    /// assert_eq!(newmap!(;16).to_rle(), [16]);
+    /// assert_eq!(Bitmap::<2>::full().to_rle(), [0, 16]);
+    /// ```
+    pub fn to_rle(&self) -> Vec<u8> {
+        let mut rle = Vec::new();
+        let len = self.bit_len();
+        let mut start = 0;
+        let mut value = false;
+        while start < len {
+            let end = __find_first_bit_from(&self.bits, !value, start).unwrap_or(len);
+            __push_varint(&mut rle, end - start);
+            start = end;
+            value = !value;
+        }
+        rle
+    }
+
+    /// Decode a map from the lengths of its runs, as given by
+    /// [`Bitmap::to_rle()`]. Only that encoding is accepted: an empty run
+    /// other than a leading one, or a varint with extra zero bytes, is an
+    /// error.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let map = Bitmap::<25>::from_rle(&[3, 2, 0xc3, 0x01]).unwrap();
+    /// assert_eq!(map, rangemap!(3..5; 200));
+    /// assert_eq!(
+    ///     Bitmap::<25>::from_rle(&[3, 2]),
+    ///     Err(BitmapError::LengthMismatch { expected: 200, found: 5 })
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    /// * [`BitmapError::InvalidEncoding`] if a run length is cut off,
+    ///   longer than needed, or does not fit in a `usize`, or if a run is
+    ///   empty but the first one followed by another run.
+    /// * [`BitmapError::LengthMismatch`] if the runs do not add up to
+    ///   [`Bitmap::bit_len()`] bits. Both lengths are in bits.
+    pub fn from_rle(rle: &[u8]) -> Result<Self, BitmapError> {
+        let mut map = Self::new();
+        let len = map.bit_len();
+        let mut total = 0usize;
+        let mut value = false;
+        let mut index = 0;
+        while index < rle.len() {
+            let (run, next) =
+                __read_varint(rle, index).ok_or(BitmapError::InvalidEncoding { index })?;
+            if run == 0 && (index > 0 || next == rle.len()) {
+                return Err(BitmapError::InvalidEncoding { index });
+            }
+            let end = total.saturating_add(run);
+            if value && total < len {
+                __set_range(&mut map.bits, total, end.min(len));
+            }
+            total = end;
+            value = !value;
+            index = next;
+        }
+        if total != len {
+            return Err(BitmapError::LengthMismatch {
+                expected: len,
+                found: total,
+            });
+        }
+        Ok(map)
+    }
+}
+
+/// Append `value` to `rle` as a LEB128 varint.
+fn __push_varint(rle: &mut Vec<u8>, mut value: usize) {
+    while value >= 0x80 {
+        rle.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    rle.push(value as u8);
+}
+
+/// Read a LEB128 varint from `rle` at `start`, giving the value and the
+/// index after it, or `None` if it is cut off, overlong or too large.
+fn __read_varint(rle: &[u8], start: usize) -> Option<(usize, usize)> {
+    let mut value = 0usize;
+    let mut shift = 0;
+    for (i, &byte) in rle[start..].iter().enumerate() {
+        let low = (byte & 0x7f) as usize;
+        if shift >= usize::BITS || (low << shift) >> shift != low {
+            return None;
+        }
+        value |= low << shift;
+        if byte & 0x80 == 0 {
+            // A zero last byte could have been left out.
+            if byte == 0 && i > 0 {
+                return None;
+            }
+            return Some((value, start + i + 1));
+        }
+        shift += 7;
+    }
+    None
+}
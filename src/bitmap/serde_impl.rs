@@ -0,0 +1,136 @@
+//! Implementations of [`serde`] traits for [`Bitmap`], with the `serde`
+//! feature.
+//!
+//! For binary formats, a map is serialized as its `BYTES` bytes, using
+//! [`Serializer::serialize_bytes()`], so it works for any `BYTES`.
+//!
+//! For human-readable formats, like JSON, a map is serialized as a binary
+//! string in the style of [`bitstr`](crate::bitstr): the leftmost digit is
+//! the highest bit, and the bytes are separated by `'_'`. Either such a
+//! string or a list of the indexes of '1's is accepted when deserializing.
+
+use super::*;
+use core::fmt;
+use serde::de::{Error, SeqAccess, Unexpected, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+impl<const BYTES: usize> Serialize for Bitmap<BYTES> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match serializer.is_human_readable() {
+            true => serializer.collect_str(&BitStr(self)),
+            false => serializer.serialize_bytes(&self.bits),
+        }
+    }
+}
+
+impl<'de, const BYTES: usize> Deserialize<'de> for Bitmap<BYTES> {
+    /// Deserialize a map from exactly `BYTES` bytes, or, for
+    /// human-readable formats, from exactly `BYTES * 8` binary digits or a
+    /// list of indexes in range. Any other input is rejected.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match deserializer.is_human_readable() {
+            true => deserializer.deserialize_any(ReadableVisitor::<BYTES>),
+            false => deserializer.deserialize_bytes(BytesVisitor::<BYTES>),
+        }
+    }
+}
+
+/// Display a map as a binary string, without allocating.
+struct BitStr<'map, const BYTES: usize>(&'map Bitmap<BYTES>);
+
+impl<'map, const BYTES: usize> fmt::Display for BitStr<'map, BYTES> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, byte) in self.0.bits.iter().rev().enumerate() {
+            if i != 0 {
+                f.write_str("_")?;
+            }
+            write!(f, "{byte:08b}")?;
+        }
+        Ok(())
+    }
+}
+
+struct BytesVisitor<const BYTES: usize>;
+
+impl<'de, const BYTES: usize> Visitor<'de> for BytesVisitor<BYTES> {
+    type Value = Bitmap<BYTES>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a bitmap of {BYTES} bytes")
+    }
+
+    fn visit_bytes<E: Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        Bitmap::from_slice_exact(v).map_err(|_| E::invalid_length(v.len(), &self))
+    }
+
+    // Formats without a bytes type may give a sequence.
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut map = Bitmap::<BYTES>::new();
+        for i in 0..BYTES {
+            match seq.next_element()? {
+                Some(byte) => map.bits[i] = byte,
+                None => return Err(A::Error::invalid_length(i, &self)),
+            }
+        }
+        // Count the extra elements, for a precise error.
+        let mut len = BYTES;
+        while seq.next_element::<u8>()?.is_some() {
+            len += 1;
+        }
+        match len == BYTES {
+            true => Ok(map),
+            false => Err(A::Error::invalid_length(len, &self)),
+        }
+    }
+}
+
+struct ReadableVisitor<const BYTES: usize>;
+
+impl<'de, const BYTES: usize> Visitor<'de> for ReadableVisitor<BYTES> {
+    type Value = Bitmap<BYTES>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "a binary string of {} digits or a list of indexes below {}",
+            BYTES * 8,
+            BYTES * 8
+        )
+    }
+
+    fn visit_str<E: Error>(self, v: &str) -> Result<Self::Value, E> {
+        let mut map = Bitmap::<BYTES>::new();
+        let mut idx = 0;
+        for c in v.chars().rev() {
+            match c {
+                '0' | '1' if idx >= BYTES * 8 => {
+                    return Err(E::invalid_length(idx + 1, &self));
+                }
+                '0' => idx += 1,
+                '1' => {
+                    map.set(idx);
+                    idx += 1;
+                }
+                '_' | ' ' => {}
+                _ => return Err(E::invalid_value(Unexpected::Char(c), &self)),
+            }
+        }
+        match idx == BYTES * 8 {
+            true => Ok(map),
+            false => Err(E::invalid_length(idx, &self)),
+        }
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut map = Bitmap::<BYTES>::new();
+        while let Some(idx) = seq.next_element::<u64>()? {
+            match usize::try_from(idx) {
+                Ok(i) if i < BYTES * 8 => {
+                    map.set(i);
+                }
+                _ => return Err(A::Error::invalid_value(Unexpected::Unsigned(idx), &self)),
+            }
+        }
+        Ok(map)
+    }
+}
@@ -0,0 +1,262 @@
+//! Implementations of set-like methods for `Bitmap`, like
+//! [`Bitmap::union()`].
+//!
+//! A bitmap is treated as the set of indexes of its '1's.
+
+use super::*;
+
+impl<const BYTES: usize> Bitmap<BYTES> {
+    /// Give a new map holding the '1's of both `self` and `other`.
+    /// Same as `&self | &other`.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let a = newmap!(0b_1100; 8);
+    /// let b = newmap!(0b_1010; 8);
+    /// let c = a.union(&b);
+    /// assert_eq!(&c.range_to_string(0, 8).unwrap(), "00001110");
+    /// ```
+    pub fn union(&self, other: &Self) -> Self {
+        let mut res = Bitmap { bits: self.bits };
+        res.union_with(other);
+        res
+    }
+
+    /// Give a new map holding the '1's set in both `self` and `other`.
+    /// Same as `&self & &other`.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let a = newmap!(0b_1100; 8);
+    /// let b = newmap!(0b_1010; 8);
+    /// let c = a.intersection(&b);
+    /// assert_eq!(&c.range_to_string(0, 8).unwrap(), "00001000");
+    /// ```
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut res = Bitmap { bits: self.bits };
+        res.intersection_with(other);
+        res
+    }
+
+    /// Give a new map holding the '1's of `self` which are not set in
+    /// `other`.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let a = newmap!(0b_1100; 8);
+    /// let b = newmap!(0b_1010; 8);
+    /// let c = a.difference(&b);
+    /// assert_eq!(&c.range_to_string(0, 8).unwrap(), "00000100");
+    /// ```
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut res = Bitmap { bits: self.bits };
+        res.difference_with(other);
+        res
+    }
+
+    /// Give a new map holding the '1's set in exactly one of `self` and
+    /// `other`. Same as `&self ^ &other`.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let a = newmap!(0b_1100; 8);
+    /// let b = newmap!(0b_1010; 8);
+    /// let c = a.symmetric_difference(&b);
+    /// assert_eq!(&c.range_to_string(0, 8).unwrap(), "00000110");
+    /// ```
+    pub fn symmetric_difference(&self, other: &Self) -> Self {
+        let mut res = Bitmap { bits: self.bits };
+        res.symmetric_difference_with(other);
+        res
+    }
+
+    /// In-place version of [`Bitmap::union()`].
+    ///
+    /// # Return
+    /// `&mut self`, allowing a call chain.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let mut map = newmap!(0b_1100; 8);
+    /// map.union_with(&newmap!(0b_1010; 8)).union_with(&newmap!(0b_1; 8));
+    /// assert_eq!(&map.range_to_string(0, 8).unwrap(), "00001111");
+    /// ```
+    pub fn union_with(&mut self, other: &Self) -> &mut Self {
+        for (byte, r) in self.bits.iter_mut().zip(other.bits.iter()) {
+            __byte_or_u8(byte, *r);
+        }
+        self
+    }
+
+    /// In-place version of [`Bitmap::intersection()`].
+    ///
+    /// # Return
+    /// `&mut self`, allowing a call chain.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let mut map = newmap!(0b_1100; 8);
+    /// map.intersection_with(&newmap!(0b_1010; 8));
+    /// assert_eq!(&map.range_to_string(0, 8).unwrap(), "00001000");
+    /// ```
+    pub fn intersection_with(&mut self, other: &Self) -> &mut Self {
+        for (byte, r) in self.bits.iter_mut().zip(other.bits.iter()) {
+            __byte_and_u8(byte, *r);
+        }
+        self
+    }
+
+    /// In-place version of [`Bitmap::difference()`].
+    ///
+    /// # Return
+    /// `&mut self`, allowing a call chain.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let mut map = newmap!(0b_1100; 8);
+    /// map.difference_with(&newmap!(0b_1010; 8));
+    /// assert_eq!(&map.range_to_string(0, 8).unwrap(), "00000100");
+    /// ```
+    pub fn difference_with(&mut self, other: &Self) -> &mut Self {
+        for (byte, r) in self.bits.iter_mut().zip(other.bits.iter()) {
+            __byte_and_u8(byte, !*r);
+        }
+        self
+    }
+
+    /// In-place version of [`Bitmap::symmetric_difference()`].
+    ///
+    /// # Return
+    /// `&mut self`, allowing a call chain.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let mut map = newmap!(0b_1100; 8);
+    /// map.symmetric_difference_with(&newmap!(0b_1010; 8));
+    /// assert_eq!(&map.range_to_string(0, 8).unwrap(), "00000110");
+    /// ```
+    pub fn symmetric_difference_with(&mut self, other: &Self) -> &mut Self {
+        for (byte, r) in self.bits.iter_mut().zip(other.bits.iter()) {
+            *byte ^= *r;
+        }
+        self
+    }
+
+    /// Whether every '1' of `self` is also set in `other`.
+    ///
+    /// A map is a subset of itself, and an empty map is a subset of
+    /// any map.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let a = newmap!(0b_0100; 8);
+    /// let b = newmap!(0b_1100; 8);
+    /// assert_eq!(a.is_subset(&b), true);
+    /// assert_eq!(b.is_subset(&a), false);
+    /// ```
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.bits
+            .iter()
+            .zip(other.bits.iter())
+            .all(|(byte, r)| byte & !r == 0)
+    }
+
+    /// Whether every '1' of `other` is also set in `self`. The mirror
+    /// of [`Bitmap::is_subset()`].
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let a = newmap!(0b_0100; 8);
+    /// let b = newmap!(0b_1100; 8);
+    /// assert_eq!(b.is_superset(&a), true);
+    /// assert_eq!(a.is_superset(&b), false);
+    /// ```
+    pub fn is_superset(&self, other: &Self) -> bool {
+        other.is_subset(self)
+    }
+
+    /// Whether `self` and `other` have no '1' in common, i.e. their
+    /// intersection is empty. The check stops at the first overlapping
+    /// byte, without building the intersection.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let a = newmap!(0b_0011; 8);
+    /// let b = newmap!(0b_1100; 8);
+    /// assert_eq!(a.is_disjoint(&b), true);
+    /// assert_eq!(a.is_disjoint(&newmap!(0b_0110; 8)), false);
+    /// ```
+    pub fn is_disjoint(&self, other: &Self) -> bool {
+        self.bits
+            .iter()
+            .zip(other.bits.iter())
+            .all(|(byte, r)| byte & r == 0)
+    }
+
+    /// Count how many bits differ between `self` and `other`, i.e. the
+    /// number of '1's in `&self ^ &other`, without building that map.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let a = newmap!(0b_0011; 8);
+    /// let b = newmap!(0b_0110; 8);
+    /// assert_eq!(a.hamming_distance(&b), 2);
+    /// assert_eq!(a.hamming_distance(&a), 0);
+    /// ```
+    pub fn hamming_distance(&self, other: &Self) -> usize {
+        self.bits
+            .iter()
+            .zip(other.bits.iter())
+            .map(|(byte, r)| (byte ^ r).count_ones() as usize)
+            .sum()
+    }
+
+    /// Whether `self` and `other` hold the same '1's, even if their
+    /// lengths differ.
+    ///
+    /// Like the ops with integers and arrays, the shorter map is treated
+    /// as if it had leading zero bytes. So the maps are equal iff their
+    /// common bytes match and the extra bytes of the longer map are all
+    /// zero. Maps of the same length can be compared with `==`.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let small = newmap!(0b_1001; 16);
+    /// let mut large = newmap!(0b_1001; 32);
+    /// assert_eq!(small.eq_zero_extended(&large), true);
+    /// large.set(31);
+    /// assert_eq!(small.eq_zero_extended(&large), false);
+    /// ```
+    pub fn eq_zero_extended<const M: usize>(&self, other: &Bitmap<M>) -> bool {
+        let size = BYTES.min(M);
+        self.bits[..size] == other.bits[..size]
+            && self.bits[size..].iter().all(|&b| b == 0)
+            && other.bits[size..].iter().all(|&b| b == 0)
+    }
+}
@@ -0,0 +1,243 @@
+//! Implementations of bit shifting and rotating for `Bitmap`.
+//!
+//! The operators [`core::ops::Shl`] and [`core::ops::Shr`] are
+//! implemented in [`crate::bitmap::ops`] on top of the helpers here.
+
+use super::*;
+
+impl<const BYTES: usize> Bitmap<BYTES> {
+    /// Rotate the whole map toward higher indexes by `n` bits. The bits
+    /// moved out from the top are put back to the bottom.
+    ///
+    /// `n` is reduced modulo [`Bitmap::bit_len()`], so rotating by `0`
+    /// or by `bit_len()` changes nothing.
+    ///
+    /// # Return
+    /// `&mut self`, allowing a call chain.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let mut map = newmap!(0b_10000001; 8);
+    /// map.rotate_left(2);
+    /// assert_eq!(&map.range_to_string(0, 8).unwrap(), "00000110");
+    /// map.rotate_left(8 + 1);
+    /// assert_eq!(&map.range_to_string(0, 8).unwrap(), "00001100");
+    /// ```
+    pub fn rotate_left(&mut self, n: usize) -> &mut Self {
+        if BYTES == 0 {
+            return self;
+        }
+        let n = n % (BYTES * 8);
+        let (bytes, bits) = __idx_1dto2d(n);
+        self.bits.rotate_right(bytes);
+        if bits > 0 {
+            let mut carry = self.bits[BYTES - 1] >> (8 - bits);
+            for byte in self.bits.iter_mut() {
+                let next = *byte >> (8 - bits);
+                *byte = (*byte << bits) | carry;
+                carry = next;
+            }
+        }
+        self
+    }
+
+    /// Rotate the whole map toward lower indexes by `n` bits. The bits
+    /// moved out from the bottom are put back to the top.
+    ///
+    /// `n` is reduced modulo [`Bitmap::bit_len()`], so rotating by `0`
+    /// or by `bit_len()` changes nothing.
+    ///
+    /// # Return
+    /// `&mut self`, allowing a call chain.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let mut map = newmap!(0b_10000001; 8);
+    /// map.rotate_right(2);
+    /// assert_eq!(&map.range_to_string(0, 8).unwrap(), "01100000");
+    /// map.rotate_right(8 + 1);
+    /// assert_eq!(&map.range_to_string(0, 8).unwrap(), "00110000");
+    /// ```
+    pub fn rotate_right(&mut self, n: usize) -> &mut Self {
+        if BYTES == 0 {
+            return self;
+        }
+        let len = BYTES * 8;
+        self.rotate_left(len - n % len)
+    }
+
+    /// Shift the map toward higher indexes by `n` bits like `<<=`, and
+    /// give back the bits shifted out from the top.
+    ///
+    /// The returned carry is laid out as the map that would sit right
+    /// above `self`: its bit `i` is the bit that would land at index
+    /// `bit_len() + i` after the shift. So a wider number made of maps
+    /// `lo` and `hi` can be shifted by shifting both, then OR-ing the
+    /// carry of `lo` into `hi`.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let mut map = newmap!(0b_11000001; 8);
+    /// let carry = map.shl_carry(3);
+    /// assert_eq!(&map.range_to_string(0, 8).unwrap(), "00001000");
+    /// assert_eq!(&carry.range_to_string(0, 8).unwrap(), "00000110");
+    /// ```
+    pub fn shl_carry(&mut self, n: usize) -> Bitmap<BYTES> {
+        let mut carry = Bitmap { bits: self.bits };
+        match n <= BYTES * 8 {
+            true => carry.__shift_right(BYTES * 8 - n),
+            false => carry.__shift_left(n - BYTES * 8),
+        }
+        self.__shift_left(n);
+        carry
+    }
+
+    /// Shift the map toward lower indexes by `n` bits like `>>=`, and
+    /// give back the bits shifted out from the bottom.
+    ///
+    /// The returned carry is laid out as the map that would sit right
+    /// below `self`: its bit `i` is the bit that would land at index
+    /// `i - bit_len()` (a negative index) after the shift. So a wider
+    /// number made of maps `lo` and `hi` can be shifted by shifting
+    /// both, then OR-ing the carry of `hi` into `lo`.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let mut map = newmap!(0b_10000011; 8);
+    /// let carry = map.shr_carry(3);
+    /// assert_eq!(&map.range_to_string(0, 8).unwrap(), "00010000");
+    /// assert_eq!(&carry.range_to_string(0, 8).unwrap(), "01100000");
+    /// ```
+    pub fn shr_carry(&mut self, n: usize) -> Bitmap<BYTES> {
+        let mut carry = Bitmap { bits: self.bits };
+        match n <= BYTES * 8 {
+            true => carry.__shift_left(BYTES * 8 - n),
+            false => carry.__shift_right(n - BYTES * 8),
+        }
+        self.__shift_right(n);
+        carry
+    }
+
+    /// Shift the map toward higher indexes by `n` bits like `<<=`, only
+    /// if no '1' would be shifted out from the top.
+    ///
+    /// # Return
+    /// [`None`] if any '1' would be lost, in which case the map is left
+    /// untouched. Otherwise `Some(&mut self)`, allowing a call chain.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let mut map = newmap!(0b_00100001; 8);
+    /// assert!(map.checked_shl(2).is_some());
+    /// assert_eq!(&map.range_to_string(0, 8).unwrap(), "10000100");
+    /// assert!(map.checked_shl(1).is_none());
+    /// assert_eq!(&map.range_to_string(0, 8).unwrap(), "10000100");
+    /// ```
+    pub fn checked_shl(&mut self, n: usize) -> Option<&mut Self> {
+        let len = BYTES * 8;
+        if self.__any_in(len - n.min(len), len) {
+            return None;
+        }
+        self.__shift_left(n);
+        Some(self)
+    }
+
+    /// Shift the map toward lower indexes by `n` bits like `>>=`, only
+    /// if no '1' would be shifted out from the bottom.
+    ///
+    /// # Return
+    /// [`None`] if any '1' would be lost, in which case the map is left
+    /// untouched. Otherwise `Some(&mut self)`, allowing a call chain.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let mut map = newmap!(0b_10000100; 8);
+    /// assert!(map.checked_shr(2).is_some());
+    /// assert_eq!(&map.range_to_string(0, 8).unwrap(), "00100001");
+    /// assert!(map.checked_shr(1).is_none());
+    /// assert_eq!(&map.range_to_string(0, 8).unwrap(), "00100001");
+    /// ```
+    pub fn checked_shr(&mut self, n: usize) -> Option<&mut Self> {
+        if self.__any_in(0, n.min(BYTES * 8)) {
+            return None;
+        }
+        self.__shift_right(n);
+        Some(self)
+    }
+}
+
+// Tools
+
+impl<const BYTES: usize> Bitmap<BYTES> {
+    /// Whether there is any '1' in the bits `[start, end)`. The range
+    /// must be inside the map.
+    fn __any_in(&self, start: usize, end: usize) -> bool {
+        if start >= end {
+            return false;
+        }
+        let (sbyte, sbit) = __idx_1dto2d(start);
+        let (ebyte, ebit) = __idx_1dto2d(end - 1);
+        let head = 0xffu8 << sbit;
+        let tail = 0xffu8 >> (7 - ebit);
+        if sbyte == ebyte {
+            return self.bits[sbyte] & head & tail != 0;
+        }
+        self.bits[sbyte] & head != 0
+            || self.bits[sbyte + 1..ebyte].iter().any(|&b| b != 0)
+            || self.bits[ebyte] & tail != 0
+    }
+
+    /// Shift the map toward higher indexes by `n` bits in place, filling
+    /// the bottom with '0'. Shifting by `bit_len()` or more clears the map.
+    pub(super) fn __shift_left(&mut self, n: usize) {
+        if n >= BYTES * 8 {
+            self.bits.fill(0);
+            return;
+        }
+        let (bytes, bits) = __idx_1dto2d(n);
+        // Walk from the top, so every source byte is read before written.
+        for k in (0..BYTES).rev() {
+            let mut byte = 0u8;
+            if k >= bytes {
+                byte = self.bits[k - bytes] << bits;
+                if bits > 0 && k > bytes {
+                    byte |= self.bits[k - bytes - 1] >> (8 - bits);
+                }
+            }
+            self.bits[k] = byte;
+        }
+    }
+
+    /// Shift the map toward lower indexes by `n` bits in place, filling
+    /// the top with '0'. Shifting by `bit_len()` or more clears the map.
+    pub(super) fn __shift_right(&mut self, n: usize) {
+        if n >= BYTES * 8 {
+            self.bits.fill(0);
+            return;
+        }
+        let (bytes, bits) = __idx_1dto2d(n);
+        // Walk from the bottom, so every source byte is read before written.
+        for k in 0..BYTES {
+            let mut byte = 0u8;
+            if k + bytes < BYTES {
+                byte = self.bits[k + bytes] >> bits;
+                if bits > 0 && k + bytes + 1 < BYTES {
+                    byte |= self.bits[k + bytes + 1] << (8 - bits);
+                }
+            }
+            self.bits[k] = byte;
+        }
+    }
+}
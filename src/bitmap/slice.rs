@@ -0,0 +1,176 @@
+//! [`BitsManage`] for byte slices, and the owned byte buffers of
+//! `alloc`.
+
+use super::view::__out_of_range;
+use super::*;
+use alloc::vec::Vec;
+
+impl BitsManage for [u8] {
+    /// Count the '1's in the slice.
+    #[inline]
+    fn count(&self) -> usize {
+        __count_ones(self)
+    }
+
+    /// Find the first '1' in the slice.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let buffer = [0u8, 0b_100];
+    /// assert_eq!(buffer[..].find_first_one(), Some(10));
+    /// ```
+    #[inline]
+    fn find_first_one(&self) -> Option<usize> {
+        __find_first_one(self)
+    }
+
+    /// Find the first '0' in the slice.
+    #[inline]
+    fn find_first_zero(&self) -> Option<usize> {
+        __find_first_zero(self)
+    }
+
+    /// Get the bool value of indexed bit.
+    ///
+    /// # Panics
+    /// Panic if `index` is out of the slice.
+    fn get_bool(&self, index: usize) -> bool {
+        if __out_bound(self.len(), index) {
+            __out_of_range("[u8]", "getting", index, self.len() * 8);
+        }
+        self[__idx_get_byte(index)] & (1 << __idx_get_bit(index)) != 0
+    }
+
+    /// Set the indexed bit to '1'.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let mut buffer = [0u8; 4];
+    /// buffer[1..].set(0).set(9);
+    /// assert_eq!(buffer, [0, 0b_1, 0b_10, 0]);
+    /// ```
+    ///
+    /// # Panics
+    /// Panic if `index` is out of the slice.
+    fn set(&mut self, index: usize) -> &mut Self {
+        *__byte_of(self, index, "setting") |= 1 << __idx_get_bit(index);
+        self
+    }
+
+    /// Set the indexed bit to '0'.
+    ///
+    /// # Panics
+    /// Panic if `index` is out of the slice.
+    fn reset(&mut self, index: usize) -> &mut Self {
+        *__byte_of(self, index, "resetting") &= !(1 << __idx_get_bit(index));
+        self
+    }
+
+    /// Flip the indexed bit.
+    ///
+    /// # Panics
+    /// Panic if `index` is out of the slice.
+    fn flip(&mut self, index: usize) -> &mut Self {
+        *__byte_of(self, index, "flipping") ^= 1 << __idx_get_bit(index);
+        self
+    }
+
+    /// Set the whole slice to 1.
+    fn set_all(&mut self) -> &mut Self {
+        self.fill(0xff);
+        self
+    }
+
+    /// Set the whole slice to 0.
+    fn reset_all(&mut self) -> &mut Self {
+        self.fill(0);
+        self
+    }
+
+    /// Flip the whole slice.
+    fn flip_all(&mut self) -> &mut Self {
+        __flip_all(self);
+        self
+    }
+}
+
+/// Get the byte of `bits` holding the indexed bit, or panic with `verb`.
+#[inline]
+fn __byte_of<'a>(bits: &'a mut [u8], index: usize, verb: &str) -> &'a mut u8 {
+    if __out_bound(bits.len(), index) {
+        __out_of_range("[u8]", verb, index, bits.len() * 8);
+    }
+    &mut bits[__idx_get_byte(index)]
+}
+
+// Owned buffers, through their slices. A buffer never grows: the bits
+// beyond `len() * 8` are out of range, like for the fixed types.
+
+macro_rules! impl_bits_manage_by_slice {
+    ($t:ty) => {
+        impl BitsManage for $t {
+            #[inline]
+            fn count(&self) -> usize {
+                self[..].count()
+            }
+
+            #[inline]
+            fn find_first_one(&self) -> Option<usize> {
+                self[..].find_first_one()
+            }
+
+            #[inline]
+            fn find_first_zero(&self) -> Option<usize> {
+                self[..].find_first_zero()
+            }
+
+            #[inline]
+            fn get_bool(&self, index: usize) -> bool {
+                self[..].get_bool(index)
+            }
+
+            #[inline]
+            fn set(&mut self, index: usize) -> &mut Self {
+                self[..].set(index);
+                self
+            }
+
+            #[inline]
+            fn reset(&mut self, index: usize) -> &mut Self {
+                self[..].reset(index);
+                self
+            }
+
+            #[inline]
+            fn flip(&mut self, index: usize) -> &mut Self {
+                self[..].flip(index);
+                self
+            }
+
+            #[inline]
+            fn set_all(&mut self) -> &mut Self {
+                self[..].set_all();
+                self
+            }
+
+            #[inline]
+            fn reset_all(&mut self) -> &mut Self {
+                self[..].reset_all();
+                self
+            }
+
+            #[inline]
+            fn flip_all(&mut self) -> &mut Self {
+                self[..].flip_all();
+                self
+            }
+        }
+    };
+}
+
+impl_bits_manage_by_slice!(Vec<u8>);
+impl_bits_manage_by_slice!(Box<[u8]>);
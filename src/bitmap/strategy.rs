@@ -0,0 +1,41 @@
+//! [`mod@proptest`] strategies generating [`Bitmap`]s, with the
+//! `proptest` feature.
+//!
+//! Both strategies shrink toward the empty map.
+//!
+//! # Examples
+//! ```
+//! use cbitmap::bitmap::*;
+//! use cbitmap::bitmap::strategy::*;
+//! use proptest::prelude::*;
+//!
+//! proptest!(|(map in arb_bitmap::<4>())| {
+//!     let mut flipped = map.clone();
+//!     flipped.flip_all();
+//!     prop_assert_eq!(map.count() + flipped.count(), map.bit_len());
+//! });
+//! ```
+
+use super::*;
+use proptest::prelude::*;
+
+/// Generate a `Bitmap<BYTES>` with uniformly random bits.
+pub fn arb_bitmap<const BYTES: usize>() -> impl Strategy<Value = Bitmap<BYTES>> {
+    proptest::collection::vec(any::<u8>(), BYTES).prop_map(|bytes| {
+        let mut map = Bitmap::<BYTES>::new();
+        map.bits.copy_from_slice(&bytes);
+        map
+    })
+}
+
+/// Generate a `Bitmap<BYTES>` where each bit is '1' with the probability
+/// `expected_ratio`, independently.
+///
+/// # Panics
+/// Panic if `expected_ratio` is not in `0.0..=1.0`.
+pub fn arb_bitmap_with_ones<const BYTES: usize>(
+    expected_ratio: f64,
+) -> impl Strategy<Value = Bitmap<BYTES>> {
+    proptest::collection::vec(proptest::bool::weighted(expected_ratio), BYTES * 8)
+        .prop_map(|bits| Bitmap::<BYTES>::from_fn(|i| bits[i]))
+}
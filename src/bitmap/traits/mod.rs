@@ -5,10 +5,23 @@ use super::*;
 // Overrided methods
 
 /// Fill the first several bytes (8*bits) of a bitmap.
+///
+/// The integer aliases are little-endian on every target: the bit 0 of
+/// the map gets the LSB of the integer.
 pub trait FillPrefix<T: Sized> {
     fn fill_prefix(&mut self, value:T) -> &mut Self;
 }
 
+/// Fill the last several bytes (8*bits) of a bitmap.
+pub trait FillSuffix<T: Sized> {
+    fn fill_suffix(&mut self, value: T) -> &mut Self;
+}
+
+/// Fill several bytes (8*bits) of a bitmap at a given byte offset.
+pub trait FillAt<T: Sized> {
+    fn fill_at(&mut self, byte_offset: usize, value: T) -> &mut Self;
+}
+
 impl<const BYTES: usize, const N: usize> FillPrefix<[u8; N]> for Bitmap<BYTES> {
     /// Fill the first N bytes (N*8 bits) of a bitmap with given byte array.
     /// 
@@ -44,19 +57,137 @@ impl<const BYTES: usize, const N: usize> FillPrefix<[u8; N]> for Bitmap<BYTES> {
     }
 }
 
+impl<'a, const N: usize> FillPrefix<[u8; N]> for BitmapMut<'a> {
+    /// Fill the first N bytes (N*8 bits) of a view with given byte array,
+    /// like [`Bitmap`]. If `N` is larger than the view, the last bytes of
+    /// the array are dropped.
+    ///
+    /// # Return
+    /// `&mut self`, allowing a call chain.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let mut buffer = [0u8; 4];
+    /// BitmapMut::new(&mut buffer[1..]).fill_prefix([0xaa_u8, 0x55]);
+    /// assert_eq!(buffer, [0, 0xaa, 0x55, 0]);
+    /// ```
+    fn fill_prefix(&mut self, value: [u8; N]) -> &mut Self {
+        self.fill_prefix(&value[..])
+    }
+}
+
+impl<'a, 'b> FillPrefix<&'b [u8]> for BitmapMut<'a> {
+    /// Fill the first bytes of a view with a byte slice. If the slice is
+    /// longer than the view, its last bytes are dropped.
+    ///
+    /// # Return
+    /// `&mut self`, allowing a call chain.
+    fn fill_prefix(&mut self, value: &'b [u8]) -> &mut Self {
+        let size = value.len().min(self.byte_len());
+        self.as_bytes_mut()[..size].copy_from_slice(&value[..size]);
+        self
+    }
+}
+
+impl<const BYTES: usize, const N: usize> FillSuffix<[u8; N]> for Bitmap<BYTES> {
+    /// Fill the last N bytes (N*8 bits) of a bitmap with given byte array.
+    ///
+    /// The last byte of the array goes to the last byte of the map.
+    /// If `N > BYTES`, the first `N - BYTES` bytes of the array are
+    /// dropped, mirroring [`FillPrefix`] dropping the last ones.
+    ///
+    /// # Return
+    /// `&mut self`, allowing a call chain.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let mut map = newmap!(;32);
+    /// map.fill_suffix([0b_1010u8, 0b_1]);
+    /// assert_eq!(map.test(17), true);
+    /// assert_eq!(map.test(19), true);
+    /// assert_eq!(map.test(24), true);
+    /// ```
+    /// Here are some aliases:
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let mut map = newmap!(;128);
+    /// // aliases
+    /// map.fill_suffix(1u8 << 7);
+    /// assert_eq!(map.test(127), true);
+    /// map.fill_suffix(1u16);
+    /// assert_eq!(map.test(112), true);
+    /// ```
+    fn fill_suffix(&mut self, value: [u8; N]) -> &mut Self {
+        let len = N.min(BYTES);
+        self.bits[BYTES - len..].copy_from_slice(&value[N - len..]);
+        self
+    }
+}
+
+impl<const BYTES: usize, const N: usize> FillAt<[u8; N]> for Bitmap<BYTES> {
+    /// Fill N bytes (N*8 bits) of a bitmap with given byte array, starting
+    /// at the byte `byte_offset`.
+    ///
+    /// The bytes are placed in the same order as [`FillPrefix`], which
+    /// is the same as `fill_at(0, value)` when the value fits.
+    ///
+    /// # Return
+    /// `&mut self`, allowing a call chain.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let mut map = newmap!(;64);
+    /// map.fill_at(5, [0b_1u8, 0b_10]);
+    /// assert_eq!(map.test(40), true);
+    /// assert_eq!(map.test(49), true);
+    /// ```
+    /// Here are some aliases:
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let mut map = newmap!(;64);
+    /// map.fill_at(4, [0xffu8; 4]);
+    /// assert_eq!(map.count(), 32);
+    /// map.fill_at(4, 0u32);
+    /// assert_eq!(map.count(), 0);
+    /// ```
+    ///
+    /// # Panics
+    /// Panic if the value does not fit entirely inside the map,
+    /// i.e. `byte_offset + N > BYTES`.
+    fn fill_at(&mut self, byte_offset: usize, value: [u8; N]) -> &mut Self {
+        match byte_offset.checked_add(N) {
+            Some(end) if end <= BYTES => {
+                self.bits[byte_offset..end].copy_from_slice(&value);
+                self
+            }
+            _ => panic!("Bitmap: filling out of range"),
+        }
+    }
+}
+
 // Alias impls
 // FillPrefix
 
 macro_rules! impl_fill_prefix {
+    (char) => {
+        impl<const BYTES: usize> FillPrefix<char> for Bitmap<BYTES> {
+            fn fill_prefix(&mut self, value: char) -> &mut Self {
+                self.fill_prefix(u32::from(value).to_le_bytes())
+            }
+        }
+    };
     ($t:ty) => {
         impl<const BYTES: usize> FillPrefix<$t> for Bitmap<BYTES> {
-            fn fill_prefix(&mut self, mut value: $t) -> &mut Self {
-                const SIZE: usize = core::mem::size_of::<$t>();
-                unsafe {
-                    let ptr = (&mut value as *mut $t).cast::<[u8; SIZE]>();
-                    __copy_bytes_to(&mut self.bits, &mut *ptr);
-                };
-                self
+            fn fill_prefix(&mut self, value: $t) -> &mut Self {
+                self.fill_prefix(value.to_le_bytes())
             }
         }
     };
@@ -74,4 +205,70 @@ impl_fill_prefix!(i64);
 impl_fill_prefix!(u128);
 impl_fill_prefix!(i128);
 impl_fill_prefix!(usize);
-impl_fill_prefix!(isize);
\ No newline at end of file
+impl_fill_prefix!(isize);
+
+// FillSuffix
+
+macro_rules! impl_fill_suffix {
+    (char) => {
+        impl<const BYTES: usize> FillSuffix<char> for Bitmap<BYTES> {
+            fn fill_suffix(&mut self, value: char) -> &mut Self {
+                self.fill_suffix(u32::from(value).to_le_bytes())
+            }
+        }
+    };
+    ($t:ty) => {
+        impl<const BYTES: usize> FillSuffix<$t> for Bitmap<BYTES> {
+            fn fill_suffix(&mut self, value: $t) -> &mut Self {
+                self.fill_suffix(value.to_le_bytes())
+            }
+        }
+    };
+}
+
+impl_fill_suffix!(u8);
+impl_fill_suffix!(i8);
+impl_fill_suffix!(char);
+impl_fill_suffix!(u16);
+impl_fill_suffix!(i16);
+impl_fill_suffix!(u32);
+impl_fill_suffix!(i32);
+impl_fill_suffix!(u64);
+impl_fill_suffix!(i64);
+impl_fill_suffix!(u128);
+impl_fill_suffix!(i128);
+impl_fill_suffix!(usize);
+impl_fill_suffix!(isize);
+
+// FillAt
+
+macro_rules! impl_fill_at {
+    (char) => {
+        impl<const BYTES: usize> FillAt<char> for Bitmap<BYTES> {
+            fn fill_at(&mut self, byte_offset: usize, value: char) -> &mut Self {
+                self.fill_at(byte_offset, u32::from(value).to_le_bytes())
+            }
+        }
+    };
+    ($t:ty) => {
+        impl<const BYTES: usize> FillAt<$t> for Bitmap<BYTES> {
+            fn fill_at(&mut self, byte_offset: usize, value: $t) -> &mut Self {
+                self.fill_at(byte_offset, value.to_le_bytes())
+            }
+        }
+    };
+}
+
+impl_fill_at!(u8);
+impl_fill_at!(i8);
+impl_fill_at!(char);
+impl_fill_at!(u16);
+impl_fill_at!(i16);
+impl_fill_at!(u32);
+impl_fill_at!(i32);
+impl_fill_at!(u64);
+impl_fill_at!(i64);
+impl_fill_at!(u128);
+impl_fill_at!(i128);
+impl_fill_at!(usize);
+impl_fill_at!(isize);
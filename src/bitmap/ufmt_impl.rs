@@ -0,0 +1,76 @@
+//! Implementations of [`ufmt`] traits for [`Bitmap`], with the `ufmt`
+//! feature. They give the same text as [`core::fmt`], without allocating.
+
+use super::*;
+use ufmt::{uDebug, uDisplay, uWrite, Formatter};
+
+impl<const BYTES: usize> uDisplay for Bitmap<BYTES> {
+    /// Formats a bitmap like [`Display`](core::fmt::Display), in a form
+    /// like `"[24 bits] ...00000001 00000001"`.
+    fn fmt<W: uWrite + ?Sized>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error> {
+        ufmt::uwrite!(f, "[{} bits] ", BYTES * 8)?;
+        self.__ufmt_tail(f)
+    }
+}
+
+impl<const BYTES: usize> uDebug for Bitmap<BYTES> {
+    /// Formats a bitmap like [`Debug`](core::fmt::Debug), in a form
+    /// like `Bitmap { #bytes: 3, #bits: 24, bits: "00000000 00000001 00000001" }`,
+    /// or summarized if it is longer than [`DEBUG_SUMMARY_BYTES`].
+    fn fmt<W: uWrite + ?Sized>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error> {
+        ufmt::uwrite!(f, "Bitmap {{ #bytes: {}, #bits: {}, ", BYTES, BYTES * 8)?;
+        if BYTES > DEBUG_SUMMARY_BYTES {
+            ufmt::uwrite!(
+                f,
+                "#ones: {}, first_one: {:?}, last_one: {:?}, ",
+                self.count(),
+                self.find_first_one(),
+                self.__find_last_one()
+            )?;
+        }
+        f.write_str("bits: \"")?;
+        for i in (0..BYTES).rev() {
+            if BYTES > DEBUG_SUMMARY_BYTES && (2..BYTES - 2).contains(&i) {
+                if i == 2 {
+                    f.write_str(" ...")?;
+                }
+                continue;
+            }
+            if i + 1 < BYTES {
+                f.write_str(" ")?;
+            }
+            self.__ufmt_byte(f, i)?;
+        }
+        f.write_str("\" }")
+    }
+}
+
+impl<const BYTES: usize> Bitmap<BYTES> {
+    /// Write the last 2 bytes as binary, with a `"..."` if there are more.
+    fn __ufmt_tail<W: uWrite + ?Sized>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error> {
+        let size = 2.min(BYTES);
+        if BYTES > size {
+            f.write_str("...")?;
+        }
+        for i in 0..size {
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+            self.__ufmt_byte(f, size - i - 1)?;
+        }
+        Ok(())
+    }
+
+    /// Write the indexed byte as 8 binary digits.
+    fn __ufmt_byte<W: uWrite + ?Sized>(&self, f: &mut Formatter<'_, W>, i: usize) -> Result<(), W::Error> {
+        let byte = self.__copy_u8(i);
+        let mut digits = [b'0'; 8];
+        for (bit, digit) in digits.iter_mut().enumerate() {
+            if byte & (0x80 >> bit) != 0 {
+                *digit = b'1';
+            }
+        }
+        // SAFETY: only '0's and '1's, which are ASCII.
+        f.write_str(unsafe { core::str::from_utf8_unchecked(&digits) })
+    }
+}
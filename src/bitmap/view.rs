@@ -0,0 +1,552 @@
+//! Bitmap views over borrowed byte slices.
+
+use super::*;
+use alloc::string::String;
+use core::ops::{BitAndAssign, BitOrAssign, BitXorAssign};
+
+/// Panic for an index out of a view, telling the runtime length.
+#[cold]
+pub(super) fn __out_of_range(view: &str, verb: &str, index: usize, bits: usize) -> ! {
+    panic!("{view}: {verb} out of range, the index is {index} but the length is {bits} bits")
+}
+
+/// A read-only bitmap view over a borrowed `&[u8]`, whose length is only
+/// known at runtime. It has the same layout as [`Bitmap`]: the bit `i` is
+/// the bit `i % 8` of the byte `i / 8`.
+///
+/// Nothing is copied: the view reads the slice directly, so any byte
+/// buffer (a network frame, a mapped file) can be read like a bitmap.
+///
+/// # Examples
+/// ```
+/// use cbitmap::bitmap::*;
+///
+/// let buffer = [0xffu8, 0b_0000_0100, 0x80, 0xff];
+/// let view = BitmapRef::new(&buffer[1..3]);
+/// assert_eq!(view.bit_len(), 16);
+/// assert_eq!(view.get_bool(2), true);
+/// assert_eq!(view.count(), 2);
+/// assert_eq!(view.find_first_one(), Some(2));
+/// assert_eq!(view.iter_ones().collect::<Vec<_>>(), [2, 15]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BitmapRef<'a> {
+    bits: &'a [u8],
+}
+
+impl<'a> BitmapRef<'a> {
+    /// Create a view over `bits`.
+    pub const fn new(bits: &'a [u8]) -> Self {
+        Self { bits }
+    }
+
+    /// Create a view over the `len_bytes` bytes at `ptr`, like
+    /// [`core::slice::from_raw_parts()`]. Usable in const contexts.
+    ///
+    /// # Safety
+    /// For the whole `'a`, which may be `'static`:
+    /// * `ptr` must be non-null and valid for reads of `len_bytes` bytes,
+    ///   all of them initialized. There is no alignment requirement.
+    /// * The memory must not be written through any other pointer, nor
+    ///   change on its own like a register bank does.
+    /// * `len_bytes` must be at most [`isize::MAX`].
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// static TABLE: [u8; 2] = [0b_1000_0001, 0];
+    /// let view: BitmapRef<'static> = unsafe { BitmapRef::from_raw_parts(TABLE.as_ptr(), 2) };
+    /// assert_eq!(view.count(), 2);
+    /// ```
+    pub const unsafe fn from_raw_parts(ptr: *const u8, len_bytes: usize) -> Self {
+        Self::new(core::slice::from_raw_parts(ptr, len_bytes))
+    }
+
+    /// Get the viewed bytes.
+    pub const fn as_bytes(&self) -> &'a [u8] {
+        self.bits
+    }
+
+    /// Get the length of the view in bits.
+    pub const fn bit_len(&self) -> usize {
+        self.bits.len() * 8
+    }
+
+    /// Get the length of the view in bytes.
+    pub const fn byte_len(&self) -> usize {
+        self.bits.len()
+    }
+
+    /// Get the bool value of indexed bit.
+    ///
+    /// # Panics
+    /// Panic if `index` is out of range.
+    pub fn get_bool(&self, index: usize) -> bool {
+        if __out_bound(self.bits.len(), index) {
+            __out_of_range("BitmapRef", "getting", index, self.bit_len());
+        }
+        let (byte, bit) = __idx_1dto2d(index);
+        self.bits[byte] & (1 << bit) != 0
+    }
+
+    /// The same as [`BitmapRef::get_bool()`].
+    ///
+    /// # Panics
+    /// Panic if `index` is out of range.
+    #[inline]
+    pub fn test(&self, index: usize) -> bool {
+        self.get_bool(index)
+    }
+
+    /// Count the '1's.
+    pub fn count(&self) -> usize {
+        __count_ones(self.bits)
+    }
+
+    /// Find the first '1', returns its index.
+    ///
+    /// # Return
+    /// [`None`] if there is no '1', `Some(usize)` otherwise.
+    pub fn find_first_one(&self) -> Option<usize> {
+        __find_first_one(self.bits)
+    }
+
+    /// Find the first '0', returns its index.
+    ///
+    /// # Return
+    /// [`None`] if there is no '0', `Some(usize)` otherwise.
+    pub fn find_first_zero(&self) -> Option<usize> {
+        __find_first_zero(self.bits)
+    }
+
+    /// Iterate over the indices of the '1's, in ascending order.
+    pub fn iter_ones(&self) -> Ones<'a> {
+        Ones::new(self.bits)
+    }
+
+    /// `true` iff there is no '0'. An empty view gives `true`.
+    pub fn all(&self) -> bool {
+        self.find_first_zero().is_none()
+    }
+
+    /// `true` iff there is a '1'.
+    pub fn any(&self) -> bool {
+        self.find_first_one().is_some()
+    }
+
+    /// `true` iff there is no '1'.
+    pub fn none(&self) -> bool {
+        self.find_first_one().is_none()
+    }
+
+    /// Format a range of bits into a [`Option<String>`], like
+    /// [`Bitmap::range_to_string()`].
+    ///
+    /// # Return
+    /// [`None`] if the range is invalid (out of the view, or length is less
+    /// than 1), `Some(String)` otherwise.
+    pub fn range_to_string(&self, start: usize, end: usize) -> Option<String> {
+        let mut contents = String::new();
+        self.write_range(&mut contents, start, end).ok()?;
+        Some(contents)
+    }
+
+    /// Write a range of bits into `w`, like [`Bitmap::write_range()`].
+    ///
+    /// # Return
+    /// [`Err`] if the range is invalid, in which case nothing is written.
+    pub fn write_range<W: core::fmt::Write>(
+        &self,
+        w: &mut W,
+        start: usize,
+        end: usize,
+    ) -> core::fmt::Result {
+        __write_range(self.bits, w, start, end, 8, ' ')
+    }
+}
+
+impl<'a> From<&'a [u8]> for BitmapRef<'a> {
+    fn from(bits: &'a [u8]) -> Self {
+        Self::new(bits)
+    }
+}
+
+impl<'a, const BYTES: usize> From<&'a Bitmap<BYTES>> for BitmapRef<'a> {
+    fn from(map: &'a Bitmap<BYTES>) -> Self {
+        Self::new(&map.bits)
+    }
+}
+
+impl<const BYTES: usize> Bitmap<BYTES> {
+    /// Get a [`BitmapRef`] view of the map.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let map = he_lang!(1 | 12; 16);
+    /// let view = map.as_bitmap_ref();
+    /// assert_eq!(view.range_to_string(0, 16), map.range_to_string(0, 16));
+    /// ```
+    pub fn as_bitmap_ref(&self) -> BitmapRef<'_> {
+        BitmapRef::new(&self.bits)
+    }
+}
+
+/// A mutable bitmap view over a borrowed `&mut [u8]`, whose length is only
+/// known at runtime. The companion of [`BitmapRef`], with the same layout
+/// as [`Bitmap`].
+///
+/// The bits are changed right in the borrowed slice, so a caller-owned
+/// buffer can be managed like a bitmap. Like any `&mut`, the view is not
+/// [`Clone`]; use [`BitmapMut::reborrow()`] to lend a shorter-lived view.
+///
+/// # Examples
+/// ```
+/// use cbitmap::bitmap::*;
+///
+/// let mut buffer = [0u8; 4];
+/// let mut view = BitmapMut::new(&mut buffer[1..3]);
+/// view.set(0).set(15);
+/// view.set_range(4..6);
+/// assert_eq!(view.count(), 4);
+/// assert_eq!(buffer, [0, 0b_0011_0001, 0b_1000_0000, 0]);
+/// ```
+#[derive(Debug, PartialEq, Eq)]
+pub struct BitmapMut<'a> {
+    bits: &'a mut [u8],
+}
+
+impl<'a> BitmapMut<'a> {
+    /// Create a mutable view over `bits`.
+    pub fn new(bits: &'a mut [u8]) -> Self {
+        Self { bits }
+    }
+
+    /// Create a mutable view over the `len_bytes` bytes at `ptr`, like
+    /// [`core::slice::from_raw_parts_mut()`], for a bitmap kept at a fixed
+    /// address, such as an allocation table in shared memory.
+    ///
+    /// # Safety
+    /// For the whole `'a`, which may be `'static`:
+    /// * `ptr` must be non-null and valid for reads and writes of
+    ///   `len_bytes` bytes, all of them initialized. There is no alignment
+    ///   requirement.
+    /// * The memory must not be accessed through any other pointer, not
+    ///   even for reading: the view behaves like a `&mut [u8]`. It must not
+    ///   change on its own like a register bank does.
+    /// * `len_bytes` must be at most [`isize::MAX`].
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let raw = Box::into_raw(Box::new([0u8; 8])).cast::<u8>();
+    /// {
+    ///     let mut view = unsafe { BitmapMut::from_raw_parts(raw, 8) };
+    ///     view.set(63);
+    /// }
+    /// let buffer = unsafe { Box::from_raw(raw.cast::<[u8; 8]>()) };
+    /// assert_eq!(buffer[7], 0x80);
+    /// ```
+    pub unsafe fn from_raw_parts(ptr: *mut u8, len_bytes: usize) -> Self {
+        Self::new(core::slice::from_raw_parts_mut(ptr, len_bytes))
+    }
+
+    /// Get a shorter-lived mutable view of the same bytes, leaving `self`
+    /// usable again once it is dropped.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// fn mark(mut view: BitmapMut<'_>) {
+    ///     view.set(3);
+    /// }
+    ///
+    /// let mut buffer = [0u8; 2];
+    /// let mut view = BitmapMut::new(&mut buffer);
+    /// mark(view.reborrow());
+    /// mark(view.reborrow());
+    /// assert_eq!(view.count(), 1);
+    /// ```
+    pub fn reborrow(&mut self) -> BitmapMut<'_> {
+        BitmapMut { bits: self.bits }
+    }
+
+    /// Get a read-only [`BitmapRef`] of the same bytes.
+    pub fn as_bitmap_ref(&self) -> BitmapRef<'_> {
+        BitmapRef::new(self.bits)
+    }
+
+    /// Give back the borrowed bytes, ending the view.
+    pub fn into_bytes(self) -> &'a mut [u8] {
+        self.bits
+    }
+
+    /// Get the viewed bytes.
+    pub fn as_bytes(&self) -> &[u8] {
+        self.bits
+    }
+
+    /// Get the viewed bytes, mutably.
+    pub fn as_bytes_mut(&mut self) -> &mut [u8] {
+        self.bits
+    }
+
+    /// Get the length of the view in bits.
+    pub fn bit_len(&self) -> usize {
+        self.bits.len() * 8
+    }
+
+    /// Get the length of the view in bytes.
+    pub fn byte_len(&self) -> usize {
+        self.bits.len()
+    }
+
+    /// Get the bool value of indexed bit.
+    ///
+    /// # Panics
+    /// Panic if `index` is out of range.
+    pub fn get_bool(&self, index: usize) -> bool {
+        if __out_bound(self.bits.len(), index) {
+            __out_of_range("BitmapMut", "getting", index, self.bit_len());
+        }
+        self.as_bitmap_ref().get_bool(index)
+    }
+
+    /// The same as [`BitmapMut::get_bool()`].
+    ///
+    /// # Panics
+    /// Panic if `index` is out of range.
+    #[inline]
+    pub fn test(&self, index: usize) -> bool {
+        self.get_bool(index)
+    }
+
+    /// Count the '1's.
+    pub fn count(&self) -> usize {
+        __count_ones(self.bits)
+    }
+
+    /// Find the first '1', returns its index.
+    ///
+    /// # Return
+    /// [`None`] if there is no '1', `Some(usize)` otherwise.
+    pub fn find_first_one(&self) -> Option<usize> {
+        __find_first_one(self.bits)
+    }
+
+    /// Find the first '0', returns its index.
+    ///
+    /// # Return
+    /// [`None`] if there is no '0', `Some(usize)` otherwise.
+    pub fn find_first_zero(&self) -> Option<usize> {
+        __find_first_zero(self.bits)
+    }
+
+    /// Iterate over the indices of the '1's, in ascending order.
+    pub fn iter_ones(&self) -> Ones<'_> {
+        Ones::new(self.bits)
+    }
+
+    /// Iterate over the indices of the '1's, in ascending order, setting
+    /// each to '0' as it is yielded. See [`DrainOnes`].
+    pub fn drain_ones(&mut self) -> DrainOnes<'_> {
+        DrainOnes::new(self.bits)
+    }
+
+    /// `true` iff there is no '0'. An empty view gives `true`.
+    pub fn all(&self) -> bool {
+        self.find_first_zero().is_none()
+    }
+
+    /// `true` iff there is a '1'.
+    pub fn any(&self) -> bool {
+        self.find_first_one().is_some()
+    }
+
+    /// `true` iff there is no '1'.
+    pub fn none(&self) -> bool {
+        self.find_first_one().is_none()
+    }
+
+    /// Format a range of bits into a [`Option<String>`], like
+    /// [`Bitmap::range_to_string()`].
+    pub fn range_to_string(&self, start: usize, end: usize) -> Option<String> {
+        self.as_bitmap_ref().range_to_string(start, end)
+    }
+
+    /// Set the indexed bit to '1'.
+    ///
+    /// # Return
+    /// `&mut self`, allowing a call chain.
+    ///
+    /// # Panics
+    /// Panic if `index` is out of range.
+    pub fn set(&mut self, index: usize) -> &mut Self {
+        *self.__byte_of(index, "setting") |= 1 << __idx_get_bit(index);
+        self
+    }
+
+    /// Set the indexed bit to '0'.
+    ///
+    /// # Return
+    /// `&mut self`, allowing a call chain.
+    ///
+    /// # Panics
+    /// Panic if `index` is out of range.
+    pub fn reset(&mut self, index: usize) -> &mut Self {
+        *self.__byte_of(index, "resetting") &= !(1 << __idx_get_bit(index));
+        self
+    }
+
+    /// Flip the indexed bit.
+    ///
+    /// # Return
+    /// `&mut self`, allowing a call chain.
+    ///
+    /// # Panics
+    /// Panic if `index` is out of range.
+    pub fn flip(&mut self, index: usize) -> &mut Self {
+        *self.__byte_of(index, "flipping") ^= 1 << __idx_get_bit(index);
+        self
+    }
+
+    /// Set all bits to '1'.
+    pub fn set_all(&mut self) -> &mut Self {
+        self.bits.fill(0xff);
+        self
+    }
+
+    /// Set all bits to '0'.
+    pub fn reset_all(&mut self) -> &mut Self {
+        self.bits.fill(0);
+        self
+    }
+
+    /// Flip all bits.
+    pub fn flip_all(&mut self) -> &mut Self {
+        __flip_all(self.bits);
+        self
+    }
+
+    /// Set a range of bits to '1', like [`Bitmap::set_range()`].
+    ///
+    /// # Return
+    /// `&mut self`, allowing a call chain.
+    ///
+    /// # Panics
+    /// Panic if the range is reversed or goes beyond the view.
+    pub fn set_range<R: core::ops::RangeBounds<usize>>(&mut self, range: R) -> &mut Self {
+        let (start, end) = match __range_to_pair(&range, self.bit_len()) {
+            Some(pair) => pair,
+            None => panic!("BitmapMut: setting out of range"),
+        };
+        __set_range(self.bits, start, end);
+        self
+    }
+
+    /// Find the first '0' and set it to '1', like
+    /// [`Bitmap::set_first_zero()`].
+    ///
+    /// # Return
+    /// The index of the bit, or [`None`] if there is no '0'.
+    pub fn set_first_zero(&mut self) -> Option<usize> {
+        __flip_first_bit(self.bits, false)
+    }
+
+    /// Find the first '1' and set it to '0', like
+    /// [`Bitmap::reset_first_one()`].
+    ///
+    /// # Return
+    /// The index of the bit, or [`None`] if there is no '1'.
+    pub fn reset_first_one(&mut self) -> Option<usize> {
+        __flip_first_bit(self.bits, true)
+    }
+
+    /// Get the mutable reference of the indexed bit, wrapped in [`BitMut`].
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let mut buffer = [0u8; 2];
+    /// let mut view = BitmapMut::new(&mut buffer);
+    /// view.at_mut(9).set();
+    /// assert_eq!(buffer, [0, 0b_10]);
+    /// ```
+    ///
+    /// # Panics
+    /// Panic if `index` is out of range.
+    pub fn at_mut(&mut self, index: usize) -> BitMut<'_> {
+        let byte = self.__byte_of(index, "indexing");
+        BitMut::new(byte, index)
+    }
+
+    /// Get the byte holding the indexed bit, or panic with `verb`.
+    #[inline]
+    fn __byte_of(&mut self, index: usize, verb: &str) -> &mut u8 {
+        if __out_bound(self.bits.len(), index) {
+            __out_of_range("BitmapMut", verb, index, self.bit_len());
+        }
+        &mut self.bits[__idx_get_byte(index)]
+    }
+}
+
+impl<'a> From<&'a mut [u8]> for BitmapMut<'a> {
+    fn from(bits: &'a mut [u8]) -> Self {
+        Self::new(bits)
+    }
+}
+
+impl<'a, const BYTES: usize> From<&'a mut Bitmap<BYTES>> for BitmapMut<'a> {
+    fn from(map: &'a mut Bitmap<BYTES>) -> Self {
+        Self::new(&mut map.bits)
+    }
+}
+
+impl<'a, 'b> BitAndAssign<&'b [u8]> for BitmapMut<'a> {
+    /// AND the view with a slice of bytes. Like with arrays and
+    /// [`Bitmap`], if the view is longer than the slice, the rest of the
+    /// view is set to all-zero.
+    fn bitand_assign(&mut self, rhs: &'b [u8]) {
+        __zip_words_with(self.bits, rhs, |a, b| a & b);
+        if self.bits.len() > rhs.len() {
+            self.bits[rhs.len()..].fill(0);
+        }
+    }
+}
+
+impl<'a, 'b> BitOrAssign<&'b [u8]> for BitmapMut<'a> {
+    /// OR the view with a slice of bytes. Extra bytes of the slice are
+    /// ignored.
+    fn bitor_assign(&mut self, rhs: &'b [u8]) {
+        __zip_words_with(self.bits, rhs, |a, b| a | b);
+    }
+}
+
+impl<'a, 'b> BitXorAssign<&'b [u8]> for BitmapMut<'a> {
+    /// XOR the view with a slice of bytes. Extra bytes of the slice are
+    /// ignored.
+    fn bitxor_assign(&mut self, rhs: &'b [u8]) {
+        __zip_words_with(self.bits, rhs, |a, b| a ^ b);
+    }
+}
+
+impl<const BYTES: usize> Bitmap<BYTES> {
+    /// Get a [`BitmapMut`] view of the map.
+    ///
+    /// # Examples
+    /// ```
+    /// use cbitmap::bitmap::*;
+    ///
+    /// let mut map = newmap!(;16);
+    /// map.as_bitmap_mut().set(3);
+    /// assert_eq!(map.test(3), true);
+    /// ```
+    pub fn as_bitmap_mut(&mut self) -> BitmapMut<'_> {
+        BitmapMut::new(&mut self.bits)
+    }
+}
@@ -0,0 +1,196 @@
+//! Volatile access to bitmaps backed by hardware registers.
+
+use super::*;
+use core::marker::PhantomData;
+use core::ptr::NonNull;
+
+/// A bitmap of `BYTES` bytes at a fixed address, every access of which
+/// is volatile. Use it when the bits are a hardware register bank, so that
+/// the compiler never merges, caches or drops a read or a write.
+///
+/// Each method reads or writes only the bytes it needs, one byte at a
+/// time with [`read_volatile`](core::ptr::read_volatile) and
+/// [`write_volatile`](core::ptr::write_volatile). Nothing is cached:
+/// changing a bit reads its byte again. So the bulk methods like
+/// [`VolatileBitmap::set_all()`] still go byte by byte.
+///
+/// # Examples
+/// ```
+/// use cbitmap::bitmap::*;
+///
+/// let mut map = newmap!(;16);
+/// let mut reg = VolatileBitmap::new(&mut map);
+/// reg.set(3).flip(12);
+/// assert_eq!(reg.get_bool(12), true);
+/// assert_eq!(reg.count(), 2);
+/// ```
+#[derive(Debug)]
+pub struct VolatileBitmap<'a, const BYTES: usize> {
+    ptr: NonNull<u8>,
+    _map: PhantomData<&'a mut [u8; BYTES]>,
+}
+
+impl<'a, const BYTES: usize> VolatileBitmap<'a, BYTES> {
+    /// Access a map in ordinary memory volatilely.
+    pub fn new(map: &'a mut Bitmap<BYTES>) -> Self {
+        Self {
+            ptr: NonNull::from(&mut map.bits).cast(),
+            _map: PhantomData,
+        }
+    }
+
+    /// Access the `BYTES` bytes at `ptr` volatilely, like a register bank
+    /// at a fixed address.
+    ///
+    /// # Safety
+    /// For the whole `'a`, which may be `'static`, `ptr` must be non-null
+    /// and valid for volatile reads and writes of `BYTES` bytes. There is
+    /// no alignment requirement. The bytes may change on their own, but
+    /// must not be accessed non-volatilely through any other pointer.
+    pub unsafe fn from_ptr(ptr: *mut u8) -> Self {
+        Self {
+            ptr: NonNull::new_unchecked(ptr),
+            _map: PhantomData,
+        }
+    }
+
+    /// Volatilely read the indexed byte.
+    ///
+    /// # Panics
+    /// Panic if `i >= BYTES`.
+    pub fn read_byte(&self, i: usize) -> u8 {
+        if i >= BYTES {
+            panic!("Bitmap: getting out of range");
+        }
+        unsafe { self.ptr.as_ptr().add(i).read_volatile() }
+    }
+
+    /// Volatilely write the indexed byte.
+    ///
+    /// # Return
+    /// `&mut self`, allowing a call chain.
+    ///
+    /// # Panics
+    /// Panic if `i >= BYTES`.
+    pub fn write_byte(&mut self, i: usize, value: u8) -> &mut Self {
+        if i >= BYTES {
+            panic!("Bitmap: setting out of range");
+        }
+        unsafe { self.ptr.as_ptr().add(i).write_volatile(value) };
+        self
+    }
+
+    /// Volatilely read all the bytes into a [`Bitmap`].
+    pub fn load(&self) -> Bitmap<BYTES> {
+        let mut map = Bitmap::new();
+        for (i, byte) in map.bits.iter_mut().enumerate() {
+            *byte = self.read_byte(i);
+        }
+        map
+    }
+
+    /// Volatilely write all the bytes of `map`.
+    ///
+    /// # Return
+    /// `&mut self`, allowing a call chain.
+    pub fn store(&mut self, map: &Bitmap<BYTES>) -> &mut Self {
+        for (i, byte) in map.bits.iter().enumerate() {
+            self.write_byte(i, *byte);
+        }
+        self
+    }
+
+    /// Get the bool value of indexed bit, reading its byte volatilely.
+    ///
+    /// # Panics
+    /// Panic if `index` is out of range.
+    pub fn get_bool(&self, index: usize) -> bool {
+        let (byte, bit) = __idx_1dto2d(index);
+        self.read_byte(byte) & (1 << bit) != 0
+    }
+
+    /// The same as [`VolatileBitmap::get_bool()`].
+    ///
+    /// # Panics
+    /// Panic if `index` is out of range.
+    #[inline]
+    pub fn test(&self, index: usize) -> bool {
+        self.get_bool(index)
+    }
+
+    /// Set the indexed bit to '1', by a volatile read and a volatile write
+    /// of its byte. The two are not atomic.
+    ///
+    /// # Return
+    /// `&mut self`, allowing a call chain.
+    ///
+    /// # Panics
+    /// Panic if `index` is out of range.
+    pub fn set(&mut self, index: usize) -> &mut Self {
+        self.__update(index, "setting", |byte, mask| byte | mask)
+    }
+
+    /// Set the indexed bit to '0', like [`VolatileBitmap::set()`].
+    ///
+    /// # Return
+    /// `&mut self`, allowing a call chain.
+    ///
+    /// # Panics
+    /// Panic if `index` is out of range.
+    pub fn reset(&mut self, index: usize) -> &mut Self {
+        self.__update(index, "resetting", |byte, mask| byte & !mask)
+    }
+
+    /// Flip the indexed bit, like [`VolatileBitmap::set()`].
+    ///
+    /// # Return
+    /// `&mut self`, allowing a call chain.
+    ///
+    /// # Panics
+    /// Panic if `index` is out of range.
+    pub fn flip(&mut self, index: usize) -> &mut Self {
+        self.__update(index, "flipping", |byte, mask| byte ^ mask)
+    }
+
+    /// Volatilely write `0xff` to every byte, one by one.
+    pub fn set_all(&mut self) -> &mut Self {
+        for i in 0..BYTES {
+            self.write_byte(i, 0xff);
+        }
+        self
+    }
+
+    /// Volatilely write `0` to every byte, one by one.
+    pub fn reset_all(&mut self) -> &mut Self {
+        for i in 0..BYTES {
+            self.write_byte(i, 0);
+        }
+        self
+    }
+
+    /// Volatilely read and write back every byte flipped, one by one.
+    pub fn flip_all(&mut self) -> &mut Self {
+        for i in 0..BYTES {
+            let byte = self.read_byte(i);
+            self.write_byte(i, !byte);
+        }
+        self
+    }
+
+    /// Count the '1's, reading every byte volatilely once.
+    pub fn count(&self) -> usize {
+        (0..BYTES).map(|i| self.read_byte(i).count_ones() as usize).sum()
+    }
+
+    /// Read the byte of `index`, apply `f(byte, mask)` and write it back.
+    /// Panic with `verb` if `index` is out of range.
+    #[inline]
+    fn __update<F: FnOnce(u8, u8) -> u8>(&mut self, index: usize, verb: &str, f: F) -> &mut Self {
+        let (byte, bit) = __idx_1dto2d(index);
+        if byte >= BYTES {
+            panic!("Bitmap: {verb} out of range");
+        }
+        let value = f(self.read_byte(byte), 1 << bit);
+        self.write_byte(byte, value)
+    }
+}
@@ -162,11 +162,13 @@
 //!
 //! It is noteworthy that, we provide [`crate::bitmap::Bitmap::at()`] to get 
 //! [`crate::bitmap::BitRef`], and we also provide immutable [`core::ops::Index`]. 
-//! However, immutable [`core::ops::Index`] only returns a `bool` value, 
-//! not `BitRef` due to a similar issue.
+//! However, immutable [`core::ops::Index`] only returns a `&'static bool`
+//! to an interned `true` or `false`, not `BitRef` due to a similar issue.
 #![no_std]
 
 extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
 
 mod tools;
 pub mod bitmap;
\ No newline at end of file
@@ -11,25 +11,338 @@ pub(super) mod inner_use {
     }
 
     #[inline]
-    pub(crate) fn __idx_get_byte(index: usize) -> usize {
+    pub(crate) const fn __idx_get_byte(index: usize) -> usize {
         index >> 3
     }
 
     #[inline]
-    pub(crate) fn __idx_get_bit(index: usize) -> usize {
+    pub(crate) const fn __idx_get_bit(index: usize) -> usize {
         index & 0b111
     }
 
     #[inline]
-    pub(crate) fn __idx_1dto2d(index: usize) -> (usize, usize) {
+    pub(crate) const fn __idx_1dto2d(index: usize) -> (usize, usize) {
         (__idx_get_byte(index), __idx_get_bit(index))
     }
 
     #[inline]
-    pub(crate) fn __out_bound(bytes: usize, index: usize) -> bool {
+    pub(crate) const fn __out_bound(bytes: usize, index: usize) -> bool {
         __idx_get_byte(index) >= bytes
     }
 
+    /// Reverse the order of the bytes, in a const context.
+    #[inline]
+    pub(crate) const fn __reversed<const N: usize>(mut bytes: [u8; N]) -> [u8; N] {
+        let mut i = 0;
+        while i < N / 2 {
+            let byte = bytes[i];
+            bytes[i] = bytes[N - 1 - i];
+            bytes[N - 1 - i] = byte;
+            i += 1;
+        }
+        bytes
+    }
+
+    /// Turn a range of bits into `[start, end)`. Give `None` if the range
+    /// is reversed or goes beyond `bits`.
+    #[inline]
+    pub(crate) fn __range_to_pair<R: core::ops::RangeBounds<usize>>(
+        range: &R,
+        bits: usize,
+    ) -> Option<(usize, usize)> {
+        use core::ops::Bound::*;
+        let start = match range.start_bound() {
+            Included(&s) => s,
+            Excluded(&s) => s.checked_add(1)?,
+            Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Included(&e) => e.checked_add(1)?,
+            Excluded(&e) => e,
+            Unbounded => bits,
+        };
+        match start <= end && end <= bits {
+            true => Some((start, end)),
+            false => None,
+        }
+    }
+
+    /// The bytes in a word of the aligned access layer.
+    pub(crate) const __WORD: usize = core::mem::size_of::<usize>();
+
+    /// Split `bits` into the bytes before the first `usize`-aligned address,
+    /// the aligned words, and the bytes after them.
+    #[inline]
+    pub(crate) fn __as_words(bits: &[u8]) -> (&[u8], &[usize], &[u8]) {
+        // SAFETY: any bytes are a valid `usize`.
+        unsafe { bits.align_to::<usize>() }
+    }
+
+    /// The mutable version of [`__as_words`].
+    #[inline]
+    pub(crate) fn __as_words_mut(bits: &mut [u8]) -> (&mut [u8], &mut [usize], &mut [u8]) {
+        // SAFETY: any bytes are a valid `usize`, and the other way round.
+        unsafe { bits.align_to_mut::<usize>() }
+    }
+
+    /// Combine `src` into `dst` by `op`, a bitwise op, a word at a time
+    /// where possible. Words are read without alignment, as the two slices
+    /// need not be aligned the same. Only the common length is combined.
+    #[inline]
+    pub(crate) fn __zip_words_with<F: Fn(usize, usize) -> usize>(dst: &mut [u8], src: &[u8], op: F) {
+        let size = dst.len().min(src.len());
+        let (dst, src) = (&mut dst[..size], &src[..size]);
+        let mut dwords = dst.chunks_exact_mut(__WORD);
+        let mut swords = src.chunks_exact(__WORD);
+        for (d, s) in (&mut dwords).zip(&mut swords) {
+            let word = op(
+                usize::from_ne_bytes(d.try_into().unwrap()),
+                usize::from_ne_bytes(s.try_into().unwrap()),
+            );
+            d.copy_from_slice(&word.to_ne_bytes());
+        }
+        for (d, s) in dwords.into_remainder().iter_mut().zip(swords.remainder()) {
+            *d = op(*d as usize, *s as usize) as u8;
+        }
+    }
+
+    /// Find the index of the first bit equal to `value` in `bits`.
+    #[inline]
+    pub(crate) fn __find_first_bit(bits: &[u8], value: bool) -> Option<usize> {
+        #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+        if bits.len() >= super::simd::MIN_BYTES {
+            return super::simd::find_first_bit(bits, value);
+        }
+        __find_first_bit_words(bits, value)
+    }
+
+    /// Find the index of the first bit equal to `value` in `bits`, at or
+    /// after `start`.
+    #[inline]
+    pub(crate) fn __find_first_bit_from(bits: &[u8], value: bool, start: usize) -> Option<usize> {
+        let (byte, bit) = __idx_1dto2d(start);
+        let flip = if value { 0 } else { 0xff };
+        let first = (*bits.get(byte)? ^ flip) & (0xff << bit);
+        if first != 0 {
+            return Some(byte * 8 + first.trailing_zeros() as usize);
+        }
+        __find_first_bit(&bits[byte + 1..], value).map(|index| (byte + 1) * 8 + index)
+    }
+
+    /// Find the index of the first bit equal to `value` in `bits`, at or
+    /// after `start`, wrapping around to bit 0 once. The bits before
+    /// `start` are searched last.
+    #[inline]
+    pub(crate) fn __find_first_bit_wrapping(bits: &[u8], value: bool, start: usize) -> Option<usize> {
+        match __find_first_bit_from(bits, value, start) {
+            Some(index) => Some(index),
+            // The bits from `start` on are not `value`, so a bit found in
+            // this prefix is before `start`.
+            None => __find_first_bit(bits.get(..=__idx_get_byte(start))?, value),
+        }
+    }
+
+    /// Find the index of the last bit equal to `value` in `bits`, a word
+    /// at a time for the aligned part.
+    #[inline]
+    pub(crate) fn __find_last_bit(bits: &[u8], value: bool) -> Option<usize> {
+        // Look for a '1' in `byte ^ flip`.
+        let flip = if value { 0 } else { usize::MAX };
+        let (head, words, tail) = __as_words(bits);
+        let in_bytes = |bytes: &[u8], base: usize| {
+            bytes.iter().enumerate().rev().find_map(|(i, byte)| {
+                let byte = *byte ^ flip as u8;
+                (byte != 0).then(|| base + i * 8 + 7 - byte.leading_zeros() as usize)
+            })
+        };
+        let base = (head.len() + words.len() * __WORD) * 8;
+        if let Some(index) = in_bytes(tail, base) {
+            return Some(index);
+        }
+        for (i, word) in words.iter().enumerate().rev() {
+            let word = usize::from_le(*word) ^ flip;
+            if word != 0 {
+                let top = head.len() * 8 + (i + 1) * __WORD * 8 - 1;
+                return Some(top - word.leading_zeros() as usize);
+            }
+        }
+        in_bytes(head, 0)
+    }
+
+    /// Find the last bit equal to `value` in `bits` and flip it, like
+    /// [`__flip_first_bit`].
+    #[inline]
+    pub(crate) fn __flip_last_bit(bits: &mut [u8], value: bool) -> Option<usize> {
+        let index = __find_last_bit(bits, value)?;
+        let (byte, bit) = __idx_1dto2d(index);
+        bits[byte] ^= 1 << bit;
+        Some(index)
+    }
+
+    /// Find the first bit equal to `value` in `bits` and flip it, writing
+    /// only its byte. Give its index.
+    #[inline]
+    pub(crate) fn __flip_first_bit(bits: &mut [u8], value: bool) -> Option<usize> {
+        let index = __find_first_bit(bits, value)?;
+        let (byte, bit) = __idx_1dto2d(index);
+        bits[byte] ^= 1 << bit;
+        Some(index)
+    }
+
+    /// [`__find_first_bit`] a word at a time for the aligned part.
+    #[inline]
+    pub(crate) fn __find_first_bit_words(bits: &[u8], value: bool) -> Option<usize> {
+        // Look for a '1' in `byte ^ flip`.
+        let flip = if value { 0 } else { usize::MAX };
+        let (head, words, tail) = __as_words(bits);
+        let in_bytes = |bytes: &[u8], base: usize| {
+            bytes.iter().enumerate().find_map(|(i, byte)| {
+                let byte = *byte ^ flip as u8;
+                (byte != 0).then(|| base + i * 8 + byte.trailing_zeros() as usize)
+            })
+        };
+        if let Some(index) = in_bytes(head, 0) {
+            return Some(index);
+        }
+        let base = head.len() * 8;
+        for (i, word) in words.iter().enumerate() {
+            // The lowest bits are in the first byte, on any endianness.
+            let word = usize::from_le(*word) ^ flip;
+            if word != 0 {
+                return Some(base + i * __WORD * 8 + word.trailing_zeros() as usize);
+            }
+        }
+        in_bytes(tail, base + words.len() * __WORD * 8)
+    }
+
+    /// Count the '1's in `bits`.
+    #[inline]
+    pub(crate) fn __count_ones(bits: &[u8]) -> usize {
+        #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+        if bits.len() >= super::simd::MIN_BYTES {
+            return super::simd::count_ones(bits);
+        }
+        __count_ones_words(bits)
+    }
+
+    /// [`__count_ones`] a word at a time for the aligned part.
+    #[inline]
+    pub(crate) fn __count_ones_words(bits: &[u8]) -> usize {
+        let (head, words, tail) = __as_words(bits);
+        let in_bytes = |bytes: &[u8]| -> usize {
+            bytes.iter().map(|byte| byte.count_ones() as usize).sum()
+        };
+        in_bytes(head)
+            + words.iter().map(|word| word.count_ones() as usize).sum::<usize>()
+            + in_bytes(tail)
+    }
+
+    /// Count the '1's in the bits `[start, end)` of `bits`. The range must
+    /// be in `bits`.
+    pub(crate) fn __count_range(bits: &[u8], start: usize, end: usize) -> usize {
+        if start == end {
+            return 0;
+        }
+        let (sbyte, sbit) = __idx_1dto2d(start);
+        let (ebyte, ebit) = __idx_1dto2d(end - 1);
+        let head = 0xffu8 << sbit;
+        let tail = 0xffu8 >> (7 - ebit);
+        if sbyte == ebyte {
+            return (bits[sbyte] & head & tail).count_ones() as usize;
+        }
+        (bits[sbyte] & head).count_ones() as usize
+            + __count_ones(&bits[sbyte + 1..ebyte])
+            + (bits[ebyte] & tail).count_ones() as usize
+    }
+
+    /// Find the index of the first '1' in `bits`.
+    #[inline]
+    pub(crate) fn __find_first_one(bits: &[u8]) -> Option<usize> {
+        __find_first_bit(bits, true)
+    }
+
+    /// Find the index of the first '0' in `bits`.
+    #[inline]
+    pub(crate) fn __find_first_zero(bits: &[u8]) -> Option<usize> {
+        __find_first_bit(bits, false)
+    }
+
+    /// Flip every bit of `bits`, a word at a time for the aligned part.
+    #[inline]
+    pub(crate) fn __flip_all(bits: &mut [u8]) {
+        let (head, words, tail) = __as_words_mut(bits);
+        for byte in head.iter_mut().chain(tail) {
+            *byte = !*byte;
+        }
+        for word in words {
+            *word = !*word;
+        }
+    }
+
+    /// Set the bits `[start, end)` of `bits` to '1'. The range must be in
+    /// `bits`.
+    pub(crate) fn __set_range(bits: &mut [u8], start: usize, end: usize) {
+        if start == end {
+            return;
+        }
+        let (sbyte, sbit) = __idx_1dto2d(start);
+        let (ebyte, ebit) = __idx_1dto2d(end - 1);
+        let head = 0xffu8 << sbit;
+        let tail = 0xffu8 >> (7 - ebit);
+        if sbyte == ebyte {
+            __byte_or_u8(&mut bits[sbyte], head & tail);
+            return;
+        }
+        __byte_or_u8(&mut bits[sbyte], head);
+        for byte in &mut bits[sbyte + 1..ebyte] {
+            *byte = 0xff;
+        }
+        __byte_or_u8(&mut bits[ebyte], tail);
+    }
+
+    /// Set the bits `[start, end)` of `bits` to '0'. The range must be in
+    /// `bits`.
+    pub(crate) fn __reset_range(bits: &mut [u8], start: usize, end: usize) {
+        if start == end {
+            return;
+        }
+        let (sbyte, sbit) = __idx_1dto2d(start);
+        let (ebyte, ebit) = __idx_1dto2d(end - 1);
+        let head = 0xffu8 << sbit;
+        let tail = 0xffu8 >> (7 - ebit);
+        if sbyte == ebyte {
+            __byte_and_u8(&mut bits[sbyte], !(head & tail));
+            return;
+        }
+        __byte_and_u8(&mut bits[sbyte], !head);
+        bits[sbyte + 1..ebyte].fill(0);
+        __byte_and_u8(&mut bits[ebyte], !tail);
+    }
+
+    /// Write the bits `[start, end)` of `bits` as `'0'`/`'1'`, the highest
+    /// on the left, with `sep` at the edge of every `group` bits. Give an
+    /// error and write nothing if the range is empty or out of `bits`.
+    pub(crate) fn __write_range<W: core::fmt::Write>(
+        bits: &[u8],
+        w: &mut W,
+        start: usize,
+        end: usize,
+        group: usize,
+        sep: char,
+    ) -> core::fmt::Result {
+        if start >= end || __out_bound(bits.len(), start) || __out_bound(bits.len(), end - 1) {
+            return Err(core::fmt::Error);
+        }
+        for i in (start..end).rev() {
+            let one = bits[__idx_get_byte(i)] & (1 << __idx_get_bit(i)) != 0;
+            w.write_char(if one { '1' } else { '0' })?;
+            if i > start && group > 0 && i % group == 0 {
+                w.write_char(sep)?;
+            }
+        }
+        Ok(())
+    }
+
     #[inline]
     pub(crate) fn __copy_bytes<const N: usize, const M: usize>(src: [u8; M]) -> [u8; N] {
         let mut dst = [0u8; N];
@@ -68,4 +381,110 @@ pub(super) mod inner_use {
             }
         };
     }
-}
\ No newline at end of file
+}
+
+/// SSE2 versions of the word-layer scans, for buffers of at least
+/// [`simd::MIN_BYTES`]. SSE2 is part of every `x86_64` CPU, so there is
+/// no runtime detection, and calling the intrinsics is always sound. The
+/// bytes after the last full block go through the word layer.
+#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+mod simd {
+    use super::inner_use::*;
+    use core::arch::x86_64::*;
+
+    /// Shorter buffers are left to the word layer.
+    pub(crate) const MIN_BYTES: usize = 256;
+
+    /// The bytes in a vector.
+    const LANES: usize = 16;
+
+    /// Load the `i`-th vector of `bytes`.
+    ///
+    /// # Panics
+    /// Panic if `bytes` is shorter than `i + 1` vectors.
+    #[inline(always)]
+    unsafe fn load(bytes: &[u8], i: usize) -> __m128i {
+        let vector = &bytes[i * LANES..(i + 1) * LANES];
+        _mm_loadu_si128(vector.as_ptr().cast())
+    }
+
+    /// Popcount the bytes of `v`, added up into its two `u64` lanes.
+    #[inline(always)]
+    unsafe fn popcount(v: __m128i) -> __m128i {
+        let m1 = _mm_set1_epi8(0x55);
+        let m2 = _mm_set1_epi8(0x33);
+        let m4 = _mm_set1_epi8(0x0f);
+        let v = _mm_sub_epi8(v, _mm_and_si128(_mm_srli_epi16(v, 1), m1));
+        let v = _mm_add_epi8(_mm_and_si128(v, m2), _mm_and_si128(_mm_srli_epi16(v, 2), m2));
+        let v = _mm_and_si128(_mm_add_epi8(v, _mm_srli_epi16(v, 4)), m4);
+        _mm_sad_epu8(v, _mm_setzero_si128())
+    }
+
+    /// Add up the two `u64` lanes of `v`.
+    #[inline(always)]
+    unsafe fn lanes(v: __m128i) -> usize {
+        (_mm_cvtsi128_si64(v) + _mm_cvtsi128_si64(_mm_unpackhi_epi64(v, v))) as usize
+    }
+
+    /// A carry-save adder: add up `a`, `b` and `c` bit by bit into a
+    /// carry and a sum.
+    #[inline(always)]
+    unsafe fn csa(a: __m128i, b: __m128i, c: __m128i) -> (__m128i, __m128i) {
+        let u = _mm_xor_si128(a, b);
+        (_mm_or_si128(_mm_and_si128(a, b), _mm_and_si128(u, c)), _mm_xor_si128(u, c))
+    }
+
+    /// Count by the Harley-Seal method: carry-save adders fold 8 vectors
+    /// into 1 of eights, and only that one is popcounted.
+    pub(crate) fn count_ones(bits: &[u8]) -> usize {
+        let mut blocks = bits.chunks_exact(LANES * 8);
+        unsafe {
+            let zero = _mm_setzero_si128();
+            let (mut ones, mut twos, mut fours, mut eights) = (zero, zero, zero, zero);
+            for block in &mut blocks {
+                let (twos_a, o) = csa(ones, load(block, 0), load(block, 1));
+                let (twos_b, o) = csa(o, load(block, 2), load(block, 3));
+                let (fours_a, t) = csa(twos, twos_a, twos_b);
+                let (twos_a, o) = csa(o, load(block, 4), load(block, 5));
+                let (twos_b, o) = csa(o, load(block, 6), load(block, 7));
+                let (fours_b, t) = csa(t, twos_a, twos_b);
+                let (e, f) = csa(fours, fours_a, fours_b);
+                (ones, twos, fours) = (o, t, f);
+                eights = _mm_add_epi64(eights, popcount(e));
+            }
+            let mut count = 8 * lanes(eights)
+                + 4 * lanes(popcount(fours))
+                + 2 * lanes(popcount(twos))
+                + lanes(popcount(ones));
+            let vectors = blocks.remainder().chunks_exact(LANES);
+            let rest = vectors.remainder();
+            for vector in vectors {
+                count += lanes(popcount(load(vector, 0)));
+            }
+            count + __count_ones_words(rest)
+        }
+    }
+
+    /// Test 4 vectors at a time, then find the bit in the first block of
+    /// 4 holding it by the word layer.
+    pub(crate) fn find_first_bit(bits: &[u8], value: bool) -> Option<usize> {
+        let mut blocks = bits.chunks_exact(LANES * 4);
+        unsafe {
+            let skip = _mm_set1_epi8(if value { 0 } else { -1 });
+            for (i, block) in (&mut blocks).enumerate() {
+                let same = |j| _mm_cmpeq_epi8(load(block, j), skip);
+                let same = _mm_and_si128(
+                    _mm_and_si128(same(0), same(1)),
+                    _mm_and_si128(same(2), same(3)),
+                );
+                if _mm_movemask_epi8(same) != 0xffff {
+                    let base = i * LANES * 4 * 8;
+                    return __find_first_bit_words(block, value).map(|index| base + index);
+                }
+            }
+        }
+        let rest = blocks.remainder();
+        let base = (bits.len() - rest.len()) * 8;
+        __find_first_bit_words(rest, value).map(|index| base + index)
+    }
+}
@@ -0,0 +1,43 @@
+#[cfg(test)]
+mod fuzzing {
+    extern crate cbitmap;
+    use arbitrary::{Arbitrary, Unstructured};
+    use cbitmap::bitmap::*;
+
+    #[test]
+    fn from_fixed_buffer() {
+        let data = [0b_1, 0b_10, 0xff, 0x80, 0x7f];
+        let mut u = Unstructured::new(&data);
+        let a = Bitmap::<2>::arbitrary(&mut u).unwrap();
+        let b = Bitmap::<2>::arbitrary(&mut u).unwrap();
+        assert_eq!(a, he_lang!(0 | 9; 16));
+        assert_eq!(b, Bitmap::<2>::from([0xff, 0x80]));
+        // Only 1 byte is left.
+        assert!(Bitmap::<2>::arbitrary(&mut u).is_err());
+        assert_eq!(Bitmap::<0>::arbitrary(&mut u).unwrap(), newmap!());
+        assert_eq!(Bitmap::<16>::size_hint(0), (16, Some(16)));
+    }
+
+    #[test]
+    fn inside_a_struct() {
+        #[derive(Debug)]
+        struct Packet {
+            flags: Bitmap<16>,
+            len: u8,
+        }
+
+        impl<'a> Arbitrary<'a> for Packet {
+            fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+                Ok(Packet {
+                    flags: u.arbitrary()?,
+                    len: u.arbitrary()?,
+                })
+            }
+        }
+
+        let data = [0x55; 32];
+        let packet = Packet::arbitrary(&mut Unstructured::new(&data)).unwrap();
+        assert_eq!(packet.flags, Bitmap::<16>::repeat_byte(0x55));
+        assert_eq!(packet.len, 0x55);
+    }
+}
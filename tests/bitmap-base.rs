@@ -1,8 +1,26 @@
+#![allow(clippy::bool_assert_comparison)]
+#![cfg_attr(
+    feature = "bytemuck",
+    allow(clippy::clone_on_copy, clippy::op_ref, clippy::needless_borrows_for_generic_args)
+)]
+
 #[cfg(test)]
 mod base {
     extern crate cbitmap;
     use cbitmap::bitmap::*;
 
+    /// A fixed buffer, to show that formatting does not allocate.
+    struct Buf([u8; 64], usize);
+
+    impl core::fmt::Write for Buf {
+        fn write_str(&mut self, s: &str) -> core::fmt::Result {
+            let end = self.1 + s.len();
+            self.0.get_mut(self.1..end).ok_or(core::fmt::Error)?.copy_from_slice(s.as_bytes());
+            self.1 = end;
+            Ok(())
+        }
+    }
+
     #[test]
     fn advanced_op() {
         let mut map = newmap!(;16);
@@ -77,7 +95,8 @@ mod base {
 
     #[test]
     fn from_into() {
-        let map0 = Bitmap::<0>::from([]);
+        // `from([])` is ambiguous since `From<[bool; N]>` exists.
+        let map0 = Bitmap::<0>::from([0u8; 0]);
         assert_eq!(Into::<[u8; 0]>::into(map0), []);
         let map1 = Bitmap::<1>::from(0b00001010);
         assert_eq!(Into::<[u8; 1]>::into(map1), [0b00001010u8; 1]);
@@ -210,6 +229,112 @@ mod base {
         assert_eq!(bitmap.get_bool(7), true);
     }
 
+    #[test]
+    fn xor() {
+        let mut bitmap = Bitmap::<16>::new();
+        bitmap.set(3).set(100);
+        bitmap ^= (1u128 << 100) | (1u128 << 127) | 1;
+        assert_eq!(bitmap.test(100), false);
+        assert_eq!(bitmap.test(127), true);
+        assert_eq!(bitmap.test(0), true);
+        assert_eq!(bitmap.test(3), true);
+        assert_eq!(bitmap.count(), 3);
+        assert_eq!(&bitmap ^ u128::MAX, !((1u128 << 127) | (1 << 3) | 1));
+
+        // A shorter rhs only touches the first bytes.
+        bitmap ^= 0b_1001_u8;
+        assert_eq!(bitmap.test(0), false);
+        assert_eq!(bitmap.test(3), false);
+        assert_eq!(bitmap.test(127), true);
+        assert_eq!(bitmap.count(), 1);
+
+        // A longer rhs: the extra bytes are ignored by ^=, and kept by ^.
+        let mut map = newmap!(;8);
+        map ^= [0b_1u8, 0b_11];
+        assert_eq!(&map.range_to_string(0, 8).unwrap(), "00000001");
+        assert_eq!(&map ^ [0b_11u8, 0b_11], [0b_10u8, 0b_11]);
+        assert_eq!(&map ^ 0x_ff01_u16, 0x_ff00_u16);
+    }
+
+    #[test]
+    fn eq() {
+        let mut a = newmap!(;24);
+        let mut b = newmap!(;24);
+        a.set(3).set(17).flip(5).reset(3);
+        b.flip(5).set(17);
+        assert_eq!(a, b);
+        assert_eq!(b, a);
+        assert_eq!(a, a.clone());
+
+        b.set(23);
+        assert_ne!(a, b);
+        a.set_all();
+        b.set_all().reset(23);
+        assert_ne!(a, b);
+
+        assert_eq!(newmap!(), newmap!());
+    }
+
+    #[test]
+    fn eq_cross_size() {
+        let small: Bitmap<2> = 0x_8001_u16.into();
+        let mut large: Bitmap<4> = 0x_8001_u16.into();
+        assert_eq!(small.eq_zero_extended(&large), true);
+        assert_eq!(large.eq_zero_extended(&small), true);
+
+        large.set(31);
+        assert_eq!(small.eq_zero_extended(&large), false);
+        assert_eq!(large.eq_zero_extended(&small), false);
+        large.reset(31).set(16);
+        assert_eq!(small.eq_zero_extended(&large), false);
+
+        let mut large: Bitmap<4> = 0x_8001_u16.into();
+        large.reset(15);
+        assert_eq!(small.eq_zero_extended(&large), false);
+
+        assert_eq!(newmap!().eq_zero_extended(&Bitmap::<4>::new()), true);
+        assert_eq!(Bitmap::<4>::from(1u8).eq_zero_extended(&newmap!()), false);
+    }
+
+    static LOOKUP: Bitmap<4> = Bitmap::from_array([0b_1, 0, 0, 0])
+        .with_bit_set(13)
+        .with_bit_set(31)
+        .with_bit_flipped(7)
+        .with_bit_reset(0);
+    const EMPTY: Bitmap<2> = Bitmap::new();
+
+    #[test]
+    fn const_map() {
+        assert_eq!(LOOKUP.count(), 3);
+        assert_eq!(LOOKUP.test_all_of(&[7, 13, 31]), true);
+        assert_eq!(LOOKUP.test(0), false);
+        assert_eq!(EMPTY.none(), true);
+        assert_eq!(LOOKUP, he_lang!(7 | 13 | 31; 32));
+    }
+
+    static DEFAULT_FLAGS: Bitmap<8> = constmap!(0b1 | 0b1000_0000u64; 64);
+    const FLAG: u32 = 1 << 20;
+    const IDENT_FLAGS: Bitmap<4> = constmap!(FLAG; 32);
+
+    #[test]
+    fn const_macro() {
+        assert_eq!(DEFAULT_FLAGS, newmap!(0b1 | 0b1000_0000u64; 64));
+        assert_eq!(DEFAULT_FLAGS.count(), 2);
+        let flag = FLAG;
+        assert_eq!(IDENT_FLAGS, newmap!(flag; 32));
+        assert_eq!(constmap!(), newmap!());
+        assert_eq!(constmap!(;24), newmap!(;24));
+
+        // Signed masks are not sign-extended.
+        const NEG: Bitmap<4> = constmap!(-1i8; 32);
+        assert_eq!(NEG, newmap!(-1i8; 32));
+        assert_eq!(NEG.count(), 8);
+        const MASK: i16 = -2;
+        const NEG_IDENT: Bitmap<4> = constmap!(MASK; 32);
+        assert_eq!(NEG_IDENT, newmap!(MASK; 32));
+        assert_eq!(NEG_IDENT.count(), 15);
+    }
+
     #[test]
     fn test_macro() {
         let map = newmap!();
@@ -235,16 +360,2216 @@ mod base {
     }
 
     #[test]
-    fn test_mem() {
-        use core::mem::*;
-        let map = newmap!();
-        assert_eq!(size_of_val(&map), 0);
-        let map = newmap!(;48);
-        assert_eq!(size_of_val(&map), 6);
+    fn macro_expr() {
+        const FLAG_A: u64 = 1 << 40;
+        const FLAG_B: u64 = 1 << 41;
+        fn flag(i: u32) -> u64 {
+            1 << i
+        }
 
-        // 4K bytes
-        let page = Box::new(newmap!(;4096 * 8));
-        assert_eq!(size_of_val(&page), 8);
-        assert_eq!(size_of_val(&*page), 4096);
+        let map = newmap!((1u64 << 12), (FLAG_A | FLAG_B); 64);
+        assert_eq!(map, he_lang!(12 | 40 | 41; 64));
+
+        let map = newmap!(1u8 << 3, flag(60), FLAG_A,; 64);
+        assert_eq!(map, he_lang!(3 | 40 | 60; 64));
+
+        let map = newmap!(1 << 12; 14);
+        assert_eq!(map, he_lang!(12; 14));
+
+        // The former syntaxes still work.
+        let a = 1u8;
+        assert_eq!(newmap!(a | FLAG_A; 64), newmap!(1u8, FLAG_A; 64));
+        assert_eq!(newmap!(0b_11; 8), newmap!(0b_01 | 0b_10; 8));
+    }
+
+    #[test]
+    fn bits_macro() {
+        let map = bits![3, 5, 17; 32];
+        assert_eq!(map, he_lang!(3 | 5 | 17; 32));
+
+        // Empty list.
+        let map = bits![; 20];
+        assert_eq!(map.bit_len(), 24);
+        assert_eq!(map.none(), true);
+
+        // Duplicates and trailing comma.
+        let map = bits![7, 7, 0, 7,; 8];
+        assert_eq!(map, newmap!(0b_10000001; 8));
+
+        // Exprs.
+        const BASE: usize = 16;
+        let offset = 3;
+        let map = bits![BASE + offset, BASE * 2 - 1, offset << 1; BASE * 2];
+        assert_eq!(map, he_lang!(19 | 31 | 6; 32));
+    }
+
+    #[test]
+    fn set_range() {
+        let mut map = newmap!(;32);
+        map.set_range(5..5);
+        assert_eq!(map.none(), true);
+        map.set_range(2..6);
+        assert_eq!(map, newmap!(0b_111100; 32));
+        map.set_range(6..=25);
+        assert_eq!(map.count(), 24);
+        assert_eq!(map.find_first_zero(), Some(0));
+        map.set_range(..);
+        assert_eq!(map.all(), true);
+
+        let mut map = newmap!(;32);
+        map.set_range(16..);
+        assert_eq!(map, newmap!(0x_ffff_0000_u32; 32));
+    }
+
+    #[test]
+    fn rangemap_macro() {
+        // Crossing byte boundaries.
+        let map = rangemap!(6..19; 24);
+        assert_eq!(&map.range_to_string(0, 24).unwrap(), "00000111 11111111 11000000");
+
+        // Inclusive range ending at the last bit.
+        let map = rangemap!(8..16, 30..=31; 32);
+        assert_eq!(map, newmap!(0x_c000_ff00_u32; 32));
+
+        // Mixed with single indexes and exprs.
+        const LAST: usize = 39;
+        let start = 1;
+        let map = rangemap!(0, start + 1..start + 4, LAST, 20..=20,; 40);
+        assert_eq!(map, he_lang!(0 | 2 | 3 | 4 | 20 | 39; 40));
+
+        let map = rangemap!(; 8);
+        assert_eq!(map.none(), true);
+    }
+
+    #[test]
+    fn macro_bytes() {
+        // Shorter than the map.
+        let map = newmap!([0xff, 0x0f]; 32);
+        assert_eq!(map, newmap!(0x_0fff_u32; 32));
+        // Equal to the map.
+        let map = newmap!([0x01, 0x02, 0x80]; 24);
+        assert_eq!(map, he_lang!(0 | 9 | 23; 24));
+        // Longer than the map: the extra bytes are ignored.
+        let map = newmap!([0x01, 0x02, 0x80]; 16);
+        assert_eq!(map, he_lang!(0 | 9; 16));
+        // Byte strings.
+        let map = newmap!(b"\xff\x0f"; 16);
+        assert_eq!(map, newmap!(0x_0fff_u16; 16));
+        let map = newmap!(b"\x01" | b"\x00\x01"; 16);
+        assert_eq!(map, he_lang!(0 | 8; 16));
+        // Mixed with other exprs.
+        let bytes = [0u8, 0x10];
+        let map = newmap!(bytes, 1u8, b"\x00\x00\x01"; 24);
+        assert_eq!(map, he_lang!(0 | 12 | 16; 24));
+    }
+
+    #[test]
+    fn bitstr_macro() {
+        let map = bitstr!("0110_1000 1111_0000");
+        assert_eq!(map, newmap!(0b_0110_1000_1111_0000_u16; 16));
+        assert_eq!(&map.range_to_string(0, 16).unwrap(), "01101000 11110000");
+
+        // Rounded up, with leading zeros.
+        let map = bitstr!("1_0000_0001");
+        assert_eq!(map.bit_len(), 16);
+        assert_eq!(&map.range_to_string(0, 16).unwrap(), "00000001 00000001");
+
+        let map = bitstr!("1");
+        assert_eq!(map, he_lang!(0; 8));
+        let map = bitstr!("");
+        assert_eq!(map.bit_len(), 0);
+    }
+
+    #[test]
+    fn he_lang_expr() {
+        const START: usize = 30;
+        let end = 63;
+        let map = he_lang!(START | START + 1 | end; 64);
+        assert_eq!(map, newmap!(3u64 << 30 | 1 << 63; 64));
+
+        let map = he_lang!(0 | end - 1 | 2 * 4 | (1 | 2) | 4; 64);
+        assert_eq!(map.count(), 5);
+        assert_eq!(map.test_all_of(&[0, 3, 4, 8, 62]), true);
+
+        let map = he_lang!(1 | 2 |; 8);
+        assert_eq!(map, newmap!(0b_110; 8));
+        let map = he_lang!(; 8);
+        assert_eq!(map.none(), true);
+    }
+
+    #[test]
+    fn long_macro_lists() {
+        let map = he_lang!(
+            0 | 1 | 2 | 3 | 4 | 5 | 6 | 7 | 8 | 9 | 10 | 11 | 12 | 13 | 14 | 15 | 16 | 17
+                | 18 | 19 | 20 | 21 | 22 | 23 | 24 | 25 | 26 | 27 | 28 | 29 | 30 | 31 | 32
+                | 33 | 34 | 35 | 36 | 37 | 38 | 39 | 40 | 41 | 42 | 43 | 44 | 45 | 46 | 47
+                | 48 | 49 | 50 | 51 | 52 | 53 | 54 | 55 | 56 | 57 | 58 | 59 | 60 | 61 | 62
+                | 63 | 64 | 65 | 66 | 67 | 68 | 69; 72
+        );
+        assert_eq!(map.count(), 70);
+        assert_eq!(map.find_first_zero(), Some(70));
+
+        // Exprs are split on `|`, not taken token by token.
+        let i = 0;
+        let map = he_lang!(
+            i | i + 1 | i + 2 | i + 3 | i + 4 | i + 5 | i + 6 | i + 7 | i + 8 | i + 9
+                | i + 10 | i + 11 | i + 12 | i + 13 | i + 14 | i + 15 | i + 16 | i + 17
+                | i + 18 | i + 19 | i + 20 | i + 21 | i + 22 | i + 23 | i + 24 | i + 25
+                | i + 26 | i + 27 | i + 28 | i + 29 | i + 30 | i + 31 | i + 32 | i + 33
+                | i + 34 | i + 35 | i + 36 | i + 37 | i + 38 | i + 39 | i + 40 | i + 41
+                | i + 42 | i + 43 | i + 44 | i + 45 | i + 46 | i + 47 | i + 48 | i + 49
+                | i + 50 | i + 51 | i + 52 | i + 53 | i + 54 | i + 55 | i + 56 | i + 57
+                | i + 58 | i + 59 | i + 60 | i + 61 | i + 62 | i + 63 | i + 64 | i + 65
+                | i + 66 | i + 67 | i + 68 | i + 69; 72
+        );
+        assert_eq!(map.count(), 70);
+
+        let map = rangemap!(
+            0..1, 2..3, 4..5, 6..7, 8..9, 10..11, 12..13, 14..15, 16..17, 18..19, 20..21,
+            22..23, 24..25, 26..27, 28..29, 30..31, 32..33, 34..35, 36..37, 38..39, 40..41,
+            42..43, 44..45, 46..47, 48..49, 50..51, 52..53, 54..55, 56..57, 58..59, 60..61,
+            62..63, 64..65, 66..67, 68..69, 70..71, 72..73, 74..75, 76..77, 78..79, 80..81,
+            82..83, 84..85, 86..87, 88..89, 90..91, 92..93, 94..95, 96..97, 98..99,
+            100..101, 102..103, 104..105, 106..107, 108..109, 110..111, 112..113,
+            114..115, 116..117, 118..119, 120..121, 122..123, 124..125, 126..=126; 128
+        );
+        assert_eq!(map, Bitmap::<16>::from_fn(|i| i % 2 == 0));
+    }
+
+    #[test]
+    fn batch_op() {
+        let mut map = newmap!(;24);
+        map.set_many(&[0, 9, 9, 23, 0]);
+        assert_eq!(map.count(), 3);
+        assert_eq!(
+            &map.range_to_string(0, 24).unwrap(),
+            "10000000 00000010 00000001"
+        );
+        map.reset_many(&[9, 23, 23]);
+        assert_eq!(map.count(), 1);
+        assert_eq!(map.test(0), true);
+        map.set_many(&[]).reset_many(&[]);
+        assert_eq!(map.count(), 1);
+
+        let mut map = newmap!(0b_0001; 16);
+        let before: [u8; 2] = map.clone().into();
+        let res = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            map.set_many(&[1, 2, 16]);
+        }));
+        assert!(res.is_err());
+        assert_eq!(Into::<[u8; 2]>::into(map.clone()), before);
+        let res = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            map.reset_many(&[0, 100]);
+        }));
+        assert!(res.is_err());
+        assert_eq!(Into::<[u8; 2]>::into(map), before);
+
+        let map = he_lang!(2 | 5 | 11; 16);
+        assert_eq!(map.test_all_of(&[2, 5, 11]), true);
+        assert_eq!(map.test_all_of(&[2, 5, 10]), false);
+        assert_eq!(map.test_all_of(&[]), true);
+        assert_eq!(map.test_any_of(&[0, 1, 11]), true);
+        assert_eq!(map.test_any_of(&[0, 1, 10]), false);
+        assert_eq!(map.test_any_of(&[]), false);
+        // Early exit: the out-of-range index is never reached.
+        assert_eq!(map.test_all_of(&[0, 100]), false);
+        assert_eq!(map.test_any_of(&[2, 100]), true);
+    }
+
+    #[test]
+    fn swap() {
+        // Same byte.
+        let mut map = newmap!(0b_0000_0010; 16);
+        map.swap(1, 6);
+        assert_eq!(&map.range_to_string(0, 8).unwrap(), "01000000");
+        map.swap(6, 6);
+        assert_eq!(&map.range_to_string(0, 8).unwrap(), "01000000");
+        // Equal bits in the same byte stay put.
+        map.set(2);
+        map.swap(2, 6);
+        assert_eq!(&map.range_to_string(0, 8).unwrap(), "01000100");
+
+        // Across bytes.
+        map.swap(6, 13);
+        assert_eq!(map.test(6), false);
+        assert_eq!(map.test(13), true);
+        map.swap(13, 2);
+        assert_eq!(map.test(2), true);
+        assert_eq!(map.test(13), true);
+        map.swap(15, 2);
+        assert_eq!(map.test(2), false);
+        assert_eq!(map.test(15), true);
+        assert_eq!(map.count(), 2);
+    }
+
+    #[test]
+    fn reverse_bytes() {
+        let mut map: Bitmap<4> = 0x_12_34_56_78_u32.into();
+        map.reverse_bytes();
+        assert_eq!(Into::<[u8; 4]>::into(map.clone()), [0x12, 0x34, 0x56, 0x78]);
+
+        // Odd length: the middle byte is untouched.
+        let mut map: Bitmap<5> = [1u8, 2, 3, 4, 5].into();
+        map.reverse_bytes();
+        assert_eq!(Into::<[u8; 5]>::into(map.clone()), [5u8, 4, 3, 2, 1]);
+
+        // A full bit reversal is reverse_bytes() plus a per-byte reverse_bits().
+        let origin = map.clone();
+        let mut naive = Bitmap::<5>::new();
+        for i in 0..40 {
+            if origin.test(i) {
+                naive.set(39 - i);
+            }
+        }
+        map.reverse_bytes();
+        let arr: [u8; 5] = map.into();
+        let map = Bitmap::<5>::from(arr.map(u8::reverse_bits));
+        assert_eq!(Into::<[u8; 5]>::into(map), Into::<[u8; 5]>::into(naive));
+    }
+
+    #[test]
+    fn rotate() {
+        fn naive(map: &Bitmap<5>, n: isize) -> Bitmap<5> {
+            let mut res = Bitmap::<5>::new();
+            for i in 0..40 {
+                if map.test(i) {
+                    res.set((i as isize + n).rem_euclid(40) as usize);
+                }
+            }
+            res
+        }
+
+        let origin: Bitmap<5> = 0x_81_f0_0d_36_a5_u64.into();
+        for n in [0, 1, 3, 7, 8, 9, 15, 16, 23, 31, 39, 40, 41, 77, 1000] {
+            let mut map = origin.clone();
+            map.rotate_left(n);
+            assert_eq!(
+                Into::<[u8; 5]>::into(map),
+                Into::<[u8; 5]>::into(naive(&origin, n as isize))
+            );
+            let mut map = origin.clone();
+            map.rotate_right(n);
+            assert_eq!(
+                Into::<[u8; 5]>::into(map),
+                Into::<[u8; 5]>::into(naive(&origin, -(n as isize)))
+            );
+        }
+
+        let mut map = origin.clone();
+        map.rotate_left(13).rotate_right(13);
+        assert_eq!(Into::<[u8; 5]>::into(map), Into::<[u8; 5]>::into(origin));
+
+        let mut map = newmap!();
+        map.rotate_left(3).rotate_right(5);
+        assert_eq!(map.bit_len(), 0);
+    }
+
+    #[test]
+    fn shift() {
+        let origin = 0x_81f0_0d36_a5c3_7e19_u64;
+        for n in [0, 1, 3, 7, 8, 9, 15, 16, 31, 33, 57, 63] {
+            let map: Bitmap<8> = origin.into();
+            assert_eq!(&(map << n) & u64::MAX, origin << n);
+            let map: Bitmap<8> = origin.into();
+            assert_eq!(&(map >> n) & u64::MAX, origin >> n);
+
+            let mut map: Bitmap<8> = origin.into();
+            map <<= n;
+            assert_eq!(&map & u64::MAX, origin << n);
+            let mut map: Bitmap<8> = origin.into();
+            map >>= n;
+            assert_eq!(&map & u64::MAX, origin >> n);
+        }
+        for n in [64, 65, 1000, usize::MAX] {
+            let map: Bitmap<8> = origin.into();
+            assert_eq!(&(map << n) & u64::MAX, 0);
+            let map: Bitmap<8> = origin.into();
+            assert_eq!(&(map >> n) & u64::MAX, 0);
+        }
+
+        let mut map = newmap!();
+        map <<= 3;
+        map >>= 3;
+        assert_eq!(map.bit_len(), 0);
+    }
+
+    #[test]
+    fn shift_carry() {
+        let origin = 0x_81f0_0d36_a5c3_7e19_u64;
+        for n in [0, 1, 3, 8, 13, 31, 32, 33, 40, 63, 64, 100] {
+            let mut wide: Bitmap<8> = origin.into();
+            wide <<= n;
+            let mut lo: Bitmap<4> = (origin as u32).into();
+            let mut hi: Bitmap<4> = ((origin >> 32) as u32).into();
+            let carry = lo.shl_carry(n);
+            hi <<= n;
+            hi |= Into::<[u8; 4]>::into(carry);
+            assert_eq!(&lo & u32::MAX, &wide & u32::MAX);
+            assert_eq!(&hi & u32::MAX, ((&wide & u64::MAX) >> 32) as u32);
+
+            let mut wide: Bitmap<8> = origin.into();
+            wide >>= n;
+            let mut lo: Bitmap<4> = (origin as u32).into();
+            let mut hi: Bitmap<4> = ((origin >> 32) as u32).into();
+            let carry = hi.shr_carry(n);
+            lo >>= n;
+            lo |= Into::<[u8; 4]>::into(carry);
+            assert_eq!(&lo & u32::MAX, &wide & u32::MAX);
+            assert_eq!(&hi & u32::MAX, ((&wide & u64::MAX) >> 32) as u32);
+        }
+    }
+
+    #[test]
+    fn checked_shift() {
+        let mut map = he_lang!(3 | 20; 24);
+        // The highest '1' lands exactly on the last index.
+        assert!(map.checked_shl(3).is_some());
+        assert_eq!(map.test(23), true);
+        assert_eq!(map.test(6), true);
+        assert_eq!(map.count(), 2);
+        // Any further shift loses it, and the map stays untouched.
+        let before: [u8; 3] = map.clone().into();
+        assert!(map.checked_shl(1).is_none());
+        assert!(map.checked_shl(100).is_none());
+        assert_eq!(Into::<[u8; 3]>::into(map.clone()), before);
+
+        // The lowest '1' lands exactly on index 0.
+        assert!(map.checked_shr(6).is_some());
+        assert_eq!(map.test(0), true);
+        assert_eq!(map.test(17), true);
+        let before: [u8; 3] = map.clone().into();
+        assert!(map.checked_shr(1).is_none());
+        assert_eq!(Into::<[u8; 3]>::into(map.clone()), before);
+
+        // Nothing to lose on an empty map, even past the length.
+        let mut map = newmap!(;24);
+        assert!(map.checked_shl(100).is_some());
+        assert!(map.checked_shr(100).is_some());
+        assert!(map.checked_shl(0).is_some());
+    }
+
+    #[test]
+    fn map_op() {
+        let a: Bitmap<3> = 0x_f0_5a_33_u32.into();
+        let b: Bitmap<3> = 0x_0f_c3_55_u32.into();
+        let c: Bitmap<3> = 0x_99_66_0f_u32.into();
+        let arr = |map: Bitmap<3>| -> [u8; 3] { map.into() };
+
+        assert_eq!(arr(&a | &b), [0x77, 0xdb, 0xff]);
+        assert_eq!(arr(&a & &b), [0x11, 0x42, 0x00]);
+        assert_eq!(arr(&a ^ &b), [0x66, 0x99, 0xff]);
+
+        // Commutativity.
+        assert_eq!(arr(&a | &b), arr(&b | &a));
+        assert_eq!(arr(&a & &b), arr(&b & &a));
+        assert_eq!(arr(&a ^ &b), arr(&b ^ &a));
+        // Associativity.
+        assert_eq!(arr(&(&a | &b) | &c), arr(&a | &(&b | &c)));
+        assert_eq!(arr(&(&a & &b) & &c), arr(&a & &(&b & &c)));
+        assert_eq!(arr(&(&a ^ &b) ^ &c), arr(&a ^ &(&b ^ &c)));
+
+        // Inputs are not mutated.
+        assert_eq!(arr(a.clone()), [0x33, 0x5a, 0xf0]);
+        assert_eq!(arr(b.clone()), [0x55, 0xc3, 0x0f]);
+
+        // Owned operands.
+        assert_eq!(arr(a.clone() | b.clone()), arr(&a | &b));
+        assert_eq!(arr(a.clone() & &b), arr(&a & &b));
+        assert_eq!(arr(&a ^ b.clone()), arr(&a ^ &b));
+    }
+
+    #[test]
+    fn set_method() {
+        let a: Bitmap<3> = 0x_f0_5a_33_u32.into();
+        let b: Bitmap<3> = 0x_0f_c3_55_u32.into();
+        let arr = |map: Bitmap<3>| -> [u8; 3] { map.into() };
+
+        assert_eq!(arr(a.union(&b)), arr(&a | &b));
+        assert_eq!(arr(a.intersection(&b)), arr(&a & &b));
+        assert_eq!(arr(a.symmetric_difference(&b)), arr(&a ^ &b));
+        let mut not_b = b.clone();
+        not_b.flip_all();
+        assert_eq!(arr(a.difference(&b)), arr(&a & &not_b));
+
+        let mut map = a.clone();
+        map.union_with(&b);
+        assert_eq!(arr(map), arr(&a | &b));
+        let mut map = a.clone();
+        map.intersection_with(&b);
+        assert_eq!(arr(map), arr(&a & &b));
+        let mut map = a.clone();
+        map.symmetric_difference_with(&b);
+        assert_eq!(arr(map), arr(&a ^ &b));
+        let mut map = a.clone();
+        map.difference_with(&b);
+        assert_eq!(arr(map), arr(&a & &not_b));
+    }
+
+    #[test]
+    fn subset() {
+        let a: Bitmap<4> = 0x_8000_0103_u32.into();
+        let b: Bitmap<4> = 0x_8000_0f13_u32.into();
+        assert_eq!(a.is_subset(&b), true);
+        assert_eq!(b.is_superset(&a), true);
+        assert_eq!(b.is_subset(&a), false);
+        assert_eq!(a.is_superset(&b), false);
+
+        // Reflexive.
+        assert_eq!(a.is_subset(&a), true);
+        assert_eq!(a.is_superset(&a), true);
+
+        // The empty map.
+        let empty = Bitmap::<4>::new();
+        assert_eq!(empty.is_subset(&a), true);
+        assert_eq!(empty.is_subset(&empty), true);
+        assert_eq!(a.is_superset(&empty), true);
+        assert_eq!(a.is_subset(&empty), false);
+
+        // A single differing bit in the last byte.
+        let mut c = b.clone();
+        c.reset(31);
+        assert_eq!(a.is_subset(&c), false);
+        assert_eq!(c.is_superset(&a), false);
+        assert_eq!(c.is_subset(&b), true);
+    }
+
+    #[test]
+    fn disjoint() {
+        let a = he_lang!(0 | 9 | 17; 32);
+        let b = he_lang!(1 | 10 | 18; 32);
+        assert_eq!(a.is_disjoint(&b), true);
+        assert_eq!(b.is_disjoint(&a), true);
+
+        // Overlapping in exactly one bit.
+        let c = he_lang!(1 | 9 | 30; 32);
+        assert_eq!(a.is_disjoint(&c), false);
+        assert_eq!(c.is_disjoint(&a), false);
+
+        // Overlapping only in the last byte.
+        let d = he_lang!(2 | 31; 32);
+        let e = he_lang!(3 | 31; 32);
+        assert_eq!(d.is_disjoint(&e), false);
+
+        // Two empty maps.
+        let empty = newmap!(;32);
+        assert_eq!(empty.is_disjoint(&newmap!(;32)), true);
+        assert_eq!(empty.is_disjoint(&a), true);
+    }
+
+    #[test]
+    fn hamming_distance() {
+        let a: Bitmap<5> = 0x_81_f0_0d_36_a5_u64.into();
+        assert_eq!(a.hamming_distance(&a.clone()), 0);
+
+        let mut not_a = a.clone();
+        not_a.flip_all();
+        assert_eq!(a.hamming_distance(&not_a), a.bit_len());
+
+        let b: Bitmap<5> = 0x_07_0f_f0_00_a4_u64.into();
+        let mut naive = 0;
+        for i in 0..40 {
+            if a.test(i) != b.test(i) {
+                naive += 1;
+            }
+        }
+        assert_eq!(a.hamming_distance(&b), naive);
+        assert_eq!(b.hamming_distance(&a), naive);
+    }
+
+    #[test]
+    fn map_op_assign() {
+        let mut acc = Bitmap::<64>::new();
+        let mut reference = Bitmap::<64>::new();
+        for k in 0..12 {
+            let mut mask = Bitmap::<64>::new();
+            for i in [k, k * 7 + 3, k * 41 + 10, 511 - k] {
+                mask.set(i);
+                reference.set(i);
+            }
+            acc |= &mask;
+        }
+        assert_eq!(Into::<[u8; 64]>::into(acc.clone()), Into::<[u8; 64]>::into(reference));
+
+        let mut mask = Bitmap::<64>::new();
+        mask.set_many(&[0, 10, 511, 200]);
+        let mut map = acc.clone();
+        map &= &mask;
+        assert_eq!(map.count(), 3);
+        assert_eq!(map.test_all_of(&[0, 10, 511]), true);
+        map ^= &mask;
+        assert_eq!(map.count(), 1);
+        assert_eq!(map.test(200), true);
+
+        let mut big = Box::new(Bitmap::<{ 64 * 1024 }>::new());
+        let mut other = Box::new(Bitmap::<{ 64 * 1024 }>::new());
+        other.set(64 * 1024 * 8 - 1);
+        *big |= &*other;
+        *big ^= &*other;
+        *big &= &*other;
+        assert_eq!(big.none(), true);
+    }
+
+    #[test]
+    fn concat() {
+        let header: Bitmap<4> = 0x_8000_0001_u32.into();
+        let body = he_lang!(0 | 13 | 95; 96);
+        let map = header.concat::<16, 12>(&body);
+        for i in 0..96 {
+            assert_eq!(map.test(32 + i), body.test(i));
+        }
+        for i in 0..32 {
+            assert_eq!(map.test(i), header.test(i));
+        }
+        assert_eq!(map, he_lang!(0 | 31 | 32 | 45 | 127; 128));
+
+        let map = newmap!().concat::<1, 1>(&newmap!(0b_1; 8));
+        assert_eq!(map, newmap!(0b_1; 8));
+    }
+
+    #[test]
+    fn split_at_byte() {
+        let value = 0x_8000_1234_5678_9abc_def0_0000_0000_0001_u128;
+        let map: Bitmap<16> = value.into();
+        let (header, body) = map.split_at_byte::<4, 12>();
+        assert_eq!(header, Bitmap::<4>::from(1u32));
+        assert_eq!(body, Bitmap::<12>::from(value >> 32));
+        assert_eq!(header.concat::<16, 12>(&body), map);
+
+        let (empty, all) = map.split_at_byte::<0, 16>();
+        assert_eq!(empty.bit_len(), 0);
+        assert_eq!(all, map);
+    }
+
+    #[test]
+    fn resize() {
+        let map = he_lang!(0 | 15; 16);
+        let large = map.resize::<8>();
+        assert_eq!(large.bit_len(), 64);
+        assert_eq!(large.count(), 2);
+        assert_eq!(large.resize::<2>(), map);
+        assert_eq!(large.checked_resize::<2>(), Some(map.clone()));
+
+        // Losing the bit 15.
+        assert_eq!(map.resize::<1>(), he_lang!(0; 8));
+        assert!(map.checked_resize::<1>().is_none());
+        assert!(large.checked_resize::<1>().is_none());
+        assert!(newmap!(0b_1; 16).checked_resize::<1>().is_some());
+        assert!(map.checked_resize::<0>().is_none());
+        assert!(newmap!(;16).checked_resize::<0>().is_some());
+    }
+
+    #[test]
+    fn copy_bits_from() {
+        let value: u64 = 0x_dead_beef_1234_5678;
+        let src = Bitmap::<8>::from(value);
+        // Different sub-byte alignments, crossing several bytes.
+        for (src_start, dst_start, len) in [(0, 0, 64), (3, 5, 40), (7, 1, 33), (12, 4, 1), (9, 0, 0)] {
+            let mut dst = newmap!(0xff; 80);
+            dst.copy_bits_from(&src, src_start, dst_start, len);
+            for i in 0..80 {
+                let expected = match i >= dst_start && i < dst_start + len {
+                    true => src.test(src_start + i - dst_start),
+                    false => i < 8,
+                };
+                assert_eq!(dst.test(i), expected);
+            }
+        }
+
+        let mut dst = Bitmap::<2>::new();
+        dst.copy_bits_from(&src, 60, 12, 4)
+            .copy_bits_from(&src, 0, 0, 4);
+        assert_eq!(dst, he_lang!(3 | 12 | 14 | 15; 16));
+    }
+
+    #[test]
+    fn fill_suffix() {
+        let mut map = newmap!(;48);
+        map.fill_prefix([0x55u8; 2]).fill_suffix([0xAAu8; 2]);
+        assert_eq!(map, Bitmap::<6>::from([0x55, 0x55, 0, 0, 0xAA, 0xAA]));
+        map.fill_suffix(0x1234u16);
+        assert_eq!(map.count(), 8 + 2 + 3);
+        assert_eq!(map.test(0), true);
+        assert_eq!(map.test(31), false);
+
+        // A longer value drops its first bytes.
+        let mut map = newmap!(;16);
+        map.fill_suffix([1u8, 2, 3]);
+        assert_eq!(map, Bitmap::<2>::from([2, 3]));
+        map.fill_prefix([4u8, 5, 6]);
+        assert_eq!(map, Bitmap::<2>::from([4, 5]));
+    }
+
+    #[test]
+    fn fill_at() {
+        let mut map = newmap!(;64);
+        map.fill_at(5, 0x_ffff_u16);
+        assert_eq!(map.count(), 16);
+        assert_eq!(map.find_first_one(), Some(40));
+        // Landing exactly at the end.
+        map.fill_at(7, [0x80u8]);
+        assert_eq!(map.count(), 17);
+        assert_eq!(map.test(63), true);
+
+        let mut other = newmap!(;64);
+        other.fill_at(0, [1u8, 2, 3]);
+        let mut prefix = newmap!(;64);
+        prefix.fill_prefix([1u8, 2, 3]);
+        assert_eq!(other, prefix);
+    }
+
+    #[test]
+    fn get_set_byte() {
+        let mut map = newmap!(;32);
+        map.set_byte(2, 0b_1010_0110).set_byte(0, 0xff);
+        for bit in 0..8 {
+            assert_eq!(map.get_bool(16 + bit), (0b_1010_0110 >> bit) & 1 == 1);
+        }
+        assert_eq!(map.get_byte(0), 0xff);
+        assert_eq!(map.get_byte(2), 0b_1010_0110);
+        assert_eq!(map.count(), 12);
+
+        assert_eq!(map.try_get_byte(3), Some(0));
+        assert_eq!(map.try_get_byte(4), None);
+        assert!(map.try_set_byte(3, 1).is_some());
+        assert_eq!(map.test(24), true);
+        assert!(map.try_set_byte(4, 1).is_none());
+        assert_eq!(map.count(), 13);
+    }
+
+    #[test]
+    fn get_bits() {
+        let value: u128 = 0x_0123_4567_89ab_cdef_fedc_ba98_7654_3210;
+        let map = Bitmap::<16>::from(value);
+        // Byte-aligned fields.
+        assert_eq!(map.get_bits(0, 8), 0x10);
+        assert_eq!(map.get_bits(64, 64), 0x_0123_4567_89ab_cdef);
+        // Crossing one boundary.
+        assert_eq!(map.get_bits(4, 8), 0x21);
+        assert_eq!(map.get_bits(13, 5), ((value >> 13) & 0x1f) as u64);
+        // Crossing two boundaries, and a 64-bit field over 9 bytes.
+        assert_eq!(map.get_bits(7, 10), ((value >> 7) & 0x3ff) as u64);
+        assert_eq!(map.get_bits(3, 64), (value >> 3) as u64);
+        assert_eq!(map.get_bits(127, 1), 0);
+        assert_eq!(map.get_bits(128, 0), 0);
+    }
+
+    #[test]
+    fn set_bits() {
+        // Neighbors set on both sides of an unaligned field.
+        let mut map = newmap!(;32);
+        map.set_all();
+        map.set_bits(5, 13, 0);
+        assert_eq!(map.get_bits(0, 5), 0b_11111);
+        assert_eq!(map.get_bits(5, 13), 0);
+        assert_eq!(map.get_bits(18, 14), 0x3fff);
+        map.set_bits(5, 13, u64::MAX);
+        assert_eq!(map.all(), true);
+
+        // Round trip over pseudo-random fields.
+        let mut seed = 0x_9e37_79b9_7f4a_7c15_u64;
+        let mut next = || {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            seed
+        };
+        let mut map = newmap!(;160);
+        for _ in 0..1000 {
+            let width = (next() % 65) as usize;
+            let offset = (next() % (160 - width as u64 + 1)) as usize;
+            let value = next();
+            let before = map.clone();
+            map.set_bits(offset, width, value);
+            let mask = match width {
+                64 => u64::MAX,
+                w => (1 << w) - 1,
+            };
+            assert_eq!(map.get_bits(offset, width), value & mask);
+            for i in (0..offset).chain(offset + width..160) {
+                assert_eq!(map.test(i), before.test(i));
+            }
+        }
+
+        let mut map = newmap!(;16);
+        assert!(map.checked_set_bits(0, 4, 0x10).is_none());
+        assert!(map.checked_set_bits(0, 64, u64::MAX).is_none());
+        assert!(map.checked_set_bits(12, 4, 0xf).is_some());
+        assert_eq!(map.count(), 4);
+    }
+
+    #[test]
+    fn little_endian() {
+        let map = Bitmap::<4>::from(1u32);
+        assert_eq!(map.find_first_one(), Some(0));
+        let map = Bitmap::<4>::from(0x_0102_0304_u32);
+        assert_eq!(Into::<[u8; 4]>::into(map.clone()), [4, 3, 2, 1]);
+        assert_eq!(Bitmap::<4>::from_be(0x_0102_0304_u32.to_be_bytes()), map);
+
+        let mut map = newmap!(;32);
+        map.fill_prefix(1u16 << 9);
+        assert_eq!(map.find_first_one(), Some(9));
+        map.reset_all().fill_prefix_be((1u16 << 9).to_be_bytes());
+        assert_eq!(map.find_first_one(), Some(9));
+
+        let map = newmap!(0x_ff00_u16; 16);
+        assert_eq!(&map & 0x_0ff0_u16, 0x_0f00);
+        let mut map = newmap!(;16);
+        map |= 1u16 << 12;
+        assert_eq!(map.test(12), true);
+        map &= 0x_00ff_u16;
+        assert_eq!(map.any(), false);
+    }
+
+    #[test]
+    fn run_length_encoding() {
+        assert_eq!(newmap!().to_rle(), Vec::<u8>::new());
+        assert_eq!(Bitmap::<0>::from_rle(&[]), Ok(newmap!()));
+        assert_eq!(newmap!(;16).to_rle(), [16]);
+        assert_eq!(Bitmap::<2>::from_rle(&[16]), Ok(newmap!(;16)));
+
+        // 1M bits in 3 bytes.
+        let full = Bitmap::<{ 1 << 17 }>::full();
+        let rle = full.to_rle();
+        assert_eq!(rle, [0, 0x80, 0x80, 0x40]);
+        assert_eq!(Bitmap::<{ 1 << 17 }>::from_rle(&rle).unwrap(), full);
+
+        let map = he_lang!(500; 1000);
+        let rle = map.to_rle();
+        assert_eq!(rle, [0xf4, 0x03, 1, 0xf3, 0x03]);
+        assert_eq!(Bitmap::<125>::from_rle(&rle), Ok(map));
+
+        // Alternating bits take a byte per bit.
+        let map = Bitmap::<16>::from_fn(|i| i % 2 == 1);
+        let rle = map.to_rle();
+        assert_eq!(rle.len(), 128);
+        assert_eq!(rle.iter().all(|&run| run == 1), true);
+        assert_eq!(Bitmap::<16>::from_rle(&rle), Ok(map));
+        let map = Bitmap::<16>::from_fn(|i| i % 2 == 0);
+        let rle = map.to_rle();
+        assert_eq!(rle.len(), 129);
+        assert_eq!(rle[0], 0);
+        assert_eq!(Bitmap::<16>::from_rle(&rle), Ok(map));
+
+        // Round trip over pseudo-random maps of various densities.
+        let mut seed = 0x_2545_f491_4f6c_dd1d_u64;
+        let mut next = || {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            seed
+        };
+        for density in [1, 2, 8, 32, 255] {
+            let map = Bitmap::<64>::from_fn(|_| next() % 256 < density);
+            assert_eq!(Bitmap::<64>::from_rle(&map.to_rle()), Ok(map));
+        }
+
+        // Runs adding up to the wrong length.
+        assert_eq!(
+            Bitmap::<2>::from_rle(&[8, 9]),
+            Err(BitmapError::LengthMismatch { expected: 16, found: 17 })
+        );
+        assert_eq!(
+            Bitmap::<2>::from_rle(&[]),
+            Err(BitmapError::LengthMismatch { expected: 16, found: 0 })
+        );
+        #[cfg(target_pointer_width = "64")]
+        assert_eq!(
+            Bitmap::<2>::from_rle(&[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01, 16]),
+            Err(BitmapError::LengthMismatch {
+                expected: 16,
+                found: usize::MAX
+            })
+        );
+        // Cut off and overflowing run lengths.
+        assert_eq!(
+            Bitmap::<2>::from_rle(&[4, 0x8c]),
+            Err(BitmapError::InvalidEncoding { index: 1 })
+        );
+        assert_eq!(
+            Bitmap::<2>::from_rle(&[0x80; 11]),
+            Err(BitmapError::InvalidEncoding { index: 0 })
+        );
+        // Encodings other than the one given by `to_rle`.
+        assert_eq!(
+            Bitmap::<2>::from_rle(&[0x90, 0x00]),
+            Err(BitmapError::InvalidEncoding { index: 0 })
+        );
+        assert_eq!(
+            Bitmap::<2>::from_rle(&[4, 0x82, 0x00, 10]),
+            Err(BitmapError::InvalidEncoding { index: 1 })
+        );
+        assert_eq!(
+            Bitmap::<2>::from_rle(&[4, 0, 12]),
+            Err(BitmapError::InvalidEncoding { index: 1 })
+        );
+        assert_eq!(
+            Bitmap::<2>::from_rle(&[16, 0]),
+            Err(BitmapError::InvalidEncoding { index: 1 })
+        );
+        assert_eq!(
+            Bitmap::<0>::from_rle(&[0]),
+            Err(BitmapError::InvalidEncoding { index: 0 })
+        );
+        assert_eq!(Bitmap::<2>::from_rle(&[0, 16]), Ok(Bitmap::<2>::full()));
+    }
+
+    #[test]
+    fn base64() {
+        // The vectors of RFC 4648.
+        assert_eq!(newmap!().to_base64(), "");
+        assert_eq!(Bitmap::from_array(*b"f").to_base64(), "Zg==");
+        assert_eq!(Bitmap::from_array(*b"fo").to_base64(), "Zm8=");
+        assert_eq!(Bitmap::from_array(*b"foo").to_base64(), "Zm9v");
+        assert_eq!(Bitmap::from_array(*b"foob").to_base64(), "Zm9vYg==");
+        assert_eq!(Bitmap::from_array(*b"fooba").to_base64(), "Zm9vYmE=");
+        assert_eq!(Bitmap::from_array(*b"foobar").to_base64(), "Zm9vYmFy");
+        assert_eq!(Bitmap::<4>::from_base64("Zm9vYg=="), Ok(Bitmap::from_array(*b"foob")));
+        assert_eq!(Bitmap::<5>::from_base64("Zm9vYmE="), Ok(Bitmap::from_array(*b"fooba")));
+        assert_eq!(Bitmap::<0>::from_base64(""), Ok(newmap!()));
+
+        // Round trip over all the digits, and each length of the last group.
+        let map = Bitmap::<48>::from_fn(|i| i % 3 == 0 || i % 7 == 2);
+        assert_eq!(Bitmap::<48>::from_base64(&map.to_base64()), Ok(map));
+        let map = Bitmap::<49>::from_fn(|i| i % 5 != 4);
+        assert_eq!(Bitmap::<49>::from_base64(&map.to_base64()), Ok(map));
+        let map = Bitmap::<50>::full();
+        assert_eq!(map.to_base64().len(), 68);
+        assert_eq!(Bitmap::<50>::from_base64(&map.to_base64()), Ok(map));
+        let digits = Bitmap::from_array([0x00, 0x10, 0x83]).to_base64();
+        assert_eq!(digits, "ABCD");
+        let map = Bitmap::<192>::from_fn(|i| i / 8 % 2 == 0);
+        assert_eq!(Bitmap::<192>::from_base64(&map.to_base64()), Ok(map));
+
+        // Invalid characters.
+        assert_eq!(
+            Bitmap::<3>::from_base64("Zm-v"),
+            Err(BitmapError::InvalidDigit { found: '-', index: 2 })
+        );
+        assert_eq!(
+            Bitmap::<3>::from_base64("Zm9v\n"),
+            Err(BitmapError::InvalidDigit { found: '\n', index: 4 })
+        );
+        assert_eq!(
+            Bitmap::<3>::from_base64("Zmé"),
+            Err(BitmapError::InvalidDigit { found: 'é', index: 2 })
+        );
+        assert_eq!(
+            Bitmap::<1>::from_base64("Z=g="),
+            Err(BitmapError::InvalidDigit { found: '=', index: 1 })
+        );
+        assert_eq!(
+            Bitmap::<1>::from_base64("Zg==="),
+            Err(BitmapError::InvalidDigit { found: '=', index: 2 })
+        );
+
+        // Wrong lengths, missing padding and stray bits.
+        assert_eq!(
+            Bitmap::<2>::from_base64("Zm9v"),
+            Err(BitmapError::LengthMismatch { expected: 2, found: 3 })
+        );
+        assert_eq!(
+            Bitmap::<4>::from_base64("Zm9v"),
+            Err(BitmapError::LengthMismatch { expected: 4, found: 3 })
+        );
+        assert_eq!(
+            Bitmap::<0>::from_base64("===="),
+            Err(BitmapError::InvalidDigit { found: '=', index: 0 })
+        );
+        assert_eq!(
+            Bitmap::<1>::from_base64("Zg"),
+            Err(BitmapError::InvalidEncoding { index: 0 })
+        );
+        assert_eq!(
+            Bitmap::<4>::from_base64("Zm9vYg="),
+            Err(BitmapError::InvalidEncoding { index: 4 })
+        );
+        assert_eq!(
+            Bitmap::<1>::from_base64("Zh=="),
+            Err(BitmapError::InvalidEncoding { index: 1 })
+        );
+        assert_eq!(
+            Bitmap::<2>::from_base64("Zm9="),
+            Err(BitmapError::InvalidEncoding { index: 2 })
+        );
+    }
+
+    #[test]
+    fn explicit_endianness() {
+        fn check<const BYTES: usize>(map: Bitmap<BYTES>) {
+            let le = map.to_le_bytes();
+            let mut be = map.to_be_bytes();
+            assert_eq!(Bitmap::from_le_bytes(le), map);
+            assert_eq!(Bitmap::from_be_bytes(be), map);
+            be.reverse();
+            assert_eq!(be, le);
+        }
+        check(newmap!());
+        check(he_lang!(3; 8));
+        check(Bitmap::<5>::from(0x_0001_0203_0405_u64));
+        check(Bitmap::<33>::from_fn(|i| i % 5 == 1));
+
+        let map = Bitmap::<4>::from(0x_0102_0304_u32);
+        assert_eq!(map.to_le_bytes(), 0x_0102_0304_u32.to_le_bytes());
+        assert_eq!(map.to_be_bytes(), 0x_0102_0304_u32.to_be_bytes());
+        assert_eq!(Bitmap::from_be_bytes([1, 2, 3, 4]), map);
+        const MAP: Bitmap<3> = Bitmap::from_be_bytes([0, 0, 0b_10]);
+        assert_eq!(MAP.find_first_one(), Some(1));
+    }
+
+    #[test]
+    fn char_as_u32() {
+        // A surrogate can be the AND of a bitmap and a valid char, so
+        // it must stay a `u32`.
+        let map = Bitmap::<4>::from(0x_d800_u32);
+        let res: u32 = &map & u32::from('\u{ffff}');
+        assert_eq!(res, 0x_d800);
+        assert_eq!(char::from_u32(res), None);
+
+        let c = '\u{10ffff}';
+        assert_eq!(Bitmap::<4>::from(c), Bitmap::<4>::from(u32::from(c)));
+        let mut map = newmap!(;32);
+        map.fill_prefix(c);
+        assert_eq!(map, Bitmap::<4>::from(0x_0010_ffff_u32));
+        map &= 'A';
+        assert_eq!(map, Bitmap::<4>::from(u32::from('A')));
+    }
+
+    #[test]
+    fn try_from_slice() {
+        let bytes: &[u8] = &[0xff, 0x01, 0x80];
+        let map = Bitmap::<4>::try_from(&bytes[..2]).unwrap();
+        assert_eq!(map, he_lang!(0 | 1 | 2 | 3 | 4 | 5 | 6 | 7 | 8; 32));
+        let map = Bitmap::<3>::try_from(bytes).unwrap();
+        assert_eq!(map.count(), 10);
+        assert_eq!(map.test(23), true);
+        assert_eq!(
+            Bitmap::<2>::try_from(bytes),
+            Err(BitmapError::LengthMismatch { expected: 2, found: 3 })
+        );
+        assert_eq!(Bitmap::<0>::try_from(&bytes[..0]), Ok(newmap!()));
+
+        assert_eq!(Bitmap::<3>::from_slice_exact(bytes), Ok(map));
+        assert!(Bitmap::<4>::from_slice_exact(bytes).is_err());
+        assert!(Bitmap::<2>::from_slice_exact(bytes).is_err());
+    }
+
+    #[test]
+    fn from_bools() {
+        let flags = [true, false, false, true, false, false, false, false, true];
+        let map = Bitmap::<2>::try_from(&flags[..]).unwrap();
+        assert_eq!(map.count(), 3);
+        // The 9th bool is the bit 0 of the second byte.
+        assert_eq!(map.test(8), true);
+        assert_eq!(Into::<[u8; 2]>::into(map.clone()), [0b_1001, 0b_1]);
+        assert_eq!(Bitmap::<2>::from(flags), map);
+
+        assert!(Bitmap::<1>::try_from(&flags[..]).is_err());
+        assert_eq!(Bitmap::<1>::try_from(&flags[..0]), Ok(newmap!(;8)));
+    }
+
+    #[test]
+    fn to_bools() {
+        let flags = [true, false, false, true, false, false, false, false, true];
+        let map = Bitmap::<2>::try_from(&flags[..]).unwrap();
+        let back = map.to_bool_vec();
+        assert_eq!(back.len(), 16);
+        assert_eq!(back[..9], flags);
+        assert_eq!(back[9..], [false; 7]);
+        assert_eq!(Bitmap::<2>::try_from(&back[..]), Ok(map.clone()));
+
+        let arr: [bool; 16] = map.to_bool_array();
+        assert_eq!(arr[..], back[..]);
+        // `vec![]` is ambiguous when the `bitvec` feature is enabled.
+        assert_eq!(Bitmap::<0>::new().to_bool_vec(), Vec::<bool>::new());
+    }
+
+    #[test]
+    fn as_ref_slice() {
+        use std::borrow::Borrow;
+
+        fn sum<T: AsRef<[u8]>>(bytes: T) -> u32 {
+            bytes.as_ref().iter().map(|&b| b as u32).sum()
+        }
+        fn clear<T: AsMut<[u8]>>(mut bytes: T) -> T {
+            bytes.as_mut().fill(0);
+            bytes
+        }
+
+        let map = Bitmap::<2>::from([1u8, 2]);
+        assert_eq!(sum(&map), 3);
+        assert_eq!(sum(map.clone()), 3);
+        assert_eq!(Borrow::<[u8]>::borrow(&map), &[1u8, 2][..]);
+        assert_eq!(map.as_array(), &[1, 2]);
+        assert_eq!(clear(map).none(), true);
+    }
+
+    #[test]
+    fn from_fn() {
+        let mut calls = vec![];
+        let map = Bitmap::<3>::from_fn(|i| {
+            calls.push(i);
+            i % 3 == 0
+        });
+        assert_eq!(calls, (0..24).collect::<Vec<_>>());
+
+        let mut expected = Bitmap::<3>::new();
+        for i in (0..24).step_by(3) {
+            expected.set(i);
+        }
+        assert_eq!(map, expected);
+    }
+
+    #[test]
+    fn splat_repeat() {
+        assert_eq!(Bitmap::<3>::splat(true).all(), true);
+        assert_eq!(Bitmap::<3>::splat(false).none(), true);
+        assert_eq!(Bitmap::<0>::splat(true), Bitmap::<0>::new());
+
+        let map = Bitmap::<3>::repeat_byte(0xAA);
+        assert_eq!(map.count(), 12);
+        for (i, v) in [(0, false), (1, true), (7, true), (8, false), (9, true), (22, false), (23, true)] {
+            assert_eq!(map.test(i), v);
+        }
+    }
+
+    #[test]
+    fn zero_full_consts() {
+        static TABLE: [Bitmap<2>; 3] = [Bitmap::ZERO, Bitmap::FULL, Bitmap::ZERO];
+        assert_eq!(TABLE[0].none(), true);
+        assert_eq!(TABLE[1].all(), true);
+        assert_eq!(Bitmap::<2>::full(), Bitmap::<2>::FULL);
+
+        let kind = |map: &Bitmap<2>| match *map {
+            Bitmap::<2>::ZERO => "empty",
+            Bitmap::<2>::FULL => "full",
+            _ => "partial",
+        };
+        assert_eq!(kind(&newmap!(;16)), "empty");
+        assert_eq!(kind(&newmap!(0x_ffff; 16)), "full");
+        assert_eq!(kind(&newmap!(0b_1; 16)), "partial");
+
+        // The empty map is both.
+        assert_eq!(Bitmap::<0>::FULL, Bitmap::<0>::ZERO);
+        assert_eq!(Bitmap::<0>::FULL.all(), true);
+    }
+
+    #[test]
+    fn init_in_place() {
+        let mut page = Box::new([0xffu8; 4096]);
+        let ptr = page.as_mut_ptr();
+        unsafe {
+            let map = Bitmap::<64>::init_zeroed_at(ptr.add(64).cast());
+            assert_eq!(map.none(), true);
+            map.set(0);
+            let map = Bitmap::<64>::init_from_at(ptr.cast(), &[0b_1, 0b_10]);
+            assert_eq!(*map, he_lang!(0 | 9; 512));
+        }
+        assert_eq!(page[..2], [0b_1, 0b_10]);
+        assert!(page[2..64].iter().all(|&b| b == 0));
+        assert_eq!(page[64], 1);
+        assert!(page[65..128].iter().all(|&b| b == 0));
+        assert!(page[128..].iter().all(|&b| b == 0xff));
+    }
+
+    #[cfg(feature = "bytemuck")]
+    #[test]
+    fn bytemuck_cast() {
+        let map = he_lang!(0 | 9 | 63; 64);
+        let bytes = bytemuck::bytes_of(&map);
+        assert_eq!(bytes, &[1, 2, 0, 0, 0, 0, 0, 0x80]);
+        assert_eq!(bytemuck::from_bytes::<Bitmap<8>>(bytes), &map);
+
+        let buf = [0xffu8; 16];
+        let maps: &[Bitmap<4>] = bytemuck::cast_slice(&buf);
+        assert_eq!(maps.len(), 4);
+        assert!(maps.iter().all(|m| m.all()));
+        assert_eq!(<Bitmap<4> as bytemuck::Zeroable>::zeroed(), Bitmap::<4>::new());
+    }
+
+    #[test]
+    fn fmt_radix() {
+        let map = Bitmap::<3>::from(0x_a5_01_f0_u32);
+        assert_eq!(format!("{map:b}"), "101001010000000111110000");
+        assert_eq!(format!("{map:#b}"), "0b101001010000000111110000");
+        assert_eq!(format!("{map:x}"), "a501f0");
+        assert_eq!(format!("{map:X}"), "A501F0");
+        assert_eq!(format!("{map:#x}"), "0xa501f0");
+        assert_eq!(format!("{map:#X}"), "0xA501F0");
+
+        // Padding like integers.
+        assert_eq!(format!("{map:010x}"), "0000a501f0");
+        assert_eq!(format!("{map:#010x}"), "0x00a501f0");
+        assert_eq!(format!("{map:>9x}"), "   a501f0");
+        assert_eq!(format!("{map:*<9x}"), "a501f0***");
+        assert_eq!(format!("{map:^10X}"), "  A501F0  ");
+        assert_eq!(format!("{map:4x}"), "a501f0");
+        assert_eq!(format!("{:x}", newmap!()), "");
+    }
+
+    #[test]
+    fn fmt_alternate() {
+        use core::fmt::Write;
+
+        let map = Bitmap::<4>::from(0x_8000_0101_u32);
+        let mut buf = Buf([0; 64], 0);
+        write!(buf, "{map}").unwrap();
+        assert_eq!(&buf.0[..buf.1], b"[32 bits] ...00000001 00000001");
+        let mut buf = Buf([0; 64], 0);
+        write!(buf, "{map:#}").unwrap();
+        assert_eq!(&buf.0[..buf.1], b"[32 bits] 10000000 00000000 00000001 00000001");
+
+        assert_eq!(format!("{:#}", newmap!(0b_11; 8)), "[8 bits] 00000011");
+        assert_eq!(format!("{:#}", newmap!()), "[0 bits] ");
+    }
+
+    #[test]
+    fn display_config() {
+        let map = Bitmap::<3>::from(0x_81_00_0f_u32);
+        assert_eq!(format!("{}", map.display()), format!("{map}"));
+        assert_eq!(
+            format!("{}", map.display().bytes_shown(usize::MAX)),
+            format!("{map:#}")
+        );
+        assert_eq!(
+            format!("{}", map.display().bytes_shown(1).group(4)),
+            "[24 bits] ...0000 1111"
+        );
+        assert_eq!(
+            format!("{}", map.display().bytes_shown(4).lsb_first(true).group(0).header(false)),
+            "111100000000000010000001"
+        );
+        assert_eq!(
+            format!("{}", map.display().lsb_first(true).group(3)),
+            "[24 bits] 111 100 000 000 000 0..."
+        );
+        assert_eq!(format!("{}", map.display().bytes_shown(0)), "[24 bits] ...");
+        assert_eq!(format!("{}", newmap!().display()), "[0 bits] ");
+    }
+
+    #[test]
+    fn range_bounds() {
+        let map = Bitmap::<2>::from(0x_f00f_u16);
+        assert_eq!(map.format_range(..), map.range_to_string(0, 16));
+        assert_eq!(map.format_range(4..), map.range_to_string(4, 16));
+        assert_eq!(map.format_range(..=11), map.range_to_string(0, 12));
+        assert_eq!(map.format_range(3..3), None);
+        assert_eq!(map.format_range(16..16), None);
+        assert_eq!(map.format_range(4..17), None);
+
+        assert_eq!(map.count_range(..), 8);
+        assert_eq!(map.count_range(4..), 4);
+        assert_eq!(map.count_range(..=11), 4);
+        assert_eq!(map.count_range(2..14), 4);
+        assert_eq!(map.count_range(3..3), 0);
+        assert_eq!(map.count_range(16..), 0);
+
+        let mut other = newmap!(;16);
+        other.set_range(..).reset_all().set_range(12..).set_range(..=3).set_range(7..7);
+        assert_eq!(other, map);
+    }
+
+    #[test]
+    fn write_range() {
+        let map = Bitmap::<4>::from(0x_8421_f00f_u32);
+        for (start, end) in [(0, 32), (0, 1), (3, 13), (7, 9), (8, 16), (15, 31), (31, 32)] {
+            let mut buf = Buf([0; 64], 0);
+            map.write_range(&mut buf, start, end).unwrap();
+            let expected = map.range_to_string(start, end).unwrap();
+            assert_eq!(&buf.0[..buf.1], expected.as_bytes());
+        }
+        for (start, end) in [(0, 33), (5, 5), (6, 5), (32, 33)] {
+            let mut buf = Buf([0; 64], 0);
+            assert!(map.write_range(&mut buf, start, end).is_err());
+            assert_eq!(buf.1, 0);
+        }
+    }
+
+    #[test]
+    fn hex_string() {
+        let map = Bitmap::<5>::from_fn(|i| i % 3 == 0);
+        let hex = map.to_hex_string();
+        assert_eq!(hex.len(), 10);
+        assert_eq!(Bitmap::<5>::from_hex_str(&hex), Ok(map));
+        assert_eq!(newmap!().to_hex_string(), "");
+        assert_eq!(Bitmap::<0>::from_hex_str("0x"), Ok(newmap!()));
+
+        let lower = Bitmap::<2>::from_hex_str("0xbeef").unwrap();
+        let mixed = Bitmap::<2>::from_hex_str("0XBe_eF").unwrap();
+        assert_eq!(lower, mixed);
+        assert_eq!(lower, Bitmap::<2>::from(0x_beef_u16));
+        assert_eq!(Bitmap::<2>::from_hex_str("fff"), Ok(Bitmap::<2>::from(0x_0fff_u16)));
+
+        assert_eq!(
+            Bitmap::<2>::from_hex_str("0xbeeg"),
+            Err(BitmapError::InvalidDigit { found: 'g', index: 5 })
+        );
+        assert_eq!(
+            Bitmap::<2>::from_hex_str("beef0"),
+            Err(BitmapError::LengthMismatch { expected: 4, found: 5 })
+        );
+        assert_eq!(
+            Bitmap::<2>::from_hex_str("ef"),
+            Err(BitmapError::LengthMismatch { expected: 4, found: 2 })
+        );
+    }
+
+    #[test]
+    fn from_str() {
+        let map = Bitmap::<3>::from_fn(|i| i % 5 == 1);
+        let string = map.range_to_string(0, 24).unwrap();
+        assert_eq!(string.parse::<Bitmap<3>>(), Ok(map.clone()));
+        let string = format!("{}", map.display().bytes_shown(3).group(4).header(false));
+        assert_eq!(string.parse::<Bitmap<3>>(), Ok(map));
+        assert_eq!("1_0000_0001".parse::<Bitmap<1>>().is_err(), true);
+        assert_eq!("".parse::<Bitmap<0>>(), Ok(newmap!()));
+        assert_eq!(" 0000\t0011\n".parse::<Bitmap<1>>(), Ok(newmap!(0b_11; 8)));
+
+        assert_eq!(
+            "0000 0011".parse::<Bitmap<2>>(),
+            Err(BitmapError::LengthMismatch { expected: 16, found: 8 })
+        );
+        assert_eq!(
+            "0000 0021".parse::<Bitmap<1>>(),
+            Err(BitmapError::InvalidDigit { found: '2', index: 7 })
+        );
+        assert!("0b00000011".parse::<Bitmap<1>>().is_err());
+    }
+
+    #[test]
+    fn range_to_string_with() {
+        let map = Bitmap::<2>::from(0x_a5_3c_u16);
+        for (start, end) in [(0, 16), (2, 11), (7, 9), (8, 16), (15, 16)] {
+            assert_eq!(
+                map.range_to_string_with(start, end, 8, ' '),
+                map.range_to_string(start, end)
+            );
+        }
+        // Nibbles across the byte edge.
+        assert_eq!(map.range_to_string_with(0, 16, 4, '_').unwrap(), "1010_0101_0011_1100");
+        assert_eq!(map.range_to_string_with(2, 11, 4, '_').unwrap(), "101_0011_11");
+        assert_eq!(map.range_to_string_with(6, 10, 4, ':').unwrap(), "01:00");
+        assert_eq!(map.range_to_string_with(0, 16, 0, '_').unwrap(), "1010010100111100");
+        assert_eq!(map.range_to_string_with(0, 16, 3, ' ').unwrap(), "1 010 010 100 111 100");
+        assert_eq!(map.range_to_string_with(4, 4, 4, '_'), None);
+    }
+
+    #[test]
+    fn bitref_fmt() {
+        let mut map = Bitmap::<64>::new();
+        map.set(10);
+        assert_eq!(format!("{}", map.at(10)), "bit[10] = 1");
+        assert_eq!(format!("{}", map.at(0)), "bit[0] = 0");
+        assert_eq!(format!("{:?}", map.at(10)), "BitRef { index: 10, value: true }");
+        {
+            let mut bitmut = map.at_mut(511);
+            assert_eq!(format!("{bitmut}"), "bit[511] = 0");
+            bitmut.flip();
+            assert_eq!(format!("{bitmut}"), "bit[511] = 1");
+            assert_eq!(format!("{bitmut:?}"), "BitRefMut { index: 511, value: true }");
+        }
+        assert_eq!(map.test(511), true);
+    }
+
+    #[test]
+    fn debug_summary() {
+        let map = Bitmap::<2>::from(0x_8001_u16);
+        assert_eq!(
+            format!("{map:?}"),
+            "Bitmap { #bytes: 2, #bits: 16, bits: \"10000000 00000001\" }"
+        );
+        let map = Bitmap::<DEBUG_SUMMARY_BYTES>::FULL;
+        let shown = format!("{map:?}");
+        assert_eq!(shown.matches("11111111").count(), DEBUG_SUMMARY_BYTES);
+        assert!(!shown.contains("#ones"));
+
+        let mut map = Bitmap::<1024>::new();
+        map.set_many(&[3, 9, 4000, 8190]);
+        let shown = format!("{map:?}");
+        assert_eq!(
+            shown,
+            "Bitmap { #bytes: 1024, #bits: 8192, #ones: 4, first_one: Some(3), \
+             last_one: Some(8190), bits: \"01000000 00000000 ... 00000010 00001000\" }"
+        );
+        assert!(format!("{:?}", Bitmap::<1024>::new()).contains("#ones: 0, first_one: None, last_one: None"));
+    }
+
+    #[test]
+    fn indices_to_string() {
+        let mut map = Bitmap::<64>::new();
+        assert_eq!(map.indices_to_string(), "{}");
+        assert_eq!(format!("{}", map.display_ones()), "{}");
+        map.set(200);
+        assert_eq!(map.indices_to_string(), "{200}");
+        map.reset(200).set(0).set(511);
+        assert_eq!(map.indices_to_string(), "{0, 511}");
+        map.set_range(7..10);
+        assert_eq!(format!("{}", map.display_ones()), "{0, 7, 8, 9, 511}");
+        assert_eq!(Bitmap::<0>::new().indices_to_string(), "{}");
+    }
+
+    #[test]
+    fn index_ref() {
+        fn takes_ref(bit: &bool) -> bool {
+            *bit
+        }
+        let map = Bitmap::<4>::from_fn(|i| i % 3 == 0);
+        for i in 0..32 {
+            assert_eq!(&map[i], &map.get_bool(i));
+            assert_eq!(takes_ref(&map[i]), map.get_bool(i));
+        }
+    }
+
+    #[test]
+    fn index_bytes() {
+        let mut map = Bitmap::<8>::new();
+        map.as_mut()[3] = 0xa5;
+        assert_eq!(&map[2..5], &[0, 0xa5, 0]);
+        assert_eq!(&map[3..4], &[0xa5]);
+        map.as_mut()[7] = 0x01;
+        assert_eq!(&map[7..], &[0x01]);
+        assert_eq!(&map[..4], &[0, 0, 0, 0xa5]);
+        assert_eq!(&map[..], map.as_ref());
+        assert_eq!(&map[8..], &[] as &[u8]);
+        // The bits 24..32 are in the byte 3.
+        assert_eq!(map.get_bits(24, 8), 0xa5);
+    }
+
+    #[test]
+    fn bitmap_ref() {
+        let mut buffer = [0u8; 64];
+        buffer[10] = 0b_1000_0001;
+        buffer[13] = 0b_0001_0000;
+        buffer[20] = 0xff;
+        let view = BitmapRef::new(&buffer[10..14]);
+        assert_eq!(view.bit_len(), 32);
+        assert_eq!(view.count(), 3);
+        assert_eq!(view.get_bool(0), true);
+        assert_eq!(view.test(7), true);
+        assert_eq!(view.test(8), false);
+        assert_eq!(view.find_first_one(), Some(0));
+        assert_eq!(view.find_first_zero(), Some(1));
+        assert_eq!(view.iter_ones().collect::<Vec<_>>(), [0, 7, 28]);
+        assert_eq!(view.any(), true);
+        assert_eq!(view.all(), false);
+        assert_eq!(view.range_to_string(4, 30).unwrap(), "010000 00000000 00000000 1000");
+
+        let map = Bitmap::<4>::from_array([0b_1000_0001, 0, 0, 0b_0001_0000]);
+        assert_eq!(view.range_to_string(0, 32), map.range_to_string(0, 32));
+        assert_eq!(map.iter_ones().collect::<Vec<_>>(), [0, 7, 28]);
+        assert_eq!(BitmapRef::from(&map), view);
+
+        let empty = BitmapRef::new(&buffer[30..40]);
+        assert_eq!(empty.none(), true);
+        assert_eq!(empty.find_first_one(), None);
+        assert_eq!(empty.iter_ones().next(), None);
+        assert_eq!(BitmapRef::new(&buffer[20..21]).all(), true);
+        assert_eq!(BitmapRef::new(&[]).iter_ones().next(), None);
+    }
+
+    #[test]
+    fn bitmap_mut() {
+        let mut buffer = [0u8; 16];
+        {
+            let mut view = BitmapMut::new(&mut buffer[4..8]);
+            view.set(0).set(31).flip(9).flip(9).flip(10);
+            view.set_range(12..20);
+            view.reset(13);
+            assert_eq!(view.count(), 10);
+            assert_eq!(view.test(13), false);
+            assert_eq!(view.iter_ones().collect::<Vec<_>>(), [0, 10, 12, 14, 15, 16, 17, 18, 19, 31]);
+            {
+                let mut inner = view.reborrow();
+                let mut bit = inner.at_mut(1);
+                assert_eq!(*bit, false);
+                bit.set();
+                assert_eq!(format!("{bit}"), "bit[1] = 1");
+            }
+            assert_eq!(view.test(1), true);
+        }
+        assert_eq!(buffer[..4], [0; 4]);
+        assert_eq!(buffer[4..8], [0b_0000_0011, 0b_1101_0100, 0b_0000_1111, 0b_1000_0000]);
+        assert_eq!(buffer[8..], [0; 8]);
+
+        let mut view = BitmapMut::new(&mut buffer[4..8]);
+        view.fill_prefix([0xff_u8, 0xff]);
+        view &= &[0x0f_u8, 0xf0, 0xff][..];
+        view |= &[0, 0, 0, 0x01][..];
+        view ^= &[0x01_u8][..];
+        assert_eq!(view.as_bytes(), [0x0e, 0xf0, 0x0f, 0x01]);
+        view.flip_all();
+        assert_eq!(view.as_bitmap_ref().count(), 32 - 12);
+        view.reset_all();
+        assert_eq!(view.none(), true);
+        assert_eq!(buffer, [0; 16]);
+
+        let mut map = Bitmap::<2>::new();
+        map.as_bitmap_mut().set(9);
+        BitmapMut::from(&mut map).set_all().reset(0);
+        assert_eq!(map.count(), 15);
+    }
+
+    #[test]
+    fn from_raw_parts() {
+        let raw = Box::into_raw(vec![0u8; 32].into_boxed_slice()).cast::<u8>();
+        unsafe {
+            {
+                let mut view = BitmapMut::from_raw_parts(raw.add(8), 16);
+                view.set(0).set(127);
+                assert_eq!(view.count(), 2);
+            }
+            {
+                let map: &mut Bitmap<4> = Bitmap::from_ptr_mut(raw.add(28));
+                map.set(31);
+            }
+            let view = BitmapRef::from_raw_parts(raw, 32);
+            assert_eq!(view.iter_ones().collect::<Vec<_>>(), [64, 191, 255]);
+            let map: &Bitmap<16> = Bitmap::from_ptr(raw.add(8));
+            assert_eq!(map.count(), 2);
+            assert_eq!(map.test(127), true);
+            let buffer = Box::from_raw(core::ptr::slice_from_raw_parts_mut(raw, 32));
+            assert_eq!(buffer[8], 1);
+            assert_eq!(buffer[23], 0x80);
+            assert_eq!(buffer[31], 0x80);
+        }
+    }
+
+    #[test]
+    fn volatile_bitmap() {
+        let ops: [(u8, usize); 8] = [(0, 3), (0, 17), (2, 3), (1, 30), (1, 17), (0, 0), (2, 0), (1, 9)];
+        let mut plain = Bitmap::<4>::from(0x_8000_0100_u32);
+        let mut backing = Bitmap::<4>::from(0x_8000_0100_u32);
+        let mut reg = VolatileBitmap::new(&mut backing);
+        for (op, index) in ops {
+            match op {
+                0 => {
+                    plain.set(index);
+                    reg.set(index);
+                }
+                1 => {
+                    plain.reset(index);
+                    reg.reset(index);
+                }
+                _ => {
+                    plain.flip(index);
+                    reg.flip(index);
+                }
+            }
+            for i in 0..32 {
+                assert_eq!(reg.get_bool(i), plain.get_bool(i));
+            }
+            assert_eq!(reg.count(), plain.count());
+        }
+        assert_eq!(reg.load(), plain);
+        reg.flip_all();
+        plain.flip_all();
+        assert_eq!(reg.load(), plain);
+        reg.set_all();
+        assert_eq!(reg.count(), 32);
+        reg.reset_all().write_byte(2, 0xa5);
+        assert_eq!(reg.read_byte(2), 0xa5);
+        reg.store(&plain);
+        assert_eq!(backing, plain);
+
+        let mut buffer = [0u8; 3];
+        let mut reg = unsafe { VolatileBitmap::<2>::from_ptr(buffer[1..].as_mut_ptr()) };
+        reg.set(15);
+        assert_eq!(buffer, [0, 0, 0x80]);
+    }
+
+    #[cfg(all(target_has_atomic = "8", target_has_atomic = "ptr"))]
+    #[test]
+    fn atomic_bitmap() {
+        use std::sync::atomic::Ordering::*;
+
+        let atomic = AtomicBitmap::from(Bitmap::<2>::from(0x_00ff_u16));
+        assert_eq!(atomic.find_first_zero(Acquire), Some(8));
+        atomic.set(8, Relaxed).reset(0, Relaxed).flip(15, Relaxed);
+        assert_eq!(atomic.test(8, Relaxed), true);
+        assert_eq!(atomic.test(0, Relaxed), false);
+        assert_eq!(atomic.find_first_zero(Relaxed), Some(0));
+        assert_eq!(atomic.test_and_reset(15, Relaxed), true);
+        assert_eq!(atomic.test_and_reset(15, Relaxed), false);
+        assert_eq!(atomic.load(Relaxed), Bitmap::<2>::from(0x_01fe_u16));
+        atomic.store(&Bitmap::FULL, Relaxed);
+        assert_eq!(atomic.find_first_zero(Relaxed), None);
+        assert_eq!(Bitmap::from(atomic), Bitmap::<2>::FULL);
+
+        const THREADS: usize = 8;
+        let shared = std::sync::Arc::new(AtomicBitmap::<64>::new());
+        let handles: Vec<_> = (0..THREADS)
+            .map(|t| {
+                let shared = shared.clone();
+                std::thread::spawn(move || {
+                    for index in (t..512).step_by(THREADS) {
+                        assert_eq!(shared.test_and_set(index, AcqRel), false);
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert_eq!(shared.count(Acquire), 512);
+    }
+
+    #[cfg(all(target_has_atomic = "8", target_has_atomic = "ptr"))]
+    #[test]
+    fn atomic_allocate() {
+        use std::sync::atomic::{AtomicBool, Ordering::*};
+
+        let slots = AtomicBitmap::<2>::from(Bitmap::<2>::from(0x_7fff_u16));
+        assert_eq!(slots.allocate(), Some(15));
+        assert_eq!(slots.allocate(), None);
+        assert_eq!(slots.release(3), true);
+        assert_eq!(slots.release(3), false);
+        assert_eq!(slots.allocate(), Some(3));
+
+        const THREADS: usize = 8;
+        const SLOTS: usize = 96;
+        let slots = std::sync::Arc::new(AtomicBitmap::<{ SLOTS / 8 }>::new());
+        let owned = std::sync::Arc::new([const { AtomicBool::new(false) }; SLOTS]);
+        let handles: Vec<_> = (0..THREADS)
+            .map(|_| {
+                let slots = slots.clone();
+                let owned = owned.clone();
+                std::thread::spawn(move || {
+                    let mut held = Vec::new();
+                    for round in 0..5000 {
+                        // Every thread holds at most 6 slots, so there is
+                        // always a free one.
+                        if held.len() < 6 && round % 3 != 2 {
+                            let index = slots.allocate().unwrap();
+                            assert_eq!(owned[index].swap(true, Relaxed), false);
+                            held.push(index);
+                        } else if let Some(index) = held.pop() {
+                            owned[index].store(false, Relaxed);
+                            assert_eq!(slots.release(index), true);
+                        }
+                    }
+                    for index in held {
+                        owned[index].store(false, Relaxed);
+                        slots.release(index);
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert_eq!(slots.count(Acquire), 0);
+    }
+
+    #[cfg(all(target_has_atomic = "8", target_has_atomic = "ptr"))]
+    #[test]
+    fn atomic_words() {
+        use std::sync::atomic::Ordering::*;
+        const W: usize = usize::BITS as usize;
+
+        // One full word and a tail of 3 bytes.
+        const BYTES: usize = W / 8 + 3;
+        let map = AtomicBitmap::<BYTES>::new();
+        assert_eq!(map.fetch_or_at(0, 1 | 1 << (W - 1), Relaxed), 0);
+        assert_eq!(map.fetch_or_at(1, 1 | 1 << 23 | 1 << 24, Relaxed), 0);
+        assert_eq!(map.load(Relaxed).iter_ones().collect::<Vec<_>>(), [0, W - 1, W, W + 23]);
+        assert_eq!(map.fetch_or_at(1, 0, Relaxed), 1 | 1 << 23);
+        assert_eq!(map.fetch_and_at(0, !1, Relaxed), 1 | 1 << (W - 1));
+        assert_eq!(map.fetch_and_at(1, !(1 << 23), Relaxed), 1 | 1 << 23);
+        assert_eq!(map.load(Relaxed).iter_ones().collect::<Vec<_>>(), [W - 1, W]);
+
+        map.set(W + 1, Relaxed).flip(W - 2, Relaxed).reset(W, Relaxed);
+        assert_eq!(map.test(W - 2, Relaxed), true);
+        assert_eq!(map.count(Relaxed), 3);
+        assert_eq!(map.find_first_zero(Relaxed), Some(0));
+
+        let mut other = Bitmap::<BYTES>::new();
+        other.set(3).set(W + 2).set(W + 23);
+        map.or_assign_atomic(&other);
+        assert_eq!(
+            map.load(Relaxed).iter_ones().collect::<Vec<_>>(),
+            [3, W - 2, W - 1, W + 1, W + 2, W + 23]
+        );
+        map.store(&Bitmap::FULL, Relaxed);
+        assert_eq!(map.find_first_zero(Relaxed), None);
+        assert_eq!(map.allocate(), None);
+        map.release(W + 20);
+        assert_eq!(map.allocate(), Some(W + 20));
+        map.release(5);
+        assert_eq!(map.allocate(), Some(5));
+        assert_eq!(map.count(Relaxed), BYTES * 8);
+    }
+
+    #[test]
+    fn word_layer() {
+        // Views at every offset, so the aligned words start anywhere.
+        let mut buffer = [0u8; 64];
+        for (i, byte) in buffer.iter_mut().enumerate() {
+            *byte = (i as u8).wrapping_mul(37) | 0x11;
+        }
+        for offset in 0..9 {
+            for len in 0..=40 {
+                let bits = &buffer[offset..offset + len];
+                let naive = (0..len * 8).find(|&i| bits[i / 8] & (1 << (i % 8)) == 0);
+                assert_eq!(BitmapRef::new(bits).find_first_zero(), naive);
+
+                let mut ones = vec![0xffu8; len];
+                if len > 0 {
+                    let at = (offset * 29 + len * 7) % (len * 8);
+                    ones[at / 8] &= !(1 << (at % 8));
+                    assert_eq!(BitmapRef::new(&ones).find_first_zero(), Some(at));
+                }
+
+                let mut flipped = buffer;
+                BitmapMut::new(&mut flipped[offset..offset + len]).flip_all();
+                for i in 0..64 {
+                    let inside = (offset..offset + len).contains(&i);
+                    assert_eq!(flipped[i], if inside { !buffer[i] } else { buffer[i] });
+                }
+
+                let mut anded = buffer;
+                let rhs = &buffer[3..3 + len.min(30)];
+                let mut view = BitmapMut::new(&mut anded[offset..offset + len]);
+                view &= rhs;
+                for i in 0..len {
+                    let r = rhs.get(i).copied().unwrap_or(0);
+                    assert_eq!(anded[offset + i], buffer[offset + i] & r);
+                }
+            }
+        }
+
+        let mut map = Bitmap::<37>::from_fn(|i| i % 7 == 0);
+        let other = Bitmap::<37>::from_fn(|i| i % 5 == 0);
+        let mut expected = Bitmap::<37>::from_fn(|i| i % 7 == 0 || i % 5 == 0);
+        map |= &other;
+        assert_eq!(map, expected);
+        map ^= &other;
+        expected = Bitmap::<37>::from_fn(|i| i % 7 == 0 && i % 5 != 0);
+        assert_eq!(map, expected);
+        map &= &Bitmap::<37>::from_fn(|i| i < 100);
+        assert_eq!(map.iter_ones().last(), Some(98));
+        map &= [0xff_u8; 9];
+        assert_eq!(map.iter_ones().last(), Some(63));
+    }
+
+    #[test]
+    fn count_by_words() {
+        let mut buffer = [0u8; 32];
+        for (i, byte) in buffer.iter_mut().enumerate() {
+            *byte = (i as u8).wrapping_mul(151) ^ 0x5a;
+        }
+        let naive = |bits: &[u8], start: usize, end: usize| {
+            (start..end)
+                .filter(|&i| bits[i / 8] & (1 << (i % 8)) != 0)
+                .count()
+        };
+        for offset in 0..9 {
+            for len in 0..=17 {
+                let bits = &buffer[offset..offset + len];
+                assert_eq!(BitmapRef::new(bits).count(), naive(bits, 0, len * 8));
+                let ones = [0xffu8; 17];
+                assert_eq!(BitmapRef::new(&ones[..len]).count(), len * 8);
+            }
+        }
+
+        let map = Bitmap::<17>::from_fn(|i| buffer[i / 8] & (1 << (i % 8)) != 0);
+        assert_eq!(map.count(), naive(&buffer, 0, 136));
+        for start in 0..=136 {
+            for end in start..=136 {
+                assert_eq!(map.count_range(start..end), naive(&buffer, start, end));
+            }
+        }
+    }
+
+    #[test]
+    fn find_first_one_by_words() {
+        let mut map = Bitmap::<24>::new();
+        assert_eq!(map.find_first_one(), None);
+        for i in 0..192 {
+            map.set(i);
+            assert_eq!(map.find_first_one(), Some(i));
+            map.set(191);
+            assert_eq!(map.find_first_one(), Some(i));
+            map.reset_all();
+        }
+
+        // The same at every alignment of the words.
+        let mut buffer = [0u8; 32];
+        for offset in 0..9 {
+            let bits = &mut buffer[offset..offset + 24];
+            for i in 0..192 {
+                bits[i / 8] = 1 << (i % 8);
+                assert_eq!(BitmapRef::new(bits).find_first_one(), Some(i));
+                bits[i / 8] = 0;
+            }
+            assert_eq!(BitmapRef::new(bits).find_first_one(), None);
+        }
+    }
+
+    #[test]
+    fn find_first_zero_by_words() {
+        let mut map = Bitmap::<24>::new();
+        map.set_all();
+        assert_eq!(map.find_first_zero(), None);
+        for i in 0..192 {
+            map.reset(i);
+            assert_eq!(map.find_first_zero(), Some(i));
+            map.reset(191);
+            assert_eq!(map.find_first_zero(), Some(i));
+            map.set_all();
+        }
+    }
+
+    #[test]
+    fn set_all_in_place() {
+        // An 8MB temporary on this stack would overflow it.
+        std::thread::Builder::new()
+            .stack_size(64 << 10)
+            .spawn(|| {
+                let mut map = Bitmap::<{ 8 << 20 }>::new_boxed();
+                map.set_all();
+                assert_eq!(map.count(), 64 << 20);
+                map.reset_all();
+                assert_eq!(map.find_first_one(), None);
+            })
+            .unwrap()
+            .join()
+            .unwrap();
+    }
+
+    #[test]
+    fn flip_all_by_words() {
+        fn check<const N: usize>() {
+            let mut bytes = [0u8; N];
+            for (i, byte) in bytes.iter_mut().enumerate() {
+                *byte = (i as u8).wrapping_mul(73) ^ 0xc3;
+            }
+            let mut map = Bitmap::from_array(bytes);
+            map.flip_all();
+            assert_eq!(<[u8; N]>::from(map), bytes.map(|byte| !byte));
+        }
+        check::<0>();
+        check::<1>();
+        check::<2>();
+        check::<3>();
+        check::<4>();
+        check::<5>();
+        check::<6>();
+        check::<7>();
+        check::<8>();
+        check::<9>();
+        check::<10>();
+        check::<11>();
+        check::<12>();
+        check::<13>();
+        check::<14>();
+        check::<15>();
+        check::<16>();
+        check::<17>();
+    }
+
+    #[test]
+    fn array_ops_by_words() {
+        fn check<const BYTES: usize, const N: usize>() {
+            let bytes: [u8; BYTES] =
+                core::array::from_fn(|i| (i as u8).wrapping_mul(89) ^ 0x3c);
+            let rhs: [u8; N] = core::array::from_fn(|i| (i as u8).wrapping_mul(41) ^ 0xa5);
+            let byte = |i: usize| bytes.get(i).copied().unwrap_or(0);
+            let model = |op: fn(u8, u8) -> u8, rest: Option<u8>| -> [u8; BYTES] {
+                core::array::from_fn(|i| match rhs.get(i) {
+                    Some(r) => op(bytes[i], *r),
+                    None => rest.unwrap_or(bytes[i]),
+                })
+            };
+
+            let mut map = Bitmap::from_array(bytes);
+            map &= rhs;
+            assert_eq!(<[u8; BYTES]>::from(map), model(|a, b| a & b, Some(0)));
+            let mut map = Bitmap::from_array(bytes);
+            map |= rhs;
+            assert_eq!(<[u8; BYTES]>::from(map), model(|a, b| a | b, None));
+            let mut map = Bitmap::from_array(bytes);
+            map ^= rhs;
+            assert_eq!(<[u8; BYTES]>::from(map), model(|a, b| a ^ b, None));
+
+            let map = Bitmap::from_array(bytes);
+            let anded: [u8; N] = core::array::from_fn(|i| rhs[i] & byte(i));
+            assert_eq!(&map & rhs, anded);
+            let xored: [u8; N] = core::array::from_fn(|i| rhs[i] ^ byte(i));
+            assert_eq!(&map ^ rhs, xored);
+        }
+        check::<21, 3>();
+        check::<21, 16>();
+        check::<21, 21>();
+        check::<21, 29>();
+        check::<5, 40>();
+        check::<0, 9>();
+        check::<9, 0>();
+    }
+
+    #[test]
+    fn hier_summary() {
+        // Three blocks of 64 bytes and a short one of 8.
+        type Hier = HierBitmap<200, { hier_summary_bytes(200) }>;
+        fn check(map: &Hier) {
+            let bits = map.as_bitmap();
+            for block in 0..8 {
+                let mut block_bits = block * 512..(block * 512 + 512).min(1600);
+                let full = block < 4 && block_bits.all(|i| bits.test(i));
+                assert_eq!(map.summary().test(block), full, "block {block}");
+            }
+            assert_eq!(map.find_first_zero(), bits.find_first_zero());
+        }
+
+        let mut map = Hier::new();
+        check(&map);
+        map.set_all();
+        check(&map);
+        assert_eq!(map.find_first_zero(), None);
+        map.reset(5);
+        check(&map);
+        map.set(5);
+        check(&map);
+        map.reset_range(600..1200);
+        check(&map);
+        assert_eq!(map.find_first_zero(), Some(600));
+        map.set_range(..);
+        check(&map);
+        map.flip(1599).flip(1000);
+        check(&map);
+        assert_eq!(map.find_first_zero(), Some(1000));
+        map.flip(1000).flip(1599);
+        check(&map);
+        map.flip_all();
+        check(&map);
+        map.set_range(1536..);
+        check(&map);
+        assert_eq!(map.summary().test(3), true);
+        map.reset_range(1535..1537);
+        check(&map);
+        assert_eq!(map.count(), 63);
+
+        let map = Hier::from(Bitmap::<200>::from_fn(|i| i < 1024 || i % 7 != 0));
+        check(&map);
+        assert_eq!(map.find_first_zero(), Some(1029));
+        let mut map = map.clone();
+        map.reset_all();
+        for i in 1536..1600 {
+            map.set(i);
+            check(&map);
+        }
+    }
+
+    #[test]
+    fn cursor_next_fit() {
+        let mut map = newmap!(;24);
+        let mut slots = BitmapCursor::new(&mut map);
+        for i in 0..24 {
+            assert_eq!(slots.alloc(), Some(i));
+        }
+        assert_eq!(slots.alloc(), None);
+        assert_eq!(slots.position(), 0);
+
+        // Freed slots behind the cursor are only found after wrapping.
+        slots.free(20, false);
+        slots.free(3, false);
+        slots.seek(10);
+        assert_eq!(slots.alloc(), Some(20));
+        assert_eq!(slots.position(), 21);
+        assert_eq!(slots.alloc(), Some(3));
+        assert_eq!(slots.alloc(), None);
+
+        // The last bit wraps the cursor to 0.
+        slots.free(23, false);
+        slots.free(0, false);
+        assert_eq!(slots.alloc(), Some(23));
+        assert_eq!(slots.position(), 0);
+        assert_eq!(slots.alloc(), Some(0));
+        assert_eq!(slots.as_bitmap().all(), true);
+
+        // Allocating and freeing the same slot never gets stuck, with or
+        // without rewinding.
+        slots.free(7, false);
+        for _ in 0..100 {
+            let slot = slots.alloc().unwrap();
+            assert_eq!(slot, 7);
+            assert_eq!(slots.free(slot, false), true);
+        }
+        for _ in 0..100 {
+            let slot = slots.alloc().unwrap();
+            assert_eq!(slot, 7);
+            assert_eq!(slots.free(slot, true), true);
+            assert_eq!(slots.position(), 7);
+        }
+        assert_eq!(slots.free(7, true), false);
+
+        let mut empty = Bitmap::<0>::new();
+        assert_eq!(BitmapCursor::new(&mut empty).alloc(), None);
+    }
+
+    #[test]
+    fn set_first_zero_fills_in_order() {
+        let mut map = Bitmap::<3>::new();
+        for i in 0..24 {
+            assert_eq!(map.set_first_zero(), Some(i));
+        }
+        assert_eq!(map.set_first_zero(), None);
+        assert_eq!(map.all(), true);
+        for i in 0..24 {
+            assert_eq!(map.reset_first_one(), Some(i));
+        }
+        assert_eq!(map.reset_first_one(), None);
+        assert_eq!(map.none(), true);
+
+        let mut buffer = [0b_0111_1111u8, 0xff];
+        let mut view = BitmapMut::new(&mut buffer);
+        assert_eq!(view.set_first_zero(), Some(7));
+        assert_eq!(view.set_first_zero(), None);
+        assert_eq!(view.reset_first_one(), Some(0));
+        assert_eq!(buffer, [0b_1111_1110, 0xff]);
+    }
+
+    #[test]
+    fn pop_drains_in_order() {
+        let pattern = |i| i % 3 == 0 || i % 11 == 5 || i >= 180;
+        let mut map = Bitmap::<24>::from_fn(pattern);
+        let count = map.count();
+        let popped: Vec<usize> = core::iter::from_fn(|| map.pop_first()).collect();
+        assert_eq!(popped.len(), count);
+        assert!(popped.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(popped, (0..192).filter(|&i| pattern(i)).collect::<Vec<_>>());
+        assert_eq!(map.none(), true);
+
+        let mut map = Bitmap::<24>::from_fn(pattern);
+        let popped: Vec<usize> = core::iter::from_fn(|| map.pop_last()).collect();
+        assert_eq!(popped.len(), count);
+        assert!(popped.windows(2).all(|w| w[0] > w[1]));
+        assert_eq!(map.pop_last(), None);
+
+        // Only the last bit set, and every single bit, at every alignment
+        // of the words.
+        let mut buffer = [0u8; 40];
+        for offset in 0..9 {
+            let ptr = buffer[offset..].as_mut_ptr();
+            let map: &mut Bitmap<24> = unsafe { Bitmap::from_ptr_mut(ptr) };
+            map.set(191);
+            assert_eq!(map.pop_first(), Some(191));
+            assert_eq!(map.pop_first(), None);
+            for i in 0..192 {
+                map.set(i);
+                assert_eq!(map.pop_last(), Some(i));
+                assert_eq!(map.pop_last(), None);
+            }
+            map.set_all();
+            for i in (0..192).rev() {
+                assert_eq!(map.pop_last(), Some(i));
+            }
+        }
+    }
+
+    #[test]
+    fn drain_ones() {
+        let pattern = |i| i % 5 == 1 || (64..80).contains(&i);
+        let mut map = Bitmap::<16>::from_fn(pattern);
+        let ones: Vec<usize> = map.iter_ones().collect();
+        assert_eq!(map.drain_ones().collect::<Vec<_>>(), ones);
+        assert_eq!(map.none(), true);
+
+        // Dropping midway leaves the rest.
+        let mut map = Bitmap::<16>::from_fn(pattern);
+        let head: Vec<usize> = map.drain_ones().take(10).collect();
+        assert_eq!(head, ones[..10]);
+        assert_eq!(map.iter_ones().collect::<Vec<_>>(), ones[10..]);
+
+        let mut empty = Bitmap::<16>::new();
+        assert_eq!(empty.drain_ones().next(), None);
+        let mut zero = Bitmap::<0>::new();
+        assert_eq!(zero.drain_ones().next(), None);
+
+        let mut full = Bitmap::<16>::new();
+        full.set_all();
+        let mut drain = full.drain_ones();
+        assert!(drain.by_ref().eq(0..128));
+        assert_eq!(drain.next(), None);
+        assert_eq!(full.none(), true);
+
+        let mut buffer = [0u8, 0b_1010, 0x80];
+        let mut view = BitmapMut::new(&mut buffer[1..]);
+        assert_eq!(view.drain_ones().collect::<Vec<_>>(), [1, 3, 15]);
+        assert_eq!(buffer, [0, 0, 0]);
+    }
+
+    #[test]
+    fn wrapping_search() {
+        // The only free bit is just before the hint: a full wrap.
+        let mut map = Bitmap::<24>::full();
+        map.reset(99);
+        assert_eq!(map.next_zero_wrapping(100), Some(99));
+        assert_eq!(map.next_zero_wrapping(99), Some(99));
+        assert_eq!(map.next_zero_wrapping(0), Some(99));
+        map.set(99);
+        assert_eq!(map.next_zero_wrapping(100), None);
+
+        map.flip_all().set(99);
+        assert_eq!(map.next_one_wrapping(100), Some(99));
+        assert_eq!(map.next_one_wrapping(191), Some(99));
+        map.reset(99);
+        assert_eq!(map.next_one_wrapping(100), None);
+
+        // Against a naive search, from every hint.
+        let pattern = |i| i % 7 == 3 || i == 190;
+        let map = Bitmap::<24>::from_fn(pattern);
+        for from in 0..192 {
+            let naive = |value: bool| (from..192).chain(0..from).find(|&i| pattern(i) == value);
+            assert_eq!(map.next_one_wrapping(from), naive(true));
+            assert_eq!(map.next_zero_wrapping(from), naive(false));
+        }
+    }
+
+    #[test]
+    fn bits_manage_slice() {
+        fn first_free<T: BitsManage + ?Sized>(map: &T) -> Option<usize> {
+            map.find_first_zero()
+        }
+
+        let mut buffer = [0xffu8; 8];
+        let slice = &mut buffer[2..5];
+        slice.reset_all();
+        assert_eq!(slice.none(), true);
+        slice.set(0).set(9).flip(23);
+        assert_eq!(slice.count(), 3);
+        assert_eq!(slice.test(9), true);
+        assert_eq!(slice.get_01(23), 1);
+        assert_eq!(slice.find_first_one(), Some(0));
+        assert_eq!(first_free(slice), Some(1));
+        slice.reset(0);
+        assert_eq!(slice.find_first_one(), Some(9));
+        assert_eq!(slice.test_all_of(&[9, 23]), true);
+        // The bytes around the sub-slice are untouched.
+        assert_eq!(buffer, [0xff, 0xff, 0, 0b_10, 0x80, 0xff, 0xff, 0xff]);
+
+        // The same answers as a map holding the same bytes.
+        let bytes = [0b_1010_0000u8, 0xff, 0, 0x0f];
+        let map = Bitmap::<4>::from(bytes);
+        assert_eq!(bytes[..].count(), map.count());
+        assert_eq!(first_free(&bytes[..]), first_free(&map));
+        let mut slice = bytes;
+        slice[..].flip_all();
+        assert_eq!(slice[..].all(), false);
+        slice[..].set_all();
+        assert_eq!(slice[..].all(), true);
+        assert_eq!(first_free(&[][..]), None);
+    }
+
+    #[test]
+    fn bits_manage_owned() {
+        let pattern = |i| i % 3 == 0 || (40..72).contains(&i);
+        let map = Bitmap::<12>::from_fn(pattern);
+        let vec: Vec<u8> = Into::<[u8; 12]>::into(map.clone()).to_vec();
+        let boxed: Box<[u8]> = vec.clone().into_boxed_slice();
+        assert_eq!(vec.count(), map.count());
+        assert_eq!(boxed.count(), map.count());
+        assert_eq!(vec.find_first_zero(), map.find_first_zero());
+        assert_eq!(boxed.find_first_zero(), map.find_first_zero());
+        for (start, end) in [(0, 8), (40, 72), (38, 74), (90, 96)] {
+            let range: Vec<usize> = (start..end).collect();
+            assert_eq!(vec.test_all_of(&range), map.test_all_of(&range));
+            assert_eq!(vec.test_any_of(&range), map.test_any_of(&range));
+            assert_eq!(boxed.test_all_of(&range), map.test_all_of(&range));
+        }
+
+        let mut vec = vec![0u8; 3];
+        vec.set(1).set(23).flip(1);
+        assert_eq!(vec, [0, 0, 0x80]);
+        let mut boxed = vec.into_boxed_slice();
+        boxed.flip_all().reset(0);
+        assert_eq!(boxed.find_first_zero(), Some(0));
+        assert_eq!(boxed.count(), 22);
+    }
+
+    #[test]
+    fn as_words() {
+        const WORD: usize = std::mem::size_of::<usize>();
+        const BITS: usize = WORD * 8;
+
+        #[repr(C, align(16))]
+        struct Aligned(Bitmap<32>);
+
+        let mut aligned = Aligned(Bitmap::new());
+        let map = &mut aligned.0;
+        map.set(0).set(BITS - 1).set(BITS + 2).set(255);
+        let words = map.try_as_words().unwrap();
+        assert_eq!(words.len(), 32 / WORD);
+        assert_eq!(usize::from_le(words[0]), 1 | (1 << (BITS - 1)));
+        assert_eq!(usize::from_le(words[1]), 1 << 2);
+        assert_eq!(usize::from_le(words[words.len() - 1]), 1 << (BITS - 1));
+        assert_eq!(words[2..words.len() - 1].iter().all(|&word| word == 0), true);
+
+        map.try_as_mut_words().unwrap()[1] = usize::from_le(0b_1000);
+        assert_eq!(map.iter_ones().collect::<Vec<_>>(), [0, BITS - 1, BITS + 3, 255]);
+
+        // A map off the word alignment.
+        let mut buffer = [0u8; 48];
+        let offset = 1 + buffer.as_ptr().align_offset(WORD);
+        let ptr = buffer[offset..].as_mut_ptr();
+        let map: &mut Bitmap<32> = unsafe { Bitmap::from_ptr_mut(ptr) };
+        assert_eq!(map.try_as_words(), None);
+        assert_eq!(map.try_as_mut_words(), None);
+        map.set(0).set(8 * (WORD - 1));
+        let (prefix, words, suffix) = map.as_words();
+        assert_eq!(prefix.len(), WORD - 1);
+        assert_eq!(suffix.len(), 1);
+        assert_eq!(usize::from_le(words[0]), 1);
+        let (_, words, _) = map.as_mut_words();
+        words[0] = usize::MAX;
+        assert_eq!(map.count(), BITS + 1);
+    }
+
+    #[test]
+    fn test_mem() {
+        use core::mem::*;
+        let map = newmap!();
+        assert_eq!(size_of_val(&map), 0);
+        let map = newmap!(;48);
+        assert_eq!(size_of_val(&map), 6);
+
+        // 4K bytes
+        let page = Box::new(newmap!(;4096 * 8));
+        assert_eq!(size_of_val(&page), 8);
+        assert_eq!(size_of_val(&*page), 4096);
+    }
+
+    #[test]
+    fn boxmap() {
+        use core::mem::*;
+        let page = boxmap!(;4096 * 8);
+        assert_eq!(size_of_val(&*page), 4096);
+        assert_eq!(page.none(), true);
+        assert_eq!(size_of_val(&*boxmap!()), 0);
+    }
+
+    #[test]
+    fn boxed_on_small_stack() {
+        // The 4MB map would overflow the 64KB stack if it were built there.
+        let handle = std::thread::Builder::new()
+            .stack_size(64 * 1024)
+            .spawn(|| {
+                let mut map = boxmap!(;4 * 1024 * 1024 * 8);
+                map.set(4 * 1024 * 1024 * 8 - 1);
+                let other = Bitmap::<{ 4 * 1024 * 1024 }>::new_boxed();
+                (map.count(), other.count())
+            })
+            .unwrap();
+        assert_eq!(handle.join().unwrap(), (1, 0));
+
+        // So would the 8MB ones.
+        let handle = std::thread::Builder::new()
+            .stack_size(64 * 1024)
+            .spawn(|| {
+                let zero = Bitmap::<{ 8 * 1024 * 1024 }>::new_boxed();
+                let full = Bitmap::<{ 8 * 1024 * 1024 }>::full_boxed();
+                (zero.none(), full.all())
+            })
+            .unwrap();
+        assert_eq!(handle.join().unwrap(), (true, true));
+        assert_eq!(*Bitmap::<0>::full_boxed(), Bitmap::<0>::new());
     }
 }
@@ -0,0 +1,75 @@
+#![allow(clippy::bool_assert_comparison)]
+#![cfg_attr(feature = "bytemuck", allow(clippy::clone_on_copy, clippy::op_ref))]
+
+#[cfg(test)]
+mod bitset_core_trait {
+    extern crate cbitmap;
+    use bitset_core::BitSet;
+    use cbitmap::bitmap::*;
+
+    /// Only knows about `BitSet`.
+    fn sieve<T: BitSet + ?Sized>(bits: &mut T) -> usize {
+        bits.bit_init(true).bit_reset(0).bit_reset(1);
+        for i in 2..bits.bit_len() {
+            if bits.bit_test(i) {
+                for j in (i * i..bits.bit_len()).step_by(i) {
+                    bits.bit_reset(j);
+                }
+            }
+        }
+        bits.bit_count()
+    }
+
+    #[test]
+    fn generic_over_bitset() {
+        let mut map = Bitmap::<4>::new();
+        assert_eq!(sieve(&mut map), 11);
+        let primes = he_lang!(2 | 3 | 5 | 7 | 11 | 13 | 17 | 19 | 23 | 29 | 31; 32);
+        assert_eq!(map, primes);
+        assert_eq!(map.count(), 11);
+
+        // The same indexes as the bytes through `BitSet`.
+        let mut bytes = [0u8; 4];
+        sieve(&mut bytes[..]);
+        assert_eq!(bytes, Into::<[u8; 4]>::into(map));
+    }
+
+    #[test]
+    fn single_bits() {
+        let mut map = Bitmap::<4>::new();
+        assert_eq!(BitSet::bit_len(&map), map.bit_len());
+        map.bit_set(9).bit_flip(30).bit_cond(17, true).bit_cond(9, false);
+        assert_eq!(map, he_lang!(17 | 30; 32));
+        for i in 0..32 {
+            assert_eq!(map.bit_test(i), map.test(i));
+        }
+        map.bit_reset(17);
+        assert_eq!(map.bit_count(), 1);
+        assert_eq!(map.bit_any(), map.any());
+        assert_eq!(map.bit_none(), false);
+        assert_eq!(map.bit_init(true).bit_all(), true);
+        assert_eq!(map.bit_not().bit_none(), true);
+    }
+
+    #[test]
+    fn combinators() {
+        let a = Bitmap::<4>::from(0x_f0f0_1234_u32);
+        let b = Bitmap::<4>::from(0x_ff00_4321_u32);
+        let mask = Bitmap::<4>::from(0x_0000_ffff_u32);
+        assert_eq!(a.clone().bit_or(&b).clone(), &a | &b);
+        assert_eq!(a.clone().bit_and(&b).clone(), &a & &b);
+        assert_eq!(a.clone().bit_xor(&b).clone(), &a ^ &b);
+        assert_eq!(a.clone().bit_andnot(&b).clone(), a.difference(&b));
+        assert_eq!(
+            a.clone().bit_mask(&b, &mask).clone(),
+            Bitmap::<4>::from(0x_f0f0_4321_u32)
+        );
+        assert_eq!(a.bit_eq(&a.clone()), true);
+        assert_eq!(a.bit_eq(&b), false);
+        assert_eq!(a.bit_disjoint(&b), a.is_disjoint(&b));
+        let both = &a & &b;
+        assert_eq!(both.bit_subset(&a), true);
+        assert_eq!(a.bit_superset(&both), true);
+        assert_eq!(a.bit_subset(&b), false);
+    }
+}
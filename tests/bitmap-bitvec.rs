@@ -0,0 +1,48 @@
+#[cfg(test)]
+mod bitvec_interop {
+    extern crate cbitmap;
+    use bitvec::prelude::*;
+    use cbitmap::bitmap::*;
+
+    #[test]
+    fn bitmap_round_trip() {
+        fn check<const BYTES: usize>(map: Bitmap<BYTES>) {
+            let bits: BitVec<u8, Lsb0> = (&map).into();
+            assert_eq!(bits.len(), BYTES * 8);
+            assert!(bits.iter_ones().eq(map.iter_ones()));
+            assert_eq!(map.as_bitslice(), bits.as_bitslice());
+            assert_eq!(Bitmap::<BYTES>::from_bitslice(&bits), Ok(map));
+        }
+        check(newmap!());
+        check(he_lang!(0 | 7; 8));
+        check(Bitmap::<3>::from_fn(|i| i % 3 == 1));
+        check(Bitmap::<125>::from_fn(|i| i % 7 == 2 || i > 990));
+    }
+
+    #[test]
+    fn bitmap_from_shorter_slice() {
+        let mut bits = bitvec![u8, Lsb0; 0; 13];
+        bits.set(1, true);
+        bits.set(12, true);
+        assert_eq!(Bitmap::<2>::from_bitslice(&bits), Ok(he_lang!(1 | 12; 16)));
+        assert_eq!(Bitmap::<4>::from_bitslice(&bits), Ok(he_lang!(1 | 12; 32)));
+        assert_eq!(
+            Bitmap::<1>::from_bitslice(&bits),
+            Err(BitmapError::LengthMismatch { expected: 8, found: 13 })
+        );
+        // A slice not starting on a byte.
+        assert_eq!(Bitmap::<2>::from_bitslice(&bits[1..]), Ok(he_lang!(0 | 11; 16)));
+    }
+
+    #[test]
+    fn dyn_round_trip() {
+        for len in [0, 1, 7, 8, 13, 64, 1000, 1001] {
+            let bits: BitVec<u8, Lsb0> = (0..len).map(|i| i % 3 == 0 || i + 1 == len).collect();
+            let map = DynBitmap::from(bits.as_bitslice());
+            assert_eq!(map.bit_len(), len);
+            assert!(map.iter_ones().eq(bits.iter_ones()));
+            assert_eq!(map.as_bitslice(), bits.as_bitslice());
+            assert_eq!(BitVec::from(&map), bits);
+        }
+    }
+}
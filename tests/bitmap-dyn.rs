@@ -0,0 +1,196 @@
+#![allow(clippy::bool_assert_comparison)]
+#![cfg_attr(feature = "bytemuck", allow(clippy::clone_on_copy))]
+
+#[cfg(test)]
+mod dynamic {
+    extern crate cbitmap;
+    use cbitmap::bitmap::*;
+
+    const BITS: usize = 1000;
+
+    #[test]
+    fn base_op() {
+        let mut map = DynBitmap::new(BITS);
+        assert_eq!(map.bit_len(), BITS);
+        assert_eq!(map.byte_len(), 125);
+        assert_eq!(map.none(), true);
+        map.set(0).set(500).set(999);
+        assert_eq!(map.test(500), true);
+        assert_eq!(map.get_01(999), 1);
+        map.reset(500).flip(1);
+        assert_eq!(map.iter_ones().collect::<Vec<_>>(), [0, 1, 999]);
+        map.set_all();
+        assert_eq!(map.all(), true);
+        assert_eq!(map.count(), BITS);
+        map.flip_all();
+        assert_eq!(map.none(), true);
+        map.flip_all().reset_all();
+        assert_eq!(map.count(), 0);
+    }
+
+    #[test]
+    fn search() {
+        let mut map = DynBitmap::full(BITS);
+        assert_eq!(map.find_first_zero(), None);
+        assert_eq!(map.find_first_one(), Some(0));
+        map.reset(777);
+        assert_eq!(map.find_first_zero(), Some(777));
+        assert_eq!(map.next_zero_wrapping(778), Some(777));
+        assert_eq!(map.set_first_zero(), Some(777));
+        assert_eq!(map.set_first_zero(), None);
+        map.reset_all().set(42).set(998);
+        assert_eq!(map.find_last_one(), Some(998));
+        assert_eq!(map.next_one_wrapping(999), Some(42));
+        assert_eq!(map.reset_first_one(), Some(42));
+    }
+
+    #[test]
+    fn ranges() {
+        let mut map = DynBitmap::new(BITS);
+        map.set_range(100..900);
+        assert_eq!(map.count(), 800);
+        assert_eq!(map.count_range(..), 800);
+        assert_eq!(map.count_range(95..105), 5);
+        map.reset_range(200..=299);
+        assert_eq!(map.count_range(100..900), 700);
+        map.set_range(990..);
+        assert_eq!(map.count_range(990..), 10);
+        assert_eq!(&map.range_to_string(988, 1000).unwrap(), "11111111 1100");
+        assert_eq!(map.range_to_string(990, 1001), None);
+    }
+
+    #[test]
+    fn bitwise_op() {
+        let mut a = DynBitmap::new(BITS);
+        let mut b = DynBitmap::new(BITS);
+        for i in 0..BITS {
+            if i % 3 == 1 {
+                a.set(i);
+            }
+            if i % 2 == 1 {
+                b.set(i);
+            }
+        }
+        let and = &a & &b;
+        assert!(and.iter_ones().eq((0..BITS).filter(|i| i % 6 == 1)));
+        let or = &a | &b;
+        assert!(or.iter_ones().eq((0..BITS).filter(|i| i % 2 == 1 || i % 3 == 1)));
+        let xor = &a ^ &b;
+        assert_eq!(xor.count(), or.count() - and.count());
+        let not = !&a;
+        assert_eq!(not.count(), BITS - a.count());
+        assert_eq!((&not & &a).none(), true);
+        a |= &b;
+        assert_eq!(a, or);
+        a &= &b;
+        assert_eq!(a, b);
+        a ^= &b;
+        assert_eq!(a.none(), true);
+    }
+
+    #[test]
+    fn padding() {
+        let mut map = DynBitmap::full(13);
+        assert_eq!(map.as_bytes(), [0xff, 0x1f]);
+        map.flip_all();
+        assert_eq!(map.as_bytes(), [0, 0]);
+        map.flip_all().reset(12);
+        assert_eq!(map.find_first_zero(), Some(12));
+        map.set(12);
+        assert_eq!(map.find_first_zero(), None);
+        assert_eq!(map.next_zero_wrapping(5), None);
+        assert_eq!(map.count(), 13);
+        assert_eq!((!&map).none(), true);
+    }
+
+    #[test]
+    fn conversions() {
+        let fixed = Bitmap::<125>::from_fn(|i| i % 7 == 1);
+        let map = DynBitmap::from(&fixed);
+        assert_eq!(map.bit_len(), BITS);
+        assert!(map.iter_ones().eq(fixed.iter_ones()));
+        assert_eq!(Bitmap::<125>::try_from(&map), Ok(fixed.clone()));
+        assert_eq!(
+            Bitmap::<124>::try_from(&map),
+            Err(BitmapError::LengthMismatch { expected: 992, found: BITS })
+        );
+        assert!(Bitmap::<2>::try_from(&DynBitmap::new(15)).is_err());
+        assert_eq!(DynBitmap::from(fixed), map);
+        assert_eq!(DynBitmap::new(0), DynBitmap::default());
+    }
+
+    #[test]
+    fn fmt() {
+        let mut map = DynBitmap::new(BITS);
+        map.set(0).set(999);
+        assert_eq!(format!("{map}"), "[1000 bits] ...00000000 00000001");
+        // The same bytes as the fixed map shows.
+        let fixed = Bitmap::<3>::from(0x_ab_00_01_u32);
+        let dynamic = DynBitmap::from(&fixed);
+        assert_eq!(format!("{dynamic}"), "[24 bits] ...00000000 00000001");
+        assert_eq!(format!("{dynamic}"), format!("{fixed}"));
+        assert_eq!(format!("{dynamic:#}"), format!("{fixed:#}"));
+        let full = format!("{map:#}");
+        assert!(full.starts_with("[1000 bits] 10000000 00000000"));
+        assert!(full.ends_with("00000000 00000001"));
+        let debug = format!("{map:?}");
+        assert!(debug.contains("#ones: 2, first_one: Some(0), last_one: Some(999)"));
+        assert!(debug.ends_with("bits: \"10000000 00000000 ... 00000000 00000001\" }"));
+        let fixed = he_lang!(0 | 999; 1000);
+        let dynamic = DynBitmap::from(&fixed);
+        assert_eq!(
+            format!("{dynamic:?}").strip_prefix("DynBitmap"),
+            format!("{fixed:?}").strip_prefix("Bitmap")
+        );
+        assert_eq!(format!("{}", DynBitmap::new(0)), "[0 bits] ");
+        assert_eq!(
+            format!("{:?}", DynBitmap::full(3)),
+            "DynBitmap { #bytes: 1, #bits: 3, bits: \"111\" }"
+        );
+    }
+
+    #[test]
+    fn push_bit() {
+        let mut map = DynBitmap::new(0);
+        assert_eq!(map.is_empty(), true);
+        for i in 0..BITS {
+            map.push_bit(i % 2 == 1);
+            assert_eq!(map.len(), i + 1);
+        }
+        assert_eq!(map.count(), BITS / 2);
+        assert!(map.iter_ones().eq((1..BITS).step_by(2)));
+        assert_eq!(map.find_first_zero(), Some(0));
+        assert!(map.capacity() >= BITS);
+
+        // Pushing after a partly used byte does not see old bits.
+        let mut map = DynBitmap::full(12);
+        map.truncate_bits(3).push_bit(false);
+        assert_eq!(map.as_bytes(), [0b_0111]);
+        assert_eq!(map.find_first_zero(), Some(3));
+    }
+
+    #[test]
+    fn resize_bits() {
+        let mut map = DynBitmap::full(BITS);
+        map.resize_bits(37, true);
+        assert_eq!(map.len(), 37);
+        assert_eq!(map.count(), 37);
+        assert_eq!(map.byte_len(), 5);
+        assert_eq!(map.as_bytes()[4], 0x1f);
+        // Growing with '0' must not bring back the dropped '1's.
+        map.resize_bits(BITS, false);
+        assert_eq!(map.count(), 37);
+        assert_eq!(map.find_first_zero(), Some(37));
+        assert!(map.iter_ones().eq(0..37));
+        map.resize_bits(BITS + 5, true);
+        assert_eq!(map.count(), 42);
+        assert_eq!(map.count_range(BITS..), 5);
+        map.flip_all();
+        assert_eq!(map.count(), BITS + 5 - 42);
+        map.resize_bits(0, true);
+        assert_eq!(map.is_empty(), true);
+        assert_eq!(map.count(), 0);
+        assert_eq!(map.find_first_zero(), None);
+        assert!(map.capacity() >= BITS);
+    }
+}
@@ -0,0 +1,63 @@
+#[cfg(test)]
+mod fixedbitset_interop {
+    extern crate cbitmap;
+    use cbitmap::bitmap::*;
+    use fixedbitset::FixedBitSet;
+
+    #[test]
+    fn round_trip() {
+        fn check<const BYTES: usize>(map: Bitmap<BYTES>) {
+            let set = FixedBitSet::from(&map);
+            assert_eq!(set.len(), BYTES * 8);
+            assert!(set.ones().eq(map.iter_ones()));
+            assert_eq!(Bitmap::<BYTES>::try_from(&set), Ok(map));
+        }
+        check(newmap!());
+        check(he_lang!(0 | 7; 8));
+        check(Bitmap::<3>::from_fn(|i| i % 3 == 1));
+        check(Bitmap::<9>::full());
+        check(Bitmap::<125>::from_fn(|i| i % 7 == 2 || i > 990));
+
+        let mut set = FixedBitSet::with_capacity(77);
+        set.insert_range(5..70);
+        assert_eq!(
+            Bitmap::<10>::try_from(&set),
+            Err(BitmapError::LengthMismatch { expected: 80, found: 77 })
+        );
+        set.grow(80);
+        let map = Bitmap::<10>::try_from(&set).unwrap();
+        assert!(map.iter_ones().eq(5..70));
+    }
+
+    #[test]
+    fn same_traversal() {
+        // A ring of 200 nodes with chords; only the nodes in `open` may be
+        // entered.
+        const NODES: usize = 200;
+        let neighbors = |n: usize| [(n + 1) % NODES, (n * 7 + 3) % NODES];
+        let open = Bitmap::<25>::from_fn(|i| i % 5 != 4);
+        let set = FixedBitSet::from(&open);
+
+        let traverse = |is_open: &dyn Fn(usize) -> bool| {
+            let mut seen = [false; NODES];
+            let mut order = vec![];
+            let mut stack = vec![0];
+            while let Some(node) = stack.pop() {
+                if seen[node] || !is_open(node) {
+                    continue;
+                }
+                seen[node] = true;
+                order.push(node);
+                stack.extend(neighbors(node));
+            }
+            order
+        };
+        let by_map = traverse(&|n| open.test(n));
+        let by_set = traverse(&|n| set.contains(n));
+        assert_eq!(by_map, by_set);
+        assert!(by_map.iter().all(|&n| open.iter_ones().any(|i| i == n)));
+
+        // Walking the converted set visits the same nodes as `iter_ones`.
+        assert!(set.ones().eq(open.iter_ones()));
+    }
+}
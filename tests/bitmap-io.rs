@@ -0,0 +1,40 @@
+#[cfg(test)]
+mod io {
+    extern crate cbitmap;
+    use cbitmap::bitmap::*;
+    use std::io::{Cursor, ErrorKind, Seek, SeekFrom, Write};
+
+    #[test]
+    fn cursor_round_trip() {
+        let a = Bitmap::<33>::from_fn(|i| i % 5 == 1);
+        let b = he_lang!(0 | 15; 16);
+        let mut cursor = Cursor::new(Vec::new());
+        a.write_to(&mut cursor).unwrap();
+        b.write_to(&mut cursor).unwrap();
+        assert_eq!(cursor.get_ref().len(), 33 + 2);
+
+        cursor.set_position(0);
+        assert_eq!(Bitmap::<33>::read_from(&mut cursor).unwrap(), a);
+        assert_eq!(Bitmap::<2>::read_from(&mut cursor).unwrap(), b);
+        assert_eq!(Bitmap::<0>::read_from(&mut cursor).unwrap(), newmap!());
+    }
+
+    #[test]
+    fn short_read() {
+        let mut input: &[u8] = &[0xff; 3];
+        let err = Bitmap::<4>::read_from(&mut input).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn file_round_trip() {
+        let map = Bitmap::<4096>::from_fn(|i| i % 7 == 3 || i > 32000);
+        let mut file = tempfile::tempfile().unwrap();
+        file.write_all(b"header").unwrap();
+        map.write_to(&mut file).unwrap();
+        file.seek(SeekFrom::Start(6)).unwrap();
+        assert_eq!(Bitmap::<4096>::read_from(&mut file).unwrap(), map);
+        let err = Bitmap::<1>::read_from(&mut file).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+    }
+}
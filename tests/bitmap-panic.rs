@@ -12,24 +12,285 @@ mod should_panic {
     #[test]
     #[should_panic]
     fn at_mut_out_of_range() {
-        let _ = (&mut Bitmap::<1>::new()).at_mut(8);
+        let _ = Bitmap::<1>::new().at_mut(8);
     }
 
     #[test]
     #[should_panic]
     fn set_mut_out_of_range() {
-        (&mut Bitmap::<1>::new()).set(8);
+        Bitmap::<1>::new().set(8);
     }
 
     #[test]
     #[should_panic]
     fn reset_mut_out_of_range() {
-        (&mut Bitmap::<1>::new()).reset(8);
+        Bitmap::<1>::new().reset(8);
     }
 
     #[test]
     #[should_panic]
     fn flip_mut_out_of_range() {
-        let _ = (&mut Bitmap::<1>::new()).flip(8);
+        let _ = Bitmap::<1>::new().flip(8);
     }
-}
\ No newline at end of file
+
+    #[test]
+    #[should_panic]
+    fn set_many_out_of_range() {
+        Bitmap::<1>::new().set_many(&[0, 8]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn reset_many_out_of_range() {
+        Bitmap::<1>::new().reset_many(&[8, 0]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_all_of_out_of_range() {
+        Bitmap::<1>::new().set_all().test_all_of(&[0, 8]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_any_of_out_of_range() {
+        Bitmap::<1>::new().test_any_of(&[0, 8]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn swap_first_out_of_range() {
+        Bitmap::<1>::new().swap(8, 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn swap_second_out_of_range() {
+        Bitmap::<1>::new().swap(0, 8);
+    }
+
+    #[test]
+    #[should_panic]
+    fn with_bit_set_out_of_range() {
+        let _ = Bitmap::<1>::new().with_bit_set(8);
+    }
+
+    #[test]
+    #[should_panic]
+    fn he_lang_out_of_range() {
+        let idx = 8;
+        let _ = he_lang!(0 | idx; 8);
+    }
+
+    #[test]
+    #[should_panic]
+    fn bits_out_of_range() {
+        let idx = 8;
+        let _ = bits![0, idx; 8];
+    }
+
+    #[test]
+    #[should_panic]
+    fn set_range_out_of_range() {
+        Bitmap::<1>::new().set_range(4..=8);
+    }
+
+    #[test]
+    #[should_panic]
+    fn set_range_reversed() {
+        #[allow(clippy::reversed_empty_ranges)]
+        Bitmap::<1>::new().set_range(4..2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn concat_wrong_length() {
+        let _ = Bitmap::<1>::new().concat::<4, 2>(&Bitmap::<2>::new());
+    }
+
+    #[test]
+    #[should_panic]
+    fn split_wrong_length() {
+        let _ = Bitmap::<4>::new().split_at_byte::<1, 2>();
+    }
+
+    #[test]
+    #[should_panic]
+    fn copy_bits_src_out_of_range() {
+        let src = Bitmap::<1>::new();
+        Bitmap::<2>::new().copy_bits_from(&src, 4, 0, 5);
+    }
+
+    #[test]
+    #[should_panic]
+    fn copy_bits_dst_out_of_range() {
+        let src = Bitmap::<2>::new();
+        Bitmap::<1>::new().copy_bits_from(&src, 0, 4, 5);
+    }
+
+    #[test]
+    #[should_panic]
+    fn fill_at_straddling_end() {
+        Bitmap::<8>::new().fill_at(5, 0u32);
+    }
+
+    #[test]
+    #[should_panic]
+    fn get_byte_out_of_range() {
+        let _ = Bitmap::<2>::new().get_byte(2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn set_byte_out_of_range() {
+        Bitmap::<2>::new().set_byte(2, 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn get_bits_out_of_range() {
+        let _ = Bitmap::<2>::new().get_bits(12, 5);
+    }
+
+    #[test]
+    #[should_panic]
+    fn get_bits_too_wide() {
+        let _ = Bitmap::<16>::new().get_bits(0, 65);
+    }
+
+    #[test]
+    #[should_panic]
+    fn set_bits_out_of_range() {
+        Bitmap::<2>::new().set_bits(12, 5, 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn from_too_many_bools() {
+        let _ = Bitmap::<1>::from([false; 9]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn to_bool_array_wrong_length() {
+        let _: [bool; 9] = Bitmap::<1>::new().to_bool_array();
+    }
+
+    #[test]
+    #[should_panic]
+    fn init_from_too_long() {
+        let mut buf = [0u8; 4];
+        unsafe { Bitmap::<2>::init_from_at(buf.as_mut_ptr().cast(), &[0; 3]) };
+    }
+
+    #[test]
+    #[should_panic]
+    fn count_range_out_of_range() {
+        let _ = Bitmap::<1>::new().count_range(..9);
+    }
+
+    #[test]
+    #[should_panic]
+    fn index_out_of_range() {
+        let _ = Bitmap::<2>::new()[16];
+    }
+
+    #[test]
+    #[should_panic]
+    fn index_bytes_out_of_range() {
+        let _ = &Bitmap::<2>::new()[1..3];
+    }
+
+    #[test]
+    #[should_panic]
+    fn index_bytes_from_out_of_range() {
+        let _ = &Bitmap::<2>::new()[3..];
+    }
+
+    #[test]
+    #[should_panic(expected = "the index is 16 but the length is 16 bits")]
+    fn bitmap_ref_out_of_range() {
+        let buffer = [0u8; 4];
+        BitmapRef::new(&buffer[1..3]).get_bool(16);
+    }
+
+    #[test]
+    #[should_panic(expected = "BitmapMut: setting out of range, the index is 24 but the length is 24 bits")]
+    fn bitmap_mut_out_of_range() {
+        let mut buffer = [0u8; 4];
+        BitmapMut::new(&mut buffer[1..]).set(24);
+    }
+
+    #[test]
+    #[should_panic(expected = "Bitmap: setting out of range")]
+    fn volatile_out_of_range() {
+        let mut map = Bitmap::<2>::new();
+        VolatileBitmap::new(&mut map).set(16);
+    }
+
+    #[test]
+    #[should_panic(expected = "Bitmap: flipping out of range")]
+    fn volatile_flip_out_of_range() {
+        let mut map = Bitmap::<2>::new();
+        VolatileBitmap::new(&mut map).flip(16);
+    }
+
+    #[cfg(all(target_has_atomic = "8", target_has_atomic = "ptr"))]
+    #[test]
+    #[should_panic(expected = "Bitmap: setting out of range")]
+    fn atomic_out_of_range() {
+        AtomicBitmap::<2>::new().set(16, core::sync::atomic::Ordering::Relaxed);
+    }
+
+    #[cfg(all(target_has_atomic = "8", target_has_atomic = "ptr"))]
+    #[test]
+    #[should_panic(expected = "Bitmap: setting out of range")]
+    fn atomic_word_out_of_range() {
+        let words = std::mem::size_of::<usize>();
+        AtomicBitmap::<17>::new().fetch_or_at(17_usize.div_ceil(words), 1, core::sync::atomic::Ordering::Relaxed);
+    }
+
+    #[test]
+    #[should_panic(expected = "Bitmap: resetting out of range")]
+    fn hier_reset_range_out_of_range() {
+        HierBitmap::<1, 1>::new().reset_range(4..9);
+    }
+
+    #[test]
+    #[should_panic(expected = "Bitmap: seeking out of range")]
+    fn cursor_seek_out_of_range() {
+        BitmapCursor::new(&mut Bitmap::<1>::new()).seek(8);
+    }
+
+    #[test]
+    #[should_panic(expected = "Bitmap: searching out of range")]
+    fn next_zero_wrapping_out_of_range() {
+        let _ = Bitmap::<1>::new().next_zero_wrapping(8);
+    }
+
+    #[test]
+    #[should_panic(expected = "[u8]: setting out of range, the index is 16 but the length is 16 bits")]
+    fn slice_out_of_range() {
+        let mut buffer = [0u8; 4];
+        buffer[1..3].set(16);
+    }
+
+    #[test]
+    #[should_panic(expected = "[u8]: setting out of range, the index is 100 but the length is 96 bits")]
+    fn vec_does_not_grow() {
+        vec![0u8; 12].set(100);
+    }
+
+    #[test]
+    #[should_panic(expected = "DynBitmap: setting out of range, the index is 1000 but the length is 1000 bits")]
+    fn dyn_out_of_range() {
+        DynBitmap::new(1000).set(1000);
+    }
+
+    #[test]
+    #[should_panic(expected = "DynBitmap: length mismatch")]
+    fn dyn_op_length_mismatch() {
+        let mut map = DynBitmap::new(16);
+        map |= &DynBitmap::new(15);
+    }
+}
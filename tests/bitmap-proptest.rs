@@ -0,0 +1,69 @@
+#![allow(clippy::bool_assert_comparison)]
+#![cfg_attr(feature = "bytemuck", allow(clippy::clone_on_copy))]
+
+#[cfg(test)]
+mod property {
+    extern crate cbitmap;
+    use cbitmap::bitmap::strategy::*;
+    use cbitmap::bitmap::*;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn count_ones_and_zeros(map in arb_bitmap::<5>()) {
+            let mut flipped = map.clone();
+            flipped.flip_all();
+            prop_assert_eq!(map.count() + flipped.count(), map.bit_len());
+        }
+
+        #[test]
+        fn double_flip(map in arb_bitmap::<5>(), i in 0usize..40) {
+            let mut other = map.clone();
+            other.flip(i).flip(i);
+            prop_assert_eq!(&other, &map);
+            other.flip_all().flip_all();
+            prop_assert_eq!(other, map);
+        }
+
+        #[test]
+        fn bits_round_trip(map in arb_bitmap::<16>(), offset in 0usize..64, width in 0usize..=64) {
+            let mut other = Bitmap::<16>::new();
+            other.set_bits(offset, width, map.get_bits(offset, width));
+            prop_assert_eq!(other.get_bits(offset, width), map.get_bits(offset, width));
+        }
+
+        #[test]
+        fn dense_and_sparse(dense in arb_bitmap_with_ones::<64>(0.9), sparse in arb_bitmap_with_ones::<64>(0.1)) {
+            // 512 bits each, far from the expected counts of 461 and 51.
+            prop_assert!(dense.count() > 400);
+            prop_assert!(sparse.count() < 112);
+        }
+    }
+
+    #[test]
+    fn extreme_ratios() {
+        use proptest::strategy::ValueTree;
+        use proptest::test_runner::TestRunner;
+
+        let mut runner = TestRunner::deterministic();
+        let full = arb_bitmap_with_ones::<4>(1.0).new_tree(&mut runner).unwrap();
+        assert_eq!(full.current().all(), true);
+        let empty = arb_bitmap_with_ones::<4>(0.0).new_tree(&mut runner).unwrap();
+        assert_eq!(empty.current().none(), true);
+    }
+
+    #[test]
+    fn shrinks_to_empty() {
+        use proptest::test_runner::{TestError, TestRunner};
+
+        let mut runner = TestRunner::deterministic();
+        let res = runner.run(&arb_bitmap::<4>(), |map| {
+            prop_assert!(map.count() > 100);
+            Ok(())
+        });
+        match res {
+            Err(TestError::Fail(_, map)) => assert_eq!(map.none(), true),
+            _ => panic!("expected a failure"),
+        }
+    }
+}
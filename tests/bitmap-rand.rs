@@ -0,0 +1,65 @@
+#![allow(clippy::bool_assert_comparison)]
+
+#[cfg(test)]
+mod random {
+    extern crate cbitmap;
+    use cbitmap::bitmap::*;
+    use rand::{rngs::SmallRng, SeedableRng};
+
+    #[test]
+    fn deterministic() {
+        let a = Bitmap::<64>::random(&mut SmallRng::seed_from_u64(7));
+        let b = Bitmap::<64>::random(&mut SmallRng::seed_from_u64(7));
+        let c = Bitmap::<64>::random(&mut SmallRng::seed_from_u64(8));
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+
+        let mut map = newmap!(;512);
+        map.fill_random(&mut SmallRng::seed_from_u64(7));
+        assert_eq!(map, a);
+        // Roughly half of the 512 bits.
+        assert!((192..320).contains(&map.count()));
+    }
+
+    #[test]
+    fn density() {
+        let mut rng = SmallRng::seed_from_u64(1);
+        for (p, low, high) in [(0.1, 300, 520), (0.5, 1900, 2200), (0.9, 3480, 3700)] {
+            let map = Bitmap::<512>::random_with_density(&mut rng, p);
+            let count = map.count();
+            assert!((low..high).contains(&count), "p = {p}, count = {count}");
+        }
+        assert_eq!(Bitmap::<8>::random_with_density(&mut rng, 0.0).none(), true);
+        assert_eq!(Bitmap::<8>::random_with_density(&mut rng, 1.0).all(), true);
+    }
+
+    #[test]
+    fn random_one() {
+        let mut rng = SmallRng::seed_from_u64(3);
+        let map = he_lang!(0 | 9 | 31 | 63; 64);
+        let mut hits = [0usize; 64];
+        for _ in 0..10_000 {
+            hits[map.random_one(&mut rng).unwrap()] += 1;
+        }
+        for (i, &hit) in hits.iter().enumerate() {
+            match map.test(i) {
+                // 2500 expected, with a standard deviation about 43.
+                true => assert!((2300..2700).contains(&hit), "bit {i}: {hit}"),
+                false => assert_eq!(hit, 0),
+            }
+        }
+
+        assert_eq!(newmap!(;64).random_one(&mut rng), None);
+        assert_eq!(newmap!().random_one(&mut rng), None);
+        let full = Bitmap::<8>::full();
+        for _ in 0..100 {
+            assert!(full.random_one(&mut rng).unwrap() < 64);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn density_out_of_range() {
+        let _ = Bitmap::<8>::random_with_density(&mut SmallRng::seed_from_u64(1), 1.5);
+    }
+}
@@ -0,0 +1,46 @@
+#[cfg(test)]
+mod serialize {
+    extern crate cbitmap;
+    use cbitmap::bitmap::*;
+
+    #[test]
+    fn serde_round_trip() {
+        fn check<const BYTES: usize>(map: Bitmap<BYTES>) {
+            let bytes = postcard::to_allocvec(&map).unwrap();
+            let binary = postcard::from_bytes::<Bitmap<BYTES>>(&bytes).unwrap();
+            assert_eq!(binary, map);
+            let json = serde_json::to_string(&map).unwrap();
+            let readable = serde_json::from_str::<Bitmap<BYTES>>(&json).unwrap();
+            assert_eq!(readable, binary);
+        }
+        check(newmap!());
+        check(newmap!(0b_1001; 8));
+        check(Bitmap::<33>::from_fn(|i| i % 5 == 0));
+        check(Bitmap::<4096>::from_fn(|i| i % 7 == 0));
+
+        // Lengths must match.
+        let bytes = postcard::to_allocvec(&newmap!(;16)).unwrap();
+        let err = postcard::from_bytes::<Bitmap<3>>(&bytes);
+        assert!(err.is_err());
+        let err = serde_json::from_str::<Bitmap<3>>("\"0101\"").unwrap_err();
+        assert!(err.to_string().contains("a binary string of 24 digits"));
+    }
+
+    #[test]
+    fn serde_readable() {
+        let map = he_lang!(0 | 5 | 6 | 15; 16);
+        let json = serde_json::to_string(&map).unwrap();
+        assert_eq!(json, "\"10000000_01100001\"");
+        assert_eq!(serde_json::to_string(&newmap!()).unwrap(), "\"\"");
+
+        // Both a binary string and a list of indexes are accepted.
+        let from_str: Bitmap<2> = serde_json::from_str("\"1000 0000 0110 0001\"").unwrap();
+        let from_list: Bitmap<2> = serde_json::from_str("[15, 0, 6, 5]").unwrap();
+        assert_eq!(from_str, map);
+        assert_eq!(from_list, map);
+
+        assert!(serde_json::from_str::<Bitmap<2>>("[16]").is_err());
+        assert!(serde_json::from_str::<Bitmap<1>>("\"0000_0002\"").is_err());
+        assert!(serde_json::from_str::<Bitmap<1>>("\"1_0000_0000\"").is_err());
+    }
+}
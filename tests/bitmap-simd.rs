@@ -0,0 +1,91 @@
+#![allow(clippy::bool_assert_comparison)]
+#![cfg_attr(feature = "bytemuck", allow(clippy::clone_on_copy))]
+
+#[cfg(test)]
+mod simd {
+    extern crate cbitmap;
+    use cbitmap::bitmap::*;
+    use rand::{rngs::SmallRng, Rng, RngCore, SeedableRng};
+
+    // Lengths around and above the vector path, none a multiple of 16.
+    const LENGTHS: [usize; 8] = [255, 257, 271, 300, 333, 511, 1001, 4099];
+
+    /// Buffers shorter than the vector path, checked one by one, give
+    /// the scalar answers for a whole buffer.
+    const PIECE: usize = 200;
+
+    fn scalar_count(bits: &[u8]) -> usize {
+        bits.chunks(PIECE).map(|piece| BitmapRef::new(piece).count()).sum()
+    }
+
+    fn scalar_find(bits: &[u8], value: bool) -> Option<usize> {
+        bits.chunks(PIECE).enumerate().find_map(|(i, piece)| {
+            let view = BitmapRef::new(piece);
+            let found = match value {
+                true => view.find_first_one(),
+                false => view.find_first_zero(),
+            };
+            found.map(|index| i * PIECE * 8 + index)
+        })
+    }
+
+    fn naive_find(bits: &[u8], value: bool) -> Option<usize> {
+        (0..bits.len() * 8).find(|&i| (bits[i / 8] & (1 << (i % 8)) != 0) == value)
+    }
+
+    #[test]
+    fn count_and_find() {
+        let mut rng = SmallRng::seed_from_u64(11);
+        let mut buffer = vec![0u8; 4099 + 3];
+        for len in LENGTHS {
+            for offset in 0..3 {
+                rng.fill_bytes(&mut buffer);
+                let bits = &mut buffer[offset..offset + len];
+                let view = BitmapRef::new(bits);
+                assert_eq!(view.count(), scalar_count(bits));
+                assert_eq!(view.find_first_one(), scalar_find(bits, true));
+                assert_eq!(view.find_first_zero(), scalar_find(bits, false));
+
+                // A single bit to find, anywhere.
+                let at = rng.random_range(0..len * 8);
+                for value in [true, false] {
+                    bits.fill(if value { 0 } else { 0xff });
+                    bits[at / 8] ^= 1 << (at % 8);
+                    let view = BitmapRef::new(bits);
+                    assert_eq!(view.count(), scalar_count(bits));
+                    assert_eq!(view.find_first_one(), naive_find(bits, true));
+                    assert_eq!(view.find_first_zero(), naive_find(bits, false));
+                }
+
+                // Nothing to find.
+                bits.fill(0);
+                assert_eq!(BitmapRef::new(bits).find_first_one(), None);
+                bits.fill(0xff);
+                assert_eq!(BitmapRef::new(bits).find_first_zero(), None);
+                assert_eq!(BitmapRef::new(bits).count(), len * 8);
+            }
+        }
+    }
+
+    #[test]
+    fn bitmap() {
+        let mut rng = SmallRng::seed_from_u64(13);
+        let mut map = Bitmap::<1001>::new();
+        let mut other = Bitmap::<1001>::new();
+        for _ in 0..32 {
+            map.set(rng.random_range(0..8008));
+            other.set(rng.random_range(0..8008));
+        }
+        let naive = |map: &Bitmap<1001>| (0..8008).filter(|&i| map.test(i)).count();
+        assert_eq!(map.count(), naive(&map));
+        assert_eq!(map.find_first_one(), (0..8008).find(|&i| map.test(i)));
+
+        let mut ored = map.clone();
+        ored |= &other;
+        assert_eq!(ored, Bitmap::<1001>::from_fn(|i| map.test(i) || other.test(i)));
+        let mut anded = map.clone();
+        anded &= &other;
+        assert_eq!(anded, Bitmap::<1001>::from_fn(|i| map.test(i) && other.test(i)));
+        assert_eq!(anded.count(), naive(&anded));
+    }
+}
@@ -0,0 +1,28 @@
+#[cfg(test)]
+mod micro_fmt {
+    extern crate cbitmap;
+    use cbitmap::bitmap::*;
+    use ufmt::uwrite;
+
+    fn check<const BYTES: usize>(map: Bitmap<BYTES>) {
+        let mut s: heapless::String<256> = heapless::String::new();
+        uwrite!(s, "{}", map).unwrap();
+        assert_eq!(s.as_str(), format!("{map}"));
+
+        let mut s: heapless::String<256> = heapless::String::new();
+        uwrite!(s, "{:?}", map).unwrap();
+        assert_eq!(s.as_str(), format!("{map:?}"));
+    }
+
+    #[test]
+    fn same_as_core_fmt() {
+        check(newmap!());
+        check(he_lang!(0 | 7; 8));
+        check(he_lang!(0 | 8 | 15; 16));
+        check(he_lang!(0 | 8 | 23; 24));
+        check(Bitmap::<16>::from_fn(|i| i % 5 == 0));
+        check(Bitmap::<17>::from_fn(|i| i % 5 == 0));
+        check(Bitmap::<256>::from_fn(|i| i % 3 == 0));
+        check(Bitmap::<1024>::new());
+    }
+}